@@ -0,0 +1,281 @@
+//! Reading and writing variable answers.
+//!
+//! `scarff new --vars-file answers.toml` reads values for template variables
+//! from a TOML or JSON file (chosen by extension; anything but `.json` is
+//! TOML). After a successful run the values actually used are written back
+//! to [`ANSWERS_FILE`] in the new project, so the same generation can be
+//! replayed, e.g. in CI:
+//!
+//! ```toml
+//! # Variable answers used by `scarff new`.
+//! # Replay with: scarff new <name> ... --vars-file .scarff-answers.toml
+//! _template = "rust-cli@1.0.0"
+//! _overlays = ["docker", "ci"]
+//! _scope = "acme"
+//!
+//! db = "postgres"
+//! features = ["auth", "metrics"]
+//! ```
+//!
+//! Top-level keys starting with `_` are metadata and never become variables.
+//! `_overlays` lists the overlays (`--with`) the run used and `_scope` its
+//! package scope (`--scope`), so a replay applies them again; `_template`
+//! is informational.
+
+use std::{collections::BTreeMap, fs, path::Path};
+
+use scarff_core::{
+    application::ApplicationError,
+    domain::{RenderValue, TemplateId},
+    error::ScarffResult,
+};
+
+use crate::template_loader::toml_to_render_value;
+
+/// File name the answers are written to inside a generated project.
+pub const ANSWERS_FILE: &str = ".scarff-answers.toml";
+
+/// What a vars or answers file holds.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    /// Variable values.
+    pub values: BTreeMap<String, RenderValue>,
+
+    /// Overlays to layer on, by name, in order (`_overlays`).
+    pub overlays: Vec<String>,
+
+    /// Package scope or module prefix (`_scope`).
+    pub scope: Option<String>,
+}
+
+/// Read variable values, and the overlays and scope to apply, from a TOML
+/// or JSON file.
+///
+/// # Errors
+///
+/// `FilesystemError` if the file cannot be read, `ValidationFailed` if it
+/// does not parse, holds a value with no string form (JSON `null`), or has
+/// an `_overlays` that is not a list of names or a `_scope` that is not a
+/// string.
+pub fn load_answers(path: &Path) -> ScarffResult<Answers> {
+    let text = fs::read_to_string(path).map_err(|e| ApplicationError::FilesystemError {
+        path: path.to_path_buf(),
+        reason: e.to_string(),
+    })?;
+
+    let is_json = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+    let parsed = if is_json {
+        parse_json(&text)
+    } else {
+        parse_toml(&text)
+    };
+
+    parsed.map_err(|reason| {
+        ApplicationError::ValidationFailed(format!("{}: {reason}", path.display())).into()
+    })
+}
+
+/// Format `answers` as the TOML of an [`ANSWERS_FILE`], recording the
+/// template they were used with.
+pub fn answers_toml(template: &TemplateId, answers: &Answers) -> String {
+    let mut meta = toml::Table::new();
+    meta.insert("_template".into(), template.to_string().into());
+    if !answers.overlays.is_empty() {
+        meta.insert("_overlays".into(), answers.overlays.clone().into());
+    }
+    if let Some(scope) = &answers.scope {
+        meta.insert("_scope".into(), scope.clone().into());
+    }
+    let values: toml::Table = answers
+        .values
+        .iter()
+        .map(|(name, value)| (name.clone(), render_value_to_toml(value)))
+        .collect();
+
+    format!(
+        "# Variable answers used by `scarff new`.\n\
+         # Replay with: scarff new <name> ... --vars-file {ANSWERS_FILE}\n\
+         {meta}\n{values}"
+    )
+}
+
+fn parse_toml(text: &str) -> Result<Answers, String> {
+    let table: toml::Table = toml::from_str(text).map_err(|e| e.to_string())?;
+    let values = table
+        .iter()
+        .filter(|(name, _)| !name.starts_with('_'))
+        .map(|(name, value)| {
+            toml_to_render_value(value)
+                .map(|v| (name.clone(), v))
+                .ok_or_else(|| format!("'{name}': unsupported value"))
+        })
+        .collect::<Result<_, _>>()?;
+    let overlays = match table.get("_overlays") {
+        None => Vec::new(),
+        Some(value) => names(
+            value
+                .as_array()
+                .map(|items| items.iter().map(toml::Value::as_str)),
+        )?,
+    };
+    let scope = table.get("_scope").map(|v| scope(v.as_str())).transpose()?;
+    Ok(Answers {
+        values,
+        overlays,
+        scope,
+    })
+}
+
+fn parse_json(text: &str) -> Result<Answers, String> {
+    let object: serde_json::Map<String, serde_json::Value> =
+        serde_json::from_str(text).map_err(|e| e.to_string())?;
+    let values = object
+        .iter()
+        .filter(|(name, _)| !name.starts_with('_'))
+        .map(|(name, value)| {
+            json_to_render_value(value)
+                .map(|v| (name.clone(), v))
+                .ok_or_else(|| format!("'{name}': null is not a value"))
+        })
+        .collect::<Result<_, _>>()?;
+    let overlays = match object.get("_overlays") {
+        None => Vec::new(),
+        Some(value) => names(
+            value
+                .as_array()
+                .map(|items| items.iter().map(serde_json::Value::as_str)),
+        )?,
+    };
+    let scope = object
+        .get("_scope")
+        .map(|v| scope(v.as_str()))
+        .transpose()?;
+    Ok(Answers {
+        values,
+        overlays,
+        scope,
+    })
+}
+
+/// The `_overlays` list: `None` when it is not a list, an inner `None` for
+/// an item that is not a string.
+fn names<'a>(items: Option<impl Iterator<Item = Option<&'a str>>>) -> Result<Vec<String>, String> {
+    items
+        .and_then(|items| items.map(|item| item.map(str::to_string)).collect())
+        .ok_or_else(|| "'_overlays' must be a list of overlay names".to_string())
+}
+
+/// The `_scope` string; `None` when it is not one.
+fn scope(value: Option<&str>) -> Result<String, String> {
+    value
+        .map(str::to_string)
+        .ok_or_else(|| "'_scope' must be a string".to_string())
+}
+
+/// JSON counterpart of [`toml_to_render_value`]; `None` for `null`.
+fn json_to_render_value(value: &serde_json::Value) -> Option<RenderValue> {
+    use serde_json::Value;
+
+    Some(match value {
+        Value::Null => return None,
+        Value::String(s) => RenderValue::String(s.clone()),
+        Value::Bool(b) => RenderValue::String(b.to_string()),
+        Value::Number(n) => RenderValue::String(n.to_string()),
+        Value::Array(items) => RenderValue::List(
+            items
+                .iter()
+                .map(json_to_render_value)
+                .collect::<Option<_>>()?,
+        ),
+        Value::Object(entries) => RenderValue::Map(
+            entries
+                .iter()
+                .map(|(k, v)| json_to_render_value(v).map(|v| (k.clone(), v)))
+                .collect::<Option<_>>()?,
+        ),
+    })
+}
+
+fn render_value_to_toml(value: &RenderValue) -> toml::Value {
+    match value {
+        RenderValue::String(s) => toml::Value::String(s.clone()),
+        RenderValue::List(items) => {
+            toml::Value::Array(items.iter().map(render_value_to_toml).collect())
+        }
+        RenderValue::Map(entries) => toml::Value::Table(
+            entries
+                .iter()
+                .map(|(k, v)| (k.clone(), render_value_to_toml(v)))
+                .collect(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn written_answers_read_back_unchanged() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(ANSWERS_FILE);
+        let answers = BTreeMap::from([
+            ("db".to_string(), RenderValue::from("postgres")),
+            (
+                "features".to_string(),
+                RenderValue::from(vec!["auth", "metrics"]),
+            ),
+            (
+                "owner".to_string(),
+                RenderValue::from(BTreeMap::from([("name".to_string(), "Ada \"L\"")])),
+            ),
+        ]);
+
+        let answers = Answers {
+            values: answers,
+            overlays: vec!["docker".into(), "ci".into()],
+            scope: Some("acme".into()),
+        };
+
+        let text = answers_toml(&TemplateId::new("rust-cli", "1.0.0"), &answers);
+        fs::write(&path, &text).unwrap();
+
+        assert!(text.contains("_template = \"rust-cli@1.0.0\""), "{text}");
+        assert!(text.contains("_overlays = [\"docker\", \"ci\"]"), "{text}");
+        assert!(text.contains("_scope = \"acme\""), "{text}");
+        assert_eq!(load_answers(&path).unwrap(), answers);
+    }
+
+    #[test]
+    fn json_scalars_become_strings() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("vars.json");
+        fs::write(
+            &path,
+            r#"{"_template": "x@1", "port": 8080, "tls": true, "tags": ["a", 1]}"#,
+        )
+        .unwrap();
+
+        let answers = load_answers(&path).unwrap().values;
+
+        assert_eq!(answers.len(), 3);
+        assert_eq!(answers["port"], RenderValue::from("8080"));
+        assert_eq!(answers["tls"], RenderValue::from("true"));
+        assert_eq!(answers["tags"], RenderValue::from(vec!["a", "1"]));
+
+        fs::write(&path, r#"{"db": null}"#).unwrap();
+        let err = load_answers(&path).unwrap_err().to_string();
+        assert!(err.contains("'db'"), "{err}");
+
+        fs::write(&path, r#"{"_overlays": ["docker", 1]}"#).unwrap();
+        let err = load_answers(&path).unwrap_err().to_string();
+        assert!(err.contains("'_overlays'"), "{err}");
+
+        fs::write(&path, r#"{"_scope": ["acme"]}"#).unwrap();
+        let err = load_answers(&path).unwrap_err().to_string();
+        assert!(err.contains("'_scope'"), "{err}");
+    }
+}
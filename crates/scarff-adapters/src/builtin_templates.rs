@@ -45,7 +45,7 @@
 // ! // Serialise t and write to ./templates/rust-cli-default/template.toml
 // ! ```
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use tracing::{debug, info, instrument, warn};
 
@@ -76,6 +76,48 @@ use crate::template_loader::FilesystemTemplateLoader;
 /// - `WARN`  — if no directory was found, or an individual template failed.
 #[instrument]
 pub fn all_templates() -> Result<Vec<Template>, DomainError> {
+    Ok(discover()?.map(|found| found.templates).unwrap_or_default())
+}
+
+/// A templates directory found by [`discover`], with the templates loaded
+/// from it.
+#[derive(Debug)]
+pub struct Discovered {
+    /// The directory. `file:` and `workspace:` external content is resolved
+    /// relative to it (see
+    /// [`ContentResolvers::standard`](crate::content::ContentResolvers::standard)).
+    pub root: PathBuf,
+
+    /// Every valid template under `root`.
+    pub templates: Vec<Template>,
+}
+
+impl Discovered {
+    /// Whether these are the built-in templates (see [`is_bundled`]).
+    pub fn is_bundled(&self) -> bool {
+        is_bundled(&self.root)
+    }
+}
+
+/// Whether `root` is the templates directory installed next to the `scarff`
+/// executable: the built-in set, as opposed to a collection picked with
+/// `$SCARFF_TEMPLATES_DIR` or found relative to the working directory.
+///
+/// Only built-in templates may run hooks without the user trusting them.
+pub fn is_bundled(root: &Path) -> bool {
+    let same = |a: &Path, b: &Path| match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    };
+    exe_sibling_templates().is_some_and(|bundled| same(&bundled, root))
+}
+
+/// First candidate directory holding at least one template, with its
+/// templates, or `None` (see [`all_templates`] for the errors).
+///
+/// Callers that need the directory as well as the templates should build
+/// both from one call, so they cannot come from different scans.
+pub fn discover() -> Result<Option<Discovered>, DomainError> {
     for candidate in candidate_paths() {
         debug!(path = %candidate.display(), "checking candidate templates path");
 
@@ -100,14 +142,17 @@ pub fn all_templates() -> Result<Vec<Template>, DomainError> {
             count = templates.len(),
             "templates loaded successfully"
         );
-        return Ok(templates);
+        return Ok(Some(Discovered {
+            root: candidate,
+            templates,
+        }));
     }
 
     warn!(
         "no templates directory found; checked $SCARFF_TEMPLATES_DIR, \
          ./templates, <exe>/templates, and ../templates"
     );
-    Ok(vec![])
+    Ok(None)
 }
 
 // ── Resolution helpers ────────────────────────────────────────────────────────
@@ -166,15 +211,15 @@ fn exe_sibling_templates() -> Option<PathBuf> {
 pub mod legacy_hardcoded {
     use scarff_core::domain::{
         Architecture, DirectorySpec, FileSpec, Framework, Language, ProjectKind, PythonFramework,
-        RustFramework, TargetMatcher, Template, TemplateContent, TemplateId, TemplateMetadata,
-        TemplateNode, TemplateSource, TemplateTree, TypeScriptFramework,
+        RenderEngine, RustFramework, TargetMatcher, Template, TemplateContent, TemplateId,
+        TemplateMetadata, TemplateNode, TemplateSource, TemplateTree, TypeScriptFramework,
     };
 
     /// Minimal Rust CLI template — no framework, no architecture opinion.
     ///
     /// Generated files:
     /// - `src/main.rs` — `fn main()` with a `{{PROJECT_NAME}}` placeholder.
-    /// - `Cargo.toml` — package manifest with `{{PROJECT_NAME_KEBAB}}`.
+    /// - `Cargo.toml` — package manifest named `{{PACKAGE_NAME}}`.
     pub fn rust_cli_default() -> Template {
         Template {
             id: TemplateId::new("rust-cli-default", "1.0.0"),
@@ -196,16 +241,23 @@ pub mod legacy_hardcoded {
                 .with_node(TemplateNode::Directory(DirectorySpec::new("src")))
                 .with_node(TemplateNode::File(FileSpec::new(
                     "src/main.rs",
-                    TemplateContent::Parameterized(TemplateSource::Static(
+                    TemplateContent::Parameterized(TemplateSource::from(
                         "fn main() {\n    println!(\"Hello, {{PROJECT_NAME}}!\");\n}\n",
                     )),
                 )))
                 .with_node(TemplateNode::File(FileSpec::new(
                     "Cargo.toml",
-                    TemplateContent::Parameterized(TemplateSource::Static(
-                        "[package]\nname = \"{{PROJECT_NAME_KEBAB}}\"\nversion = \"0.1.0\"\nedition = \"2024\"\n\n[dependencies]\n",
+                    TemplateContent::Parameterized(TemplateSource::from(
+                        "[package]\nname = \"{{PACKAGE_NAME}}\"\nversion = \"0.1.0\"\nedition = \"2024\"\n\n[dependencies]\n",
                     )),
                 ))),
+            strict: true,
+            engine: RenderEngine::Simple,
+            variables: Vec::new(),
+            format: Vec::new(),
+            extends: None,
+            overlay: None,
+            post_hooks: Vec::new(),
         }
     }
 
@@ -227,16 +279,23 @@ pub mod legacy_hardcoded {
                 .with_node(TemplateNode::Directory(DirectorySpec::new("src")))
                 .with_node(TemplateNode::File(FileSpec::new(
                     "Cargo.toml",
-                    TemplateContent::Parameterized(TemplateSource::Static(
-                        "[package]\nname = \"{{PROJECT_NAME_KEBAB}}\"\nversion = \"0.1.0\"\nedition = \"2024\"\n\n[dependencies]\naxum = \"0.7\"\ntokio = { version = \"1\", features = [\"full\"] }\n",
+                    TemplateContent::Parameterized(TemplateSource::from(
+                        "[package]\nname = \"{{PACKAGE_NAME}}\"\nversion = \"0.1.0\"\nedition = \"2024\"\n\n[dependencies]\naxum = \"0.7\"\ntokio = { version = \"1\", features = [\"full\"] }\n",
                     )),
                 )))
                 .with_node(TemplateNode::File(FileSpec::new(
                     "src/main.rs",
-                    TemplateContent::Parameterized(TemplateSource::Static(
+                    TemplateContent::Parameterized(TemplateSource::from(
                         "#[tokio::main]\nasync fn main() {\n    println!(\"{{PROJECT_NAME}} starting\");\n}\n",
                     )),
                 ))),
+            strict: true,
+            engine: RenderEngine::Simple,
+            variables: Vec::new(),
+            format: Vec::new(),
+            extends: None,
+            overlay: None,
+            post_hooks: Vec::new(),
         }
     }
 
@@ -258,16 +317,23 @@ pub mod legacy_hardcoded {
                 .with_node(TemplateNode::Directory(DirectorySpec::new("src")))
                 .with_node(TemplateNode::File(FileSpec::new(
                     "pyproject.toml",
-                    TemplateContent::Parameterized(TemplateSource::Static(
-                        "[project]\nname = \"{{PROJECT_NAME_KEBAB}}\"\nversion = \"0.1.0\"\n\n[dependencies]\nfastapi = \">=0.100\"\nuvicorn = {extras=[\"standard\"]}\n",
+                    TemplateContent::Parameterized(TemplateSource::from(
+                        "[project]\nname = \"{{PACKAGE_NAME}}\"\nversion = \"0.1.0\"\n\n[dependencies]\nfastapi = \">=0.100\"\nuvicorn = {extras=[\"standard\"]}\n",
                     )),
                 )))
                 .with_node(TemplateNode::File(FileSpec::new(
                     "src/main.py",
-                    TemplateContent::Parameterized(TemplateSource::Static(
+                    TemplateContent::Parameterized(TemplateSource::from(
                         "from fastapi import FastAPI\n\napp = FastAPI(title=\"{{PROJECT_NAME}}\")\n\n@app.get(\"/\")\ndef root():\n    return {\"app\": \"{{PROJECT_NAME}}\"}\n",
                     )),
                 ))),
+            strict: true,
+            engine: RenderEngine::Simple,
+            variables: Vec::new(),
+            format: Vec::new(),
+            extends: None,
+            overlay: None,
+            post_hooks: Vec::new(),
         }
     }
 
@@ -289,16 +355,23 @@ pub mod legacy_hardcoded {
                 .with_node(TemplateNode::Directory(DirectorySpec::new("src")))
                 .with_node(TemplateNode::File(FileSpec::new(
                     "package.json",
-                    TemplateContent::Parameterized(TemplateSource::Static(
-                        "{\n  \"name\": \"{{PROJECT_NAME_KEBAB}}\",\n  \"version\": \"0.1.0\",\n  \"scripts\": { \"dev\": \"vite\", \"build\": \"tsc && vite build\" },\n  \"dependencies\": { \"react\": \"^18\", \"react-dom\": \"^18\" },\n  \"devDependencies\": { \"typescript\": \"^5\", \"vite\": \"^5\" }\n}\n",
+                    TemplateContent::Parameterized(TemplateSource::from(
+                        "{\n  \"name\": \"{{PACKAGE_NAME}}\",\n  \"version\": \"0.1.0\",\n  \"scripts\": { \"dev\": \"vite\", \"build\": \"tsc && vite build\" },\n  \"dependencies\": { \"react\": \"^18\", \"react-dom\": \"^18\" },\n  \"devDependencies\": { \"typescript\": \"^5\", \"vite\": \"^5\" }\n}\n",
                     )),
                 )))
                 .with_node(TemplateNode::File(FileSpec::new(
                     "src/App.tsx",
-                    TemplateContent::Parameterized(TemplateSource::Static(
+                    TemplateContent::Parameterized(TemplateSource::from(
                         "export default function App() {\n  return <h1>{{PROJECT_NAME}}</h1>;\n}\n",
                     )),
                 ))),
+            strict: true,
+            engine: RenderEngine::Simple,
+            variables: Vec::new(),
+            format: Vec::new(),
+            extends: None,
+            overlay: None,
+            post_hooks: Vec::new(),
         }
     }
 }
@@ -342,6 +415,15 @@ name = "Test"
         fs::write(slot.join("template.toml"), MINIMAL_MANIFEST).unwrap();
     }
 
+    // ── is_bundled ────────────────────────────────────────────────────────
+
+    #[test]
+    fn only_the_directory_beside_the_executable_is_bundled() {
+        let elsewhere = tempfile::TempDir::new().unwrap();
+        assert!(!is_bundled(elsewhere.path()));
+        assert!(!is_bundled(Path::new("templates/does-not-exist")));
+    }
+
     // ── candidate_paths ───────────────────────────────────────────────────
 
     #[test]
@@ -0,0 +1,83 @@
+//! Snippets compiled into the scarff binary (`builtin:` scheme).
+
+use scarff_core::{
+    application::ports::ContentResolver,
+    domain::{ContentTemplateId, TemplateSource},
+    error::ScarffResult,
+};
+
+use super::unresolvable;
+
+/// Embedded snippets by name.
+const SNIPPETS: &[(&str, &str)] = &[
+    ("mit", include_str!("snippets/mit.txt")),
+    ("editorconfig", include_str!("snippets/editorconfig.txt")),
+    (
+        "gitignore-rust",
+        include_str!("snippets/gitignore-rust.txt"),
+    ),
+    (
+        "gitignore-python",
+        include_str!("snippets/gitignore-python.txt"),
+    ),
+    (
+        "gitignore-node",
+        include_str!("snippets/gitignore-node.txt"),
+    ),
+    ("gitignore-go", include_str!("snippets/gitignore-go.txt")),
+];
+
+/// Resolves `builtin:<name>` to a snippet shipped with scarff.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BuiltinResolver;
+
+impl BuiltinResolver {
+    /// Create the resolver.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Names of all embedded snippets.
+    pub fn names() -> impl Iterator<Item = &'static str> {
+        SNIPPETS.iter().map(|(name, _)| *name)
+    }
+}
+
+impl ContentResolver for BuiltinResolver {
+    fn scheme(&self) -> &str {
+        "builtin"
+    }
+
+    fn resolve(&self, id: &ContentTemplateId) -> ScarffResult<TemplateSource> {
+        SNIPPETS
+            .iter()
+            .find(|(name, _)| *name == id.key())
+            .map(|(_, text)| TemplateSource::from(*text))
+            .ok_or_else(|| {
+                let names: Vec<&str> = Self::names().collect();
+                unresolvable(
+                    id,
+                    format!(
+                        "no built-in snippet named '{}' (available: {})",
+                        id.key(),
+                        names.join(", ")
+                    ),
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_snippet_lists_available_names() {
+        let err = BuiltinResolver::new()
+            .resolve(&ContentTemplateId("builtin:gpl"))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("'gpl'"), "{err}");
+        assert!(err.contains("gitignore-rust"), "{err}");
+    }
+}
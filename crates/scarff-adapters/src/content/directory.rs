@@ -0,0 +1,92 @@
+//! Content read from a directory on disk (`file:` and `workspace:` schemes).
+
+use std::{
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+use scarff_core::{
+    application::ports::ContentResolver,
+    domain::{ContentTemplateId, TemplateSource},
+    error::ScarffResult,
+};
+
+use super::unresolvable;
+
+/// Resolves `<scheme>:<relative/path>` to a file under `root`.
+///
+/// Keys must be relative and may not leave `root` via `..`.
+#[derive(Debug, Clone)]
+pub struct DirectoryResolver {
+    scheme: String,
+    root: PathBuf,
+}
+
+impl DirectoryResolver {
+    /// Serve `scheme:` ids from files under `root`.
+    pub fn new(scheme: impl Into<String>, root: impl Into<PathBuf>) -> Self {
+        Self {
+            scheme: scheme.into(),
+            root: root.into(),
+        }
+    }
+
+    /// Directory the keys are relative to.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+}
+
+impl ContentResolver for DirectoryResolver {
+    fn scheme(&self) -> &str {
+        &self.scheme
+    }
+
+    fn resolve(&self, id: &ContentTemplateId) -> ScarffResult<TemplateSource> {
+        let key = Path::new(id.key());
+        if key.as_os_str().is_empty()
+            || !key
+                .components()
+                .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+        {
+            return Err(unresolvable(
+                id,
+                "path must be relative and stay inside its root",
+            ));
+        }
+
+        let path = self.root.join(key);
+        fs::read_to_string(&path)
+            .map(TemplateSource::from)
+            .map_err(|e| unresolvable(id, format!("{}: {e}", path.display())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn rejects_paths_escaping_the_root() {
+        let root = TempDir::new().unwrap();
+        let resolver = DirectoryResolver::new("file", root.path().join("inner"));
+
+        for id in ["file:../secret", "file:/etc/passwd", "file:"] {
+            assert!(
+                resolver.resolve(&ContentTemplateId(id)).is_err(),
+                "{id} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn missing_file_names_the_path() {
+        let root = TempDir::new().unwrap();
+        let err = DirectoryResolver::new("file", root.path())
+            .resolve(&ContentTemplateId("file:nope.txt"))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("nope.txt"), "{err}");
+    }
+}
@@ -0,0 +1,160 @@
+//! Resolvers for `TemplateContent::External` references.
+//!
+//! A manifest entry such as
+//!
+//! ```toml
+//! [[files]]
+//! path        = "LICENSE"
+//! type        = "external"
+//! external_id = "builtin:mit"
+//! ```
+//!
+//! is resolved at render time by the resolver registered for the id's scheme:
+//!
+//! | Scheme | Resolver | Source |
+//! |--------|----------|--------|
+//! | `builtin:` | [`BuiltinResolver`] | snippets embedded in the binary |
+//! | `file:` | [`DirectoryResolver`] | files relative to the templates root |
+//! | `workspace:` | [`DirectoryResolver`] | shared files in `<templates root>/_shared/` |
+//!
+//! The resolved text is then rendered with the same context as any
+//! parameterized file.
+
+mod builtin;
+mod directory;
+
+use std::{path::Path, sync::Arc};
+
+use scarff_core::{
+    application::{ApplicationError, ports::ContentResolver},
+    domain::{ContentTemplateId, TemplateSource},
+    error::ScarffResult,
+};
+
+pub use builtin::BuiltinResolver;
+pub use directory::DirectoryResolver;
+
+/// Directory under the templates root served by the `workspace:` scheme.
+pub const SHARED_DIR: &str = "_shared";
+
+/// Registry of [`ContentResolver`]s keyed by scheme.
+///
+/// Cheap to clone; renderers share one registry.
+#[derive(Clone)]
+pub struct ContentResolvers {
+    resolvers: Vec<Arc<dyn ContentResolver>>,
+}
+
+impl ContentResolvers {
+    /// An empty registry; every external id fails to resolve.
+    pub fn empty() -> Self {
+        Self {
+            resolvers: Vec::new(),
+        }
+    }
+
+    /// Only the `builtin:` resolver. Used when no templates root is known.
+    pub fn builtin() -> Self {
+        Self::empty().with(BuiltinResolver::new())
+    }
+
+    /// `builtin:`, plus `file:` and `workspace:` rooted at `templates_root`.
+    pub fn standard(templates_root: impl AsRef<Path>) -> Self {
+        let root = templates_root.as_ref();
+        Self::builtin()
+            .with(DirectoryResolver::new("file", root))
+            .with(DirectoryResolver::new("workspace", root.join(SHARED_DIR)))
+    }
+
+    /// Register `resolver`, replacing any resolver for the same scheme.
+    pub fn with(mut self, resolver: impl ContentResolver + 'static) -> Self {
+        self.resolvers.retain(|r| r.scheme() != resolver.scheme());
+        self.resolvers.push(Arc::new(resolver));
+        self
+    }
+
+    /// Resolve `id` with the resolver registered for its scheme.
+    pub fn resolve(&self, id: &ContentTemplateId) -> ScarffResult<TemplateSource> {
+        let Some(scheme) = id.scheme() else {
+            return Err(unresolvable(
+                id,
+                "expected '<scheme>:<name>', e.g. 'builtin:mit'",
+            ));
+        };
+
+        match self.resolvers.iter().find(|r| r.scheme() == scheme) {
+            Some(resolver) => resolver.resolve(id),
+            None => {
+                let known: Vec<String> = self
+                    .resolvers
+                    .iter()
+                    .map(|r| format!("{}:", r.scheme()))
+                    .collect();
+                Err(unresolvable(
+                    id,
+                    format!(
+                        "no resolver for scheme '{scheme}:' (available: {})",
+                        known.join(", ")
+                    ),
+                ))
+            }
+        }
+    }
+}
+
+impl Default for ContentResolvers {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+pub(crate) fn unresolvable(
+    id: &ContentTemplateId,
+    reason: impl Into<String>,
+) -> scarff_core::error::ScarffError {
+    ApplicationError::ContentResolution {
+        id: id.0.to_string(),
+        reason: reason.into(),
+    }
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn dispatches_on_scheme() {
+        let root = TempDir::new().unwrap();
+        fs::create_dir_all(root.path().join("_shared")).unwrap();
+        fs::write(root.path().join("notice.txt"), "from file").unwrap();
+        fs::write(root.path().join("_shared/readme.md"), "from workspace").unwrap();
+
+        let resolvers = ContentResolvers::standard(root.path());
+        let resolve = |id: &'static str| {
+            resolvers
+                .resolve(&ContentTemplateId(id))
+                .map(|s| s.as_str().to_string())
+        };
+
+        assert!(resolve("builtin:mit").unwrap().starts_with("MIT License"));
+        assert_eq!(resolve("file:notice.txt").unwrap(), "from file");
+        assert_eq!(resolve("workspace:readme.md").unwrap(), "from workspace");
+    }
+
+    #[test]
+    fn unknown_or_missing_scheme_is_reported() {
+        let resolvers = ContentResolvers::builtin();
+
+        let err = resolvers
+            .resolve(&ContentTemplateId("remote:x"))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("no resolver for scheme 'remote:'"), "{err}");
+        assert!(err.contains("builtin:"), "{err}");
+
+        assert!(resolvers.resolve(&ContentTemplateId("mit")).is_err());
+    }
+}
@@ -27,7 +27,7 @@ impl Filesystem for LocalFilesystem {
         std::fs::create_dir_all(path).map_err(|e| map_io_error(path, e, "create directory"))
     }
 
-    fn write_file(&self, path: &Path, content: &str) -> ScarffResult<()> {
+    fn write_file(&self, path: &Path, content: &[u8]) -> ScarffResult<()> {
         std::fs::write(path, content).map_err(|e| map_io_error(path, e, "write file"))
     }
 
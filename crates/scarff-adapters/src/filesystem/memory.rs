@@ -16,7 +16,7 @@ pub struct MemoryFilesystem {
 
 #[derive(Debug, Default)]
 struct MemoryFilesystemInner {
-    files: HashMap<PathBuf, String>,
+    files: HashMap<PathBuf, Vec<u8>>,
     directories: HashSet<PathBuf>,
     executables: HashSet<PathBuf>,
 }
@@ -29,8 +29,14 @@ impl MemoryFilesystem {
         }
     }
 
-    /// Read a file's content (testing helper).
+    /// Read a text file's content (testing helper). `None` if the file is
+    /// missing or not UTF-8.
     pub fn read_file(&self, path: &Path) -> Option<String> {
+        String::from_utf8(self.read_bytes(path)?).ok()
+    }
+
+    /// Read a file's raw bytes (testing helper).
+    pub fn read_bytes(&self, path: &Path) -> Option<Vec<u8>> {
         let inner = self.inner.read().ok()?;
         inner.files.get(path).cloned()
     }
@@ -78,7 +84,7 @@ impl Filesystem for MemoryFilesystem {
         Ok(())
     }
 
-    fn write_file(&self, path: &Path, content: &str) -> scarff_core::error::ScarffResult<()> {
+    fn write_file(&self, path: &Path, content: &[u8]) -> scarff_core::error::ScarffResult<()> {
         let mut inner = self
             .inner
             .write()
@@ -97,7 +103,7 @@ impl Filesystem for MemoryFilesystem {
             }
         }
 
-        inner.files.insert(path.to_path_buf(), content.to_string());
+        inner.files.insert(path.to_path_buf(), content.to_vec());
         Ok(())
     }
 
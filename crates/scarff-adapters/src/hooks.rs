@@ -0,0 +1,178 @@
+//! [`HookRunner`] that runs post-generation hooks as child processes.
+
+use std::{
+    io::{BufRead, BufReader, Read},
+    path::{MAIN_SEPARATOR, Path, PathBuf},
+    process::{Command, Stdio},
+    sync::mpsc,
+    thread,
+};
+
+use scarff_core::{
+    application::{ApplicationError, ports::HookRunner},
+    domain::Hook,
+    error::ScarffResult,
+};
+
+/// Runs each hook as a child process with stdin closed, forwarding its
+/// stdout and stderr, interleaved as they arrive, one line at a time.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProcessHookRunner;
+
+impl ProcessHookRunner {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl HookRunner for ProcessHookRunner {
+    fn run(&self, hook: &Hook, dir: &Path, output: &mut dyn FnMut(&str)) -> ScarffResult<()> {
+        let failed = |reason: String| ApplicationError::HookFailed {
+            command: hook.to_string(),
+            reason,
+        };
+
+        let mut child = Command::new(program(&hook.command, dir))
+            .args(&hook.args)
+            .current_dir(dir)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| failed(format!("cannot start '{}': {e}", hook.command)))?;
+
+        let (lines, received) = mpsc::channel();
+        let readers: Vec<_> = [
+            child
+                .stdout
+                .take()
+                .map(|s| Box::new(s) as Box<dyn Read + Send>),
+            child
+                .stderr
+                .take()
+                .map(|s| Box::new(s) as Box<dyn Read + Send>),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|stream| {
+            let lines = lines.clone();
+            thread::spawn(move || forward_lines(stream, &lines))
+        })
+        .collect();
+        // The loop below ends once both readers have dropped their senders.
+        drop(lines);
+
+        for line in received {
+            output(&line);
+        }
+        for reader in readers {
+            let _ = reader.join();
+        }
+
+        let status = child
+            .wait()
+            .map_err(|e| failed(format!("cannot wait for it: {e}")))?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(failed(match status.code() {
+                Some(code) => format!("exited with status {code}"),
+                None => "terminated by a signal".into(),
+            })
+            .into())
+        }
+    }
+}
+
+/// The program to start for `command`. A relative path such as
+/// `./scripts/setup.sh` is joined with `dir`: `Command` leaves unspecified
+/// whether it is resolved before or after changing directory. Bare names
+/// are looked up on `PATH`.
+fn program(command: &str, dir: &Path) -> PathBuf {
+    let path = Path::new(command);
+    if path.is_relative() && command.contains(['/', MAIN_SEPARATOR]) {
+        dir.join(path)
+    } else {
+        path.to_path_buf()
+    }
+}
+
+/// Send every line of `stream`, without its terminator, until it closes.
+/// Output that is not UTF-8 is converted lossily.
+fn forward_lines(stream: impl Read, lines: &mpsc::Sender<String>) {
+    let mut reader = BufReader::new(stream);
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        match reader.read_until(b'\n', &mut buf) {
+            Ok(0) | Err(_) => return,
+            Ok(_) => {
+                let line = String::from_utf8_lossy(&buf);
+                let line = line.trim_end_matches(['\r', '\n']).to_string();
+                if lines.send(line).is_err() {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn run(hook: &Hook, dir: &Path) -> (ScarffResult<()>, Vec<String>) {
+        let mut lines = Vec::new();
+        let result = ProcessHookRunner.run(hook, dir, &mut |line| lines.push(line.to_string()));
+        (result, lines)
+    }
+
+    #[test]
+    fn output_of_both_streams_is_forwarded() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("marker"), "").unwrap();
+        let hook = Hook::new("sh").args(["-c", "ls; echo to-stderr >&2"]);
+
+        let (result, mut lines) = run(&hook, dir.path());
+        result.unwrap();
+        lines.sort();
+        assert_eq!(lines, ["marker", "to-stderr"]);
+    }
+
+    #[test]
+    fn relative_commands_run_from_the_project() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = TempDir::new().unwrap();
+        let script = dir.path().join("scripts/setup.sh");
+        std::fs::create_dir_all(script.parent().unwrap()).unwrap();
+        std::fs::write(&script, "#!/bin/sh\necho set up\n").unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let (result, lines) = run(&Hook::new("./scripts/setup.sh"), dir.path());
+        result.unwrap();
+        assert_eq!(lines, ["set up"]);
+    }
+
+    #[test]
+    fn failures_name_the_command() {
+        let dir = TempDir::new().unwrap();
+
+        let (result, lines) = run(
+            &Hook::new("sh").args(["-c", "echo oops; exit 3"]),
+            dir.path(),
+        );
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("`sh -c 'echo oops; exit 3'`"), "{err}");
+        assert!(err.contains("exited with status 3"), "{err}");
+        assert_eq!(lines, ["oops"]);
+
+        let (result, _) = run(&Hook::new("scarff-no-such-command"), dir.path());
+        let err = result.unwrap_err().to_string();
+        assert!(
+            err.contains("cannot start 'scarff-no-such-command'"),
+            "{err}"
+        );
+    }
+}
@@ -3,13 +3,22 @@
 //! This crate implements the ports defined in `scarff-core::application::ports`.
 //! It contains all external dependencies and I/O operations.
 
+pub mod answers;
 pub mod builtin_templates;
+pub mod content;
 pub mod filesystem;
+pub mod hooks;
+pub mod merge;
+pub mod post_process;
 pub mod renderer;
+pub mod run_info;
 pub mod template_loader;
 pub mod template_store;
 
 // Re-export commonly used adapters
+pub use content::ContentResolvers;
 pub use filesystem::{LocalFilesystem, MemoryFilesystem};
-pub use renderer::SimpleRenderer;
+pub use hooks::ProcessHookRunner;
+pub use renderer::{EngineRenderer, SimpleRenderer};
+pub use run_info::SystemRunInfo;
 pub use template_store::InMemoryStore;
@@ -0,0 +1,771 @@
+//! Built-in [`FileMerger`]s, for overlays that declare `strategy = "merge"`
+//! on a file the project already has.
+//!
+//! | Merger | Format | Typical files |
+//! |--------|--------|---------------|
+//! | [`TomlMerger`] | `toml` | `Cargo.toml`, `pyproject.toml` |
+//! | [`JsonMerger`] | `json` | `package.json`, `tsconfig.json` |
+//! | [`LineSetMerger`] | `lines` | `.gitignore`, `.dockerignore` |
+//! | [`DotenvMerger`] | `dotenv` | `.env`, `.env.example` |
+//!
+//! The existing file keeps its order, comments and layout; whatever the
+//! overlay adds comes after what is already there. Where both set the same
+//! scalar value, the overlay's wins.
+
+use std::{collections::HashSet, fmt, ops::Range};
+
+use scarff_core::{
+    application::{ApplicationError, ports::FileMerger},
+    domain::MergeFormat,
+    error::{ScarffError, ScarffResult},
+};
+use toml_edit::{DocumentMut, Item, Table, TableLike, Value};
+
+/// One merger per [`MergeFormat`].
+pub fn builtin_mergers() -> Vec<Box<dyn FileMerger>> {
+    vec![
+        Box::new(TomlMerger),
+        Box::new(JsonMerger),
+        Box::new(LineSetMerger),
+        Box::new(DotenvMerger),
+    ]
+}
+
+fn unparsable(side: &str, format: &str, error: impl fmt::Display) -> ScarffError {
+    ApplicationError::RenderingFailed {
+        reason: format!("the {side} file is not valid {format}: {error}"),
+    }
+    .into()
+}
+
+/// Line terminator of `text`: `\r\n` if it uses any, `\n` otherwise.
+fn line_ending(text: &str) -> &'static str {
+    if text.contains("\r\n") { "\r\n" } else { "\n" }
+}
+
+/// `base` followed by `added` lines, separated from it by a blank line.
+fn append_block(base: &str, added: &[&str]) -> String {
+    if added.is_empty() {
+        return base.to_string();
+    }
+    let eol = line_ending(base);
+    let mut out = base.to_string();
+    if !out.trim().is_empty() {
+        if !out.ends_with('\n') {
+            out.push_str(eol);
+        }
+        if !out.trim_end_matches(['\r', '\n']).is_empty() && !out.ends_with(&format!("{eol}{eol}"))
+        {
+            out.push_str(eol);
+        }
+    } else {
+        out.clear();
+    }
+    for line in added {
+        out.push_str(line);
+        out.push_str(eol);
+    }
+    out
+}
+
+// ── TOML ──────────────────────────────────────────────────────────────────────
+
+/// Deep-merges TOML documents with `toml_edit`, so comments, key order and
+/// formatting of the existing file survive.
+///
+/// Tables merge key by key, arrays gain the items they lack, and any other
+/// value is replaced in place (keeping its surrounding whitespace and
+/// comments). Tables the overlay adds go at the end of the document.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TomlMerger;
+
+impl FileMerger for TomlMerger {
+    fn format(&self) -> MergeFormat {
+        MergeFormat::Toml
+    }
+
+    fn merge(&self, base: &str, overlay: &str) -> ScarffResult<String> {
+        let mut doc: DocumentMut = base
+            .parse()
+            .map_err(|e| unparsable("existing", "TOML", e))?;
+        let extra: DocumentMut = overlay
+            .parse()
+            .map_err(|e| unparsable("overlay's", "TOML", e))?;
+
+        let mut next_position = last_position(doc.as_table()) + 1;
+        merge_toml_table(doc.as_table_mut(), extra.as_table(), &mut next_position);
+        Ok(doc.to_string())
+    }
+}
+
+fn merge_toml_table(base: &mut dyn TableLike, extra: &dyn TableLike, next_position: &mut isize) {
+    for (key, item) in extra.iter() {
+        match base.get_mut(key) {
+            Some(existing) => merge_toml_item(existing, item, next_position),
+            None => {
+                let mut item = item.clone();
+                place_new_tables(&mut item, next_position);
+                match extra.get_key_value(key) {
+                    Some((formatted, _)) => {
+                        base.entry_format(formatted).or_insert(item);
+                    }
+                    None => {
+                        base.insert(key, item);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn merge_toml_item(base: &mut Item, extra: &Item, next_position: &mut isize) {
+    if base.is_table_like() && extra.is_table_like() {
+        if let (Some(base), Some(extra)) = (base.as_table_like_mut(), extra.as_table_like()) {
+            merge_toml_table(base, extra, next_position);
+        }
+    } else if let (Some(base), Some(extra)) =
+        (base.as_array_of_tables_mut(), extra.as_array_of_tables())
+    {
+        for table in extra {
+            if !base.iter().any(|t| t.to_string() == table.to_string()) {
+                let mut item = Item::Table(table.clone());
+                place_new_tables(&mut item, next_position);
+                if let Item::Table(table) = item {
+                    base.push(table);
+                }
+            }
+        }
+    } else if base.is_array() && extra.is_array() {
+        if let (Some(base), Some(extra)) = (base.as_array_mut(), extra.as_array()) {
+            let decor = (base.len() > 1).then(|| base.iter().last().map(|v| v.decor().clone()));
+            for value in extra {
+                if base.iter().any(|v| bare(v) == bare(value)) {
+                    continue;
+                }
+                match &decor {
+                    Some(Some(decor)) => {
+                        let mut value = value.clone();
+                        *value.decor_mut() = decor.clone();
+                        base.push_formatted(value);
+                    }
+                    _ => base.push(bare_value(value)),
+                }
+            }
+        }
+    } else if base.is_value() && extra.is_value() {
+        if let (Some(base), Some(extra)) = (base.as_value_mut(), extra.as_value()) {
+            let decor = base.decor().clone();
+            *base = extra.clone();
+            *base.decor_mut() = decor;
+        }
+    } else {
+        let mut item = extra.clone();
+        place_new_tables(&mut item, next_position);
+        *base = item;
+    }
+}
+
+fn bare_value(value: &Value) -> Value {
+    let mut value = value.clone();
+    value.decor_mut().clear();
+    value
+}
+
+/// A value as written, without surrounding whitespace and comments.
+fn bare(value: &Value) -> String {
+    bare_value(value).to_string()
+}
+
+/// The highest table position in `table`, or 0.
+fn last_position(table: &Table) -> isize {
+    table
+        .iter()
+        .flat_map(|(_, item)| match item {
+            Item::Table(t) => vec![t],
+            Item::ArrayOfTables(tables) => tables.iter().collect(),
+            _ => Vec::new(),
+        })
+        .map(|t| t.position().unwrap_or(0).max(last_position(t)))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Give the tables in `item` positions after every existing table, in
+/// order, with a blank line before each header.
+fn place_new_tables(item: &mut Item, next_position: &mut isize) {
+    let place = |table: &mut Table, next_position: &mut isize| {
+        table.set_position(Some(*next_position));
+        *next_position += 1;
+        let prefix = table
+            .decor()
+            .prefix()
+            .and_then(|p| p.as_str())
+            .unwrap_or("")
+            .to_string();
+        if !prefix.starts_with('\n') {
+            let prefix = prefix.trim_start();
+            table.decor_mut().set_prefix(format!("\n{prefix}"));
+        }
+        for (_, child) in table.iter_mut() {
+            place_new_tables(child, next_position);
+        }
+    };
+    match item {
+        Item::Table(table) => place(table, next_position),
+        Item::ArrayOfTables(tables) => {
+            for table in tables.iter_mut() {
+                place(table, next_position);
+            }
+        }
+        _ => {}
+    }
+}
+
+// ── JSON ──────────────────────────────────────────────────────────────────────
+
+/// Deep-merges JSON documents by editing the existing text.
+///
+/// Objects merge key by key and arrays gain the items they lack; any other
+/// value is replaced. Only what changes is touched, so the rest of the file
+/// keeps its exact layout. What the overlay adds follows the object or array
+/// it goes into: on one line in a one-line one, one entry per line at the
+/// existing indentation otherwise.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonMerger;
+
+impl FileMerger for JsonMerger {
+    fn format(&self) -> MergeFormat {
+        MergeFormat::Json
+    }
+
+    fn merge(&self, base: &str, overlay: &str) -> ScarffResult<String> {
+        serde_json::from_str::<serde_json::Value>(base)
+            .map_err(|e| unparsable("existing", "JSON", e))?;
+        serde_json::from_str::<serde_json::Value>(overlay)
+            .map_err(|e| unparsable("overlay's", "JSON", e))?;
+        let doc = JsonNode::parse(base)
+            .ok_or_else(|| unparsable("existing", "JSON", "unexpected input"))?;
+        let extra = JsonNode::parse(overlay)
+            .ok_or_else(|| unparsable("overlay's", "JSON", "unexpected input"))?;
+
+        let mut edits = JsonEdits {
+            base,
+            overlay,
+            unit: json_indent(base),
+            pretty: base.trim().contains('\n'),
+            edits: Vec::new(),
+        };
+        edits.merge(&doc, &extra);
+
+        let mut edits = edits.edits;
+        edits.sort_by_key(|(range, _)| range.start);
+        let mut out = base.to_string();
+        for (range, text) in edits.into_iter().rev() {
+            out.replace_range(range, &text);
+        }
+        Ok(out)
+    }
+}
+
+/// A JSON value and where it is in the text it was parsed from.
+struct JsonNode {
+    span: Range<usize>,
+    kind: JsonKind,
+}
+
+enum JsonKind {
+    Object(Vec<JsonMember>),
+    Array(Vec<JsonNode>),
+    Scalar,
+}
+
+struct JsonMember {
+    key: String,
+    key_span: Range<usize>,
+    value: JsonNode,
+}
+
+impl JsonNode {
+    /// Locates the values of `text`, which `serde_json` has already accepted.
+    fn parse(text: &str) -> Option<Self> {
+        Self::value(text, &mut 0)
+    }
+
+    fn value(text: &str, pos: &mut usize) -> Option<Self> {
+        let bytes = text.as_bytes();
+        skip_json_whitespace(bytes, pos);
+        let start = *pos;
+        let kind = match bytes.get(start)? {
+            b'{' => {
+                let mut members = Vec::new();
+                *pos += 1;
+                loop {
+                    skip_json_whitespace(bytes, pos);
+                    match bytes.get(*pos)? {
+                        b'}' => break,
+                        b',' => *pos += 1,
+                        _ => {
+                            let key_start = *pos;
+                            skip_json_string(bytes, pos)?;
+                            let key_span = key_start..*pos;
+                            let key = serde_json::from_str(&text[key_span.clone()]).ok()?;
+                            skip_json_whitespace(bytes, pos);
+                            (bytes.get(*pos) == Some(&b':')).then_some(())?;
+                            *pos += 1;
+                            let value = Self::value(text, pos)?;
+                            members.push(JsonMember {
+                                key,
+                                key_span,
+                                value,
+                            });
+                        }
+                    }
+                }
+                *pos += 1;
+                JsonKind::Object(members)
+            }
+            b'[' => {
+                let mut items = Vec::new();
+                *pos += 1;
+                loop {
+                    skip_json_whitespace(bytes, pos);
+                    match bytes.get(*pos)? {
+                        b']' => break,
+                        b',' => *pos += 1,
+                        _ => items.push(Self::value(text, pos)?),
+                    }
+                }
+                *pos += 1;
+                JsonKind::Array(items)
+            }
+            b'"' => {
+                skip_json_string(bytes, pos)?;
+                JsonKind::Scalar
+            }
+            _ => {
+                while bytes.get(*pos).is_some_and(|b| !b" \t\r\n,]}".contains(b)) {
+                    *pos += 1;
+                }
+                (*pos > start).then_some(JsonKind::Scalar)?
+            }
+        };
+        Some(JsonNode {
+            span: start..*pos,
+            kind,
+        })
+    }
+
+    /// Where each member or item starts and ends, keys included.
+    fn children(&self) -> Vec<Range<usize>> {
+        match &self.kind {
+            JsonKind::Object(members) => members
+                .iter()
+                .map(|m| m.key_span.start..m.value.span.end)
+                .collect(),
+            JsonKind::Array(items) => items.iter().map(|i| i.span.clone()).collect(),
+            JsonKind::Scalar => Vec::new(),
+        }
+    }
+
+    fn is_scalar(&self) -> bool {
+        matches!(self.kind, JsonKind::Scalar)
+    }
+
+    fn value_in(&self, text: &str) -> serde_json::Value {
+        serde_json::from_str(&text[self.span.clone()]).unwrap_or_default()
+    }
+}
+
+fn skip_json_whitespace(bytes: &[u8], pos: &mut usize) {
+    while bytes.get(*pos).is_some_and(|b| b" \t\r\n".contains(b)) {
+        *pos += 1;
+    }
+}
+
+fn skip_json_string(bytes: &[u8], pos: &mut usize) -> Option<()> {
+    *pos += 1;
+    loop {
+        match bytes.get(*pos)? {
+            b'\\' => *pos += 2,
+            b'"' => {
+                *pos += 1;
+                return Some(());
+            }
+            _ => *pos += 1,
+        }
+    }
+}
+
+/// An object member (with its key as the overlay wrote it) or array item.
+type JsonEntry<'a> = (Option<&'a str>, &'a JsonNode);
+
+/// The text replacements that merge `overlay` into `base`.
+struct JsonEdits<'a> {
+    base: &'a str,
+    overlay: &'a str,
+    /// One level of indentation in `base`.
+    unit: String,
+    /// Whether `base` spans several lines.
+    pretty: bool,
+    edits: Vec<(Range<usize>, String)>,
+}
+
+impl<'a> JsonEdits<'a> {
+    fn merge(&mut self, node: &JsonNode, extra: &'a JsonNode) {
+        match (&node.kind, &extra.kind) {
+            (JsonKind::Object(members), JsonKind::Object(extra_members)) => {
+                let mut added: Vec<&JsonMember> = Vec::new();
+                for member in extra_members {
+                    if let Some(existing) = members.iter().find(|m| m.key == member.key) {
+                        self.merge(&existing.value, &member.value);
+                    } else if !added.iter().any(|m| m.key == member.key) {
+                        added.push(member);
+                    }
+                }
+                let entries: Vec<JsonEntry<'a>> = added
+                    .into_iter()
+                    .map(|m| (Some(&self.overlay[m.key_span.clone()]), &m.value))
+                    .collect();
+                self.append(node, &entries);
+            }
+            (JsonKind::Array(items), JsonKind::Array(extra_items)) => {
+                let mut seen: Vec<serde_json::Value> =
+                    items.iter().map(|i| i.value_in(self.base)).collect();
+                let mut entries: Vec<JsonEntry<'a>> = Vec::new();
+                for item in extra_items {
+                    let value = item.value_in(self.overlay);
+                    if !seen.contains(&value) {
+                        seen.push(value);
+                        entries.push((None, item));
+                    }
+                }
+                self.append(node, &entries);
+            }
+            _ => {
+                let indent = line_indent(self.base, node.span.start);
+                let text = self.write(extra, indent, self.pretty);
+                self.edits.push((node.span.clone(), text));
+            }
+        }
+    }
+
+    /// Adds `entries` after the last member or item of `node`, laid out
+    /// like the ones already there.
+    fn append(&mut self, node: &JsonNode, entries: &[JsonEntry<'a>]) {
+        if entries.is_empty() {
+            return;
+        }
+        let base = self.base;
+        let children = node.children();
+        let multiline = match children.first() {
+            Some(_) => base[node.span.clone()].contains('\n'),
+            None => {
+                self.pretty
+                    && (matches!(node.kind, JsonKind::Object(_))
+                        || entries.iter().any(|(_, value)| !value.is_scalar()))
+            }
+        };
+        let outer = line_indent(base, node.span.start);
+        let indent = match children.first() {
+            Some(first) if multiline => line_indent(base, first.start).to_string(),
+            _ => format!("{outer}{}", self.unit),
+        };
+        let separator = match &children[..] {
+            _ if multiline => format!(",\n{indent}"),
+            [first, second, ..] => base[first.end..second.start].to_string(),
+            _ if self.pretty => ", ".to_string(),
+            _ => ",".to_string(),
+        };
+        let colon = match &node.kind {
+            JsonKind::Object(members) if !members.is_empty() => {
+                base[members[0].key_span.end..members[0].value.span.start].to_string()
+            }
+            _ if multiline || self.pretty => ": ".to_string(),
+            _ => ":".to_string(),
+        };
+        let written: Vec<String> = entries
+            .iter()
+            .map(|entry| self.entry(entry, &colon, &indent, multiline))
+            .collect();
+
+        match children.last() {
+            Some(last) => {
+                let text = written.iter().map(|e| format!("{separator}{e}")).collect();
+                self.edits.push((last.end..last.end, text));
+            }
+            None => {
+                let (open, close) = brackets(&base[node.span.clone()]);
+                let text = if multiline {
+                    format!(
+                        "{open}\n{indent}{}\n{outer}{close}",
+                        written.join(&separator)
+                    )
+                } else {
+                    format!("{open}{}{close}", written.join(&separator))
+                };
+                self.edits.push((node.span.clone(), text));
+            }
+        }
+    }
+
+    /// `node` from the overlay, written at `indent`, over several lines or
+    /// on one.
+    fn write(&self, node: &JsonNode, indent: &str, multiline: bool) -> String {
+        let text = &self.overlay[node.span.clone()];
+        let entries: Vec<JsonEntry<'_>> = match &node.kind {
+            JsonKind::Scalar => return text.to_string(),
+            JsonKind::Object(members) => members
+                .iter()
+                .map(|m| (Some(&self.overlay[m.key_span.clone()]), &m.value))
+                .collect(),
+            JsonKind::Array(items) => items.iter().map(|i| (None, i)).collect(),
+        };
+        let (open, close) = brackets(text);
+        if entries.is_empty() {
+            return format!("{open}{close}");
+        }
+
+        let inner = format!("{indent}{}", self.unit);
+        let (separator, colon) = match () {
+            _ if multiline => (format!(",\n{inner}"), ": "),
+            _ if self.pretty => (", ".to_string(), ": "),
+            _ => (",".to_string(), ":"),
+        };
+        let written: Vec<String> = entries
+            .iter()
+            .map(|entry| self.entry(entry, colon, &inner, multiline))
+            .collect();
+        if multiline {
+            format!(
+                "{open}\n{inner}{}\n{indent}{close}",
+                written.join(&separator)
+            )
+        } else {
+            format!("{open}{}{close}", written.join(&separator))
+        }
+    }
+
+    fn entry(
+        &self,
+        (key, value): &JsonEntry<'_>,
+        colon: &str,
+        indent: &str,
+        multiline: bool,
+    ) -> String {
+        let value = self.write(value, indent, multiline);
+        match key {
+            Some(key) => format!("{key}{colon}{value}"),
+            None => value,
+        }
+    }
+}
+
+/// The opening and closing bracket of an object or array.
+fn brackets(text: &str) -> (&str, &str) {
+    (&text[..1], &text[text.len() - 1..])
+}
+
+/// The whitespace the line holding `pos` starts with.
+fn line_indent(text: &str, pos: usize) -> &str {
+    let line = &text[text[..pos].rfind('\n').map_or(0, |i| i + 1)..];
+    &line[..line.len() - line.trim_start_matches([' ', '\t']).len()]
+}
+
+/// One level of indentation in pretty-printed `json`; two spaces if it
+/// cannot be told.
+fn json_indent(json: &str) -> String {
+    json.lines()
+        .skip(1)
+        .map(|line| &line[..line.len() - line.trim_start().len()])
+        .find(|indent| !indent.is_empty())
+        .unwrap_or("  ")
+        .to_string()
+}
+
+// ── Line sets ─────────────────────────────────────────────────────────────────
+
+/// Union of lines, for ignore files: lines the existing file lacks are
+/// added at the end, after a blank line. Comments count as lines; blank
+/// lines in the overlay are dropped.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LineSetMerger;
+
+impl FileMerger for LineSetMerger {
+    fn format(&self) -> MergeFormat {
+        MergeFormat::Lines
+    }
+
+    fn merge(&self, base: &str, overlay: &str) -> ScarffResult<String> {
+        let mut seen: HashSet<&str> = base.lines().map(str::trim).collect();
+        let added: Vec<&str> = overlay
+            .lines()
+            .map(str::trim_end)
+            .filter(|line| !line.trim().is_empty() && seen.insert(line.trim()))
+            .collect();
+        Ok(append_block(base, &added))
+    }
+}
+
+// ── Dotenv ────────────────────────────────────────────────────────────────────
+
+/// Key-wise merge of `.env` files.
+///
+/// A key the existing file has keeps its place and its line up to the `=`
+/// (an `export`, the spacing) and takes the overlay's value; new keys are
+/// added at the end with the comments directly above them.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DotenvMerger;
+
+impl FileMerger for DotenvMerger {
+    fn format(&self) -> MergeFormat {
+        MergeFormat::Dotenv
+    }
+
+    fn merge(&self, base: &str, overlay: &str) -> ScarffResult<String> {
+        let eol = line_ending(base);
+        let mut lines: Vec<String> = base.lines().map(str::to_string).collect();
+        let mut added: Vec<&str> = Vec::new();
+        let mut comments: Vec<&str> = Vec::new();
+
+        for line in overlay.lines() {
+            let Some(key) = dotenv_key(line) else {
+                if line.trim_start().starts_with('#') {
+                    comments.push(line);
+                } else {
+                    comments.clear();
+                }
+                continue;
+            };
+            match lines.iter().position(|l| dotenv_key(l) == Some(key)) {
+                Some(index) => {
+                    let (_, value) = line.split_once('=').unwrap_or_default();
+                    let (prefix, _) = lines[index].split_once('=').unwrap_or_default();
+                    lines[index] = format!("{prefix}={value}");
+                }
+                None if !added.iter().any(|l| dotenv_key(l) == Some(key)) => {
+                    added.append(&mut comments);
+                    added.push(line);
+                }
+                None => {}
+            }
+            comments.clear();
+        }
+
+        let mut merged = lines.join(eol);
+        if base.ends_with('\n') || (merged.is_empty() && !added.is_empty()) {
+            merged.push_str(eol);
+        }
+        Ok(append_block(&merged, &added))
+    }
+}
+
+/// The key of a `KEY=value` or `export KEY=value` line.
+fn dotenv_key(line: &str) -> Option<&str> {
+    let line = line.trim_start();
+    if line.starts_with('#') {
+        return None;
+    }
+    let line = line.strip_prefix("export ").unwrap_or(line);
+    let (key, _) = line.split_once('=')?;
+    let key = key.trim();
+    (!key.is_empty()).then_some(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toml_merge_keeps_comments_and_order() {
+        let base = "\
+# Managed by scarff
+[package]
+name = \"app\"   # crate name
+version = \"0.1.0\"
+
+[dependencies]
+serde = \"1\"
+tokio = { version = \"1\", features = [\"rt\"] }
+";
+        let overlay = "\
+[package]
+version = \"0.2.0\"
+
+[dependencies]
+tokio = { version = \"1\", features = [\"rt\", \"macros\"] }
+tracing = \"0.1\"
+
+# Observability
+[features]
+otel = []
+";
+        let merged = TomlMerger.merge(base, overlay).unwrap();
+        assert_eq!(
+            merged,
+            "\
+# Managed by scarff
+[package]
+name = \"app\"   # crate name
+version = \"0.2.0\"
+
+[dependencies]
+serde = \"1\"
+tokio = { version = \"1\", features = [\"rt\", \"macros\"] }
+tracing = \"0.1\"
+
+# Observability
+[features]
+otel = []
+"
+        );
+
+        let err = TomlMerger.merge(base, "[x").unwrap_err().to_string();
+        assert!(err.contains("overlay's file is not valid TOML"), "{err}");
+    }
+
+    #[test]
+    fn json_merge_keeps_the_existing_layout() {
+        let base = "{\n    \"name\": \"app\",\n    \"scripts\": {\n        \"build\": \"tsc\"\n    },\n    \"files\": [\"dist\"]\n}\n";
+        let overlay =
+            r#"{"scripts": {"start": "node ."}, "files": ["dist", "Dockerfile"], "name": "web"}"#;
+
+        assert_eq!(
+            JsonMerger.merge(base, overlay).unwrap(),
+            "{\n    \"name\": \"web\",\n    \"scripts\": {\n        \"build\": \"tsc\",\n        \"start\": \"node .\"\n    },\n    \"files\": [\"dist\", \"Dockerfile\"]\n}\n"
+        );
+        assert_eq!(
+            JsonMerger
+                .merge(
+                    "{\n  \"a\": {}\n}",
+                    r#"{"a": {"x": [1]}, "b": {"c": true}}"#
+                )
+                .unwrap(),
+            "{\n  \"a\": {\n    \"x\": [\n      1\n    ]\n  },\n  \"b\": {\n    \"c\": true\n  }\n}"
+        );
+        assert_eq!(
+            JsonMerger.merge(r#"{"a":1}"#, r#"{"b":2}"#).unwrap(),
+            r#"{"a":1,"b":2}"#
+        );
+    }
+
+    #[test]
+    fn line_sets_and_dotenv_add_only_what_is_missing() {
+        assert_eq!(
+            LineSetMerger
+                .merge("/target\n*.log\n", "# Docker\n*.log\n\n.docker/\n")
+                .unwrap(),
+            "/target\n*.log\n\n# Docker\n.docker/\n"
+        );
+        assert_eq!(
+            LineSetMerger.merge("/target\n", "/target\n").unwrap(),
+            "/target\n"
+        );
+
+        let base = "# App\nPORT=8080\nexport RUST_LOG=info\n";
+        let overlay = "RUST_LOG=debug\n\n# Tracing\nOTEL_ENDPOINT=http://localhost:4317\n";
+        assert_eq!(
+            DotenvMerger.merge(base, overlay).unwrap(),
+            "# App\nPORT=8080\nexport RUST_LOG=debug\n\n# Tracing\nOTEL_ENDPOINT=http://localhost:4317\n"
+        );
+    }
+}
@@ -0,0 +1,248 @@
+//! Built-in [`PostProcessor`]s for `[[format]]` rules.
+//!
+//! | Processor | Setting |
+//! |-----------|---------|
+//! | [`ByteOrderMark`] | `bom = true \| false` |
+//! | [`TrimTrailingWhitespace`] | `trim_trailing_whitespace = true` |
+//! | [`Indentation`] | `indent = "spaces" \| "tabs"`, `indent_size = 4` |
+//! | [`FinalNewline`] | `final_newline = true \| false` |
+//! | [`LineEndings`] | `line_endings = "lf" \| "crlf"` |
+//!
+//! All of them leave binary files untouched.
+
+use scarff_core::{
+    application::ports::PostProcessor,
+    domain::{FileContent, FileToWrite, FormatOptions, Indent, LineEnding},
+    error::ScarffResult,
+};
+
+const BOM: char = '\u{feff}';
+
+/// Every built-in processor, in the order they should run: line endings
+/// last, so the other processors may emit plain `\n`.
+pub fn builtin_processors() -> Vec<Box<dyn PostProcessor>> {
+    vec![
+        Box::new(ByteOrderMark),
+        Box::new(TrimTrailingWhitespace),
+        Box::new(Indentation),
+        Box::new(FinalNewline),
+        Box::new(LineEndings),
+    ]
+}
+
+/// Replace the text of `file` with `edit(text)`; binary files are skipped.
+fn edit_text(file: &mut FileToWrite, edit: impl FnOnce(&str) -> String) {
+    if let FileContent::Text(text) = &mut file.content {
+        *text = edit(text);
+    }
+}
+
+/// Split `line` (as yielded by `split_inclusive('\n')`) into its body and
+/// terminator.
+fn split_terminator(line: &str) -> (&str, &str) {
+    if let Some(body) = line.strip_suffix("\r\n") {
+        (body, "\r\n")
+    } else if let Some(body) = line.strip_suffix('\n') {
+        (body, "\n")
+    } else {
+        (line, "")
+    }
+}
+
+/// Rewrite every line's body, keeping its terminator.
+fn map_lines(text: &str, mut f: impl FnMut(&str) -> String) -> String {
+    let mut out = String::with_capacity(text.len());
+    for line in text.split_inclusive('\n') {
+        let (body, terminator) = split_terminator(line);
+        out.push_str(&f(body));
+        out.push_str(terminator);
+    }
+    out
+}
+
+/// Adds or strips a UTF-8 byte order mark.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ByteOrderMark;
+
+impl PostProcessor for ByteOrderMark {
+    fn name(&self) -> &str {
+        "bom"
+    }
+
+    fn process(&self, file: &mut FileToWrite, options: &FormatOptions) -> ScarffResult<()> {
+        if let Some(bom) = options.bom {
+            edit_text(file, |text| {
+                let body = text.strip_prefix(BOM).unwrap_or(text);
+                if bom {
+                    format!("{BOM}{body}")
+                } else {
+                    body.to_string()
+                }
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Strips spaces and tabs at the end of each line.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TrimTrailingWhitespace;
+
+impl PostProcessor for TrimTrailingWhitespace {
+    fn name(&self) -> &str {
+        "trim_trailing_whitespace"
+    }
+
+    fn process(&self, file: &mut FileToWrite, options: &FormatOptions) -> ScarffResult<()> {
+        if options.trim_trailing_whitespace == Some(true) {
+            edit_text(file, |text| {
+                map_lines(text, |body| body.trim_end_matches([' ', '\t']).to_string())
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Converts leading indentation between tabs and spaces. Tab stops are
+/// `width` columns apart; spaces short of a full tab are kept as spaces.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Indentation;
+
+impl PostProcessor for Indentation {
+    fn name(&self) -> &str {
+        "indent"
+    }
+
+    fn process(&self, file: &mut FileToWrite, options: &FormatOptions) -> ScarffResult<()> {
+        if let Some(indent) = options.indent {
+            edit_text(file, |text| map_lines(text, |body| reindent(body, indent)));
+        }
+        Ok(())
+    }
+}
+
+fn reindent(line: &str, indent: Indent) -> String {
+    let (Indent::Spaces(width) | Indent::Tabs(width)) = indent;
+    let width = width.max(1);
+
+    let rest = line.trim_start_matches([' ', '\t']);
+    let leading = &line[..line.len() - rest.len()];
+    let columns = leading.chars().fold(0, |col, c| match c {
+        '\t' => (col / width + 1) * width,
+        _ => col + 1,
+    });
+
+    let indentation = match indent {
+        Indent::Spaces(_) => " ".repeat(columns),
+        Indent::Tabs(_) => "\t".repeat(columns / width) + &" ".repeat(columns % width),
+    };
+    indentation + rest
+}
+
+/// Ends the file with exactly one line break (`true`) or none (`false`).
+/// An empty file stays empty.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FinalNewline;
+
+impl PostProcessor for FinalNewline {
+    fn name(&self) -> &str {
+        "final_newline"
+    }
+
+    fn process(&self, file: &mut FileToWrite, options: &FormatOptions) -> ScarffResult<()> {
+        if let Some(final_newline) = options.final_newline {
+            edit_text(file, |text| {
+                let terminator = if text.contains("\r\n") { "\r\n" } else { "\n" };
+                let body = text.trim_end_matches(['\r', '\n']);
+                if final_newline && !body.is_empty() {
+                    format!("{body}{terminator}")
+                } else {
+                    body.to_string()
+                }
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Converts every `\n` and `\r\n` line break to the configured terminator.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LineEndings;
+
+impl PostProcessor for LineEndings {
+    fn name(&self) -> &str {
+        "line_endings"
+    }
+
+    fn process(&self, file: &mut FileToWrite, options: &FormatOptions) -> ScarffResult<()> {
+        if let Some(ending) = options.line_ending {
+            edit_text(file, |text| {
+                let lf = text.replace("\r\n", "\n");
+                match ending {
+                    LineEnding::Lf => lf,
+                    LineEnding::CrLf => lf.replace('\n', "\r\n"),
+                }
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scarff_core::domain::{Permissions, ProjectStructure};
+
+    fn run(content: impl Into<FileContent>, options: &FormatOptions) -> FileContent {
+        let structure =
+            ProjectStructure::new("/out").with_file("f", content, Permissions::read_write());
+        let mut file = structure.files().next().unwrap().clone();
+        for processor in builtin_processors() {
+            processor.process(&mut file, options).unwrap();
+        }
+        file.content
+    }
+
+    #[test]
+    fn processors_apply_only_configured_settings() {
+        let messy = "\u{feff}fn main() {  \r\n\tlet x = 1;\t\n}\n\n\n";
+
+        assert_eq!(run(messy, &FormatOptions::default()), messy);
+
+        let tidy = FormatOptions {
+            line_ending: Some(LineEnding::Lf),
+            trim_trailing_whitespace: Some(true),
+            final_newline: Some(true),
+            indent: Some(Indent::Spaces(4)),
+            bom: Some(false),
+        };
+        assert_eq!(run(messy, &tidy), "fn main() {\n    let x = 1;\n}\n");
+
+        let windows = FormatOptions {
+            line_ending: Some(LineEnding::CrLf),
+            final_newline: Some(false),
+            bom: Some(true),
+            ..FormatOptions::default()
+        };
+        assert_eq!(run("a\nb\r\n", &windows), "\u{feff}a\r\nb");
+    }
+
+    #[test]
+    fn tabs_replace_whole_indent_levels() {
+        assert_eq!(reindent("      x", Indent::Tabs(4)), "\t  x");
+        assert_eq!(reindent("  \tx", Indent::Tabs(4)), "\tx");
+        assert_eq!(reindent("\t  x", Indent::Spaces(2)), "    x");
+        assert_eq!(reindent("   ", Indent::Tabs(2)), "\t ");
+    }
+
+    #[test]
+    fn binary_files_are_left_alone() {
+        let bytes = b"\xff\xfe  \r\n".to_vec();
+        let options = FormatOptions {
+            line_ending: Some(LineEnding::Lf),
+            trim_trailing_whitespace: Some(true),
+            ..FormatOptions::default()
+        };
+        assert_eq!(run(bytes.clone(), &options), FileContent::Binary(bytes));
+    }
+}
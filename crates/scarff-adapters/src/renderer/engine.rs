@@ -0,0 +1,81 @@
+//! Renderer that picks the template language per template.
+
+use std::path::Path;
+
+use scarff_core::{
+    application::ports::TemplateRenderer,
+    domain::{
+        ContentTemplateId, ProjectStructure, RenderContext, RenderEngine, Template, TemplateSource,
+    },
+    error::ScarffResult,
+};
+
+#[cfg(not(feature = "jinja"))]
+use scarff_core::application::ApplicationError;
+
+#[cfg(feature = "jinja")]
+use super::MinijinjaRenderer;
+use super::SimpleRenderer;
+use crate::content::ContentResolvers;
+
+/// Dispatches to the renderer matching [`Template::engine`].
+///
+/// Templates without an `engine` key use [`SimpleRenderer`]. `engine = "jinja"`
+/// templates use [`MinijinjaRenderer`] when the `jinja` feature is enabled and
+/// fail with an explanatory error otherwise.
+pub struct EngineRenderer {
+    simple: SimpleRenderer,
+    #[cfg(feature = "jinja")]
+    jinja: MinijinjaRenderer,
+}
+
+impl EngineRenderer {
+    /// Create a renderer covering every engine compiled into this build.
+    pub fn new() -> Self {
+        Self::with_resolvers(ContentResolvers::builtin())
+    }
+
+    /// Like [`new`](Self::new), resolving external content through `resolvers`.
+    pub fn with_resolvers(resolvers: ContentResolvers) -> Self {
+        Self {
+            #[cfg(feature = "jinja")]
+            jinja: MinijinjaRenderer::with_resolvers(resolvers.clone()),
+            simple: SimpleRenderer::with_resolvers(resolvers),
+        }
+    }
+}
+
+impl Default for EngineRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TemplateRenderer for EngineRenderer {
+    fn render(
+        &self,
+        template: &Template,
+        context: &RenderContext,
+        output_root: &Path,
+    ) -> ScarffResult<ProjectStructure> {
+        match template.engine {
+            RenderEngine::Simple => self.simple.render(template, context, output_root),
+            #[cfg(feature = "jinja")]
+            RenderEngine::Jinja => self.jinja.render(template, context, output_root),
+            #[cfg(not(feature = "jinja"))]
+            RenderEngine::Jinja => Err(ApplicationError::RenderingFailed {
+                reason: format!(
+                    "template '{}' requires the jinja engine, which is not enabled in this build \
+                     (enable the `jinja` cargo feature)",
+                    template.id
+                ),
+            }
+            .into()),
+        }
+    }
+
+    fn resolve_external(&self, id: &ContentTemplateId) -> ScarffResult<TemplateSource> {
+        // Both engines share one set of resolvers.
+        self.simple.resolve_external(id)
+    }
+}
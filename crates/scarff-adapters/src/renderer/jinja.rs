@@ -0,0 +1,324 @@
+//! Jinja2 renderer backed by MiniJinja.
+//!
+//! Selected per template with `engine = "jinja"` in `template.toml`. Every
+//! parameterized file is registered under its relative path, so files can
+//! `{% include %}`, `{% import %}` or `{% extends %}` each other. Files in
+//! `_macros/` and `_includes/` ([`JINJA_HELPER_DIRS`]) are only there to be
+//! included and are not written:
+//!
+//! ```jinja
+//! {% import "_macros/deps.jinja" as deps %}
+//! [dependencies]
+//! {{ deps.crate("serde", "1") }}
+//! ```
+//!
+//! Context variables are exposed unchanged (`{{ PROJECT_NAME }}`), and
+//! Scarff's case/inflection filters are registered next to MiniJinja's
+//! built-ins: `snake`, `kebab`, `pascal`, `camel`, `screaming_snake`,
+//! `plural`, `singular`.
+
+use std::path::Path;
+
+use minijinja::{Environment, UndefinedBehavior, Value};
+use scarff_core::{
+    application::{ApplicationError, ports::TemplateRenderer},
+    domain::{
+        ContentTemplateId, DomainValidator as validator, FileContent, ProjectStructure,
+        RelativePath, RenderContext, RenderEngine, RenderValue, Template, TemplateContent,
+        TemplateNode, TemplateSource, apply_filter,
+    },
+    error::{ScarffError, ScarffResult},
+};
+use tracing::instrument;
+
+use super::is_jinja_helper;
+use crate::content::ContentResolvers;
+
+#[cfg(doc)]
+use super::JINJA_HELPER_DIRS;
+
+/// Filters shared with the simple engine that MiniJinja lacks.
+const SCARFF_FILTERS: [&str; 7] = [
+    "snake",
+    "kebab",
+    "pascal",
+    "camel",
+    "screaming_snake",
+    "plural",
+    "singular",
+];
+
+/// Renderer for templates written in Jinja2 syntax.
+///
+/// External content (`builtin:mit`, …) is shared with simple-engine templates,
+/// so it is rendered with Scarff's own syntax rather than Jinja.
+pub struct MinijinjaRenderer {
+    resolvers: ContentResolvers,
+}
+
+impl MinijinjaRenderer {
+    /// Create a new MiniJinja renderer that resolves `builtin:` content only.
+    pub fn new() -> Self {
+        Self::with_resolvers(ContentResolvers::builtin())
+    }
+
+    /// Create a renderer that resolves external content through `resolvers`.
+    pub fn with_resolvers(resolvers: ContentResolvers) -> Self {
+        Self { resolvers }
+    }
+
+    fn environment<'t>(template: &'t Template, strict: bool) -> ScarffResult<Environment<'t>> {
+        let mut env = Environment::new();
+        // Generated files should end exactly like their sources.
+        env.set_keep_trailing_newline(true);
+        env.set_undefined_behavior(if strict {
+            UndefinedBehavior::Strict
+        } else {
+            UndefinedBehavior::Lenient
+        });
+
+        for name in SCARFF_FILTERS {
+            env.add_filter(name, move |value: String| {
+                apply_filter(name, &value).unwrap_or(value)
+            });
+        }
+
+        for node in &template.tree.nodes {
+            if let TemplateNode::File(spec) = node
+                && let TemplateContent::Parameterized(source) = &spec.content
+            {
+                env.add_template(spec.path.as_str(), source.as_str())
+                    .map_err(|e| rendering_failed(spec.path.as_str(), &e))?;
+            }
+        }
+
+        Ok(env)
+    }
+}
+
+impl Default for MinijinjaRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TemplateRenderer for MinijinjaRenderer {
+    #[instrument(skip_all)]
+    fn render(
+        &self,
+        template: &Template,
+        context: &RenderContext,
+        output_root: &Path,
+    ) -> ScarffResult<ProjectStructure> {
+        validator::validate_template(template).map_err(ScarffError::Domain)?;
+
+        if template.engine != RenderEngine::Jinja {
+            return Err(ApplicationError::RenderingFailed {
+                reason: format!(
+                    "template '{}' uses the '{}' engine, not jinja",
+                    template.id, template.engine
+                ),
+            }
+            .into());
+        }
+
+        let strict = context.is_strict() && template.strict;
+        let env = Self::environment(template, strict)?;
+        let globals: Value = context
+            .variables()
+            .map(|(key, value)| (key, to_value(value)))
+            .collect();
+
+        let mut structure = ProjectStructure::new(output_root);
+
+        // Every file stays includable above; only these are emitted.
+        for node in template
+            .tree
+            .nodes_for(context)
+            .into_iter()
+            .filter(|node| !is_jinja_helper(node.path().as_str()))
+        {
+            match node {
+                TemplateNode::File(spec) => {
+                    let output_path = render_path(&env, &spec.path, &globals)?;
+                    let path = spec.path.as_str();
+                    let content: FileContent = match &spec.content {
+                        TemplateContent::Literal(source) => source.as_str().into(),
+                        TemplateContent::Parameterized(_) => env
+                            .get_template(path)
+                            .and_then(|t| t.render(&globals))
+                            .map_err(|e| rendering_failed(path, &e))?
+                            .into(),
+                        TemplateContent::External(id) => {
+                            let source = self.resolvers.resolve(id)?;
+                            context
+                                .clone()
+                                .strict(strict)
+                                .render_source(&source)
+                                .map_err(|e| ApplicationError::RenderingFailed {
+                                    reason: format!("{path}: {e}"),
+                                })?
+                                .into()
+                        }
+                        TemplateContent::Binary(source) => source.as_bytes().to_vec().into(),
+                    };
+                    structure.add_file(output_path.into_path_buf(), content, spec.permissions);
+                }
+                TemplateNode::Directory(spec) => {
+                    let output_path = render_path(&env, &spec.path, &globals)?;
+                    structure.add_directory(output_path.into_path_buf(), spec.permissions);
+                }
+            }
+        }
+
+        validator::validate_project_structure(&structure).map_err(ScarffError::Domain)?;
+
+        Ok(structure)
+    }
+
+    fn resolve_external(&self, id: &ContentTemplateId) -> ScarffResult<TemplateSource> {
+        self.resolvers.resolve(id)
+    }
+}
+
+fn to_value(value: &RenderValue) -> Value {
+    match value {
+        RenderValue::String(s) => Value::from(s.as_str()),
+        RenderValue::List(items) => items.iter().map(to_value).collect(),
+        RenderValue::Map(entries) => entries
+            .iter()
+            .map(|(k, v)| (k.as_str(), to_value(v)))
+            .collect(),
+    }
+}
+
+/// Render a file or directory path with the template's own syntax, then
+/// check the result stays inside the project.
+fn render_path(
+    env: &Environment<'_>,
+    path: &RelativePath,
+    globals: &Value,
+) -> ScarffResult<RelativePath> {
+    let rendered = env
+        .render_str(path.as_str(), globals)
+        .map_err(|e| rendering_failed(path.as_str(), &e))?;
+    RelativePath::parse(&rendered).map_err(|e| {
+        ApplicationError::RenderingFailed {
+            reason: format!("{path}: {e}"),
+        }
+        .into()
+    })
+}
+
+/// MiniJinja errors carry the failing template name and line; keep both.
+fn rendering_failed(path: &str, err: &minijinja::Error) -> ScarffError {
+    let location = match err.line() {
+        Some(line) => format!("{path}:{line}"),
+        None => path.to_string(),
+    };
+    let detail = err.detail().map(|d| format!(": {d}")).unwrap_or_default();
+
+    ApplicationError::RenderingFailed {
+        reason: format!("{location}: {}{detail}", err.kind()),
+    }
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scarff_core::domain::{
+        FileSpec, TargetMatcher, TemplateId, TemplateMetadata, TemplateSource,
+    };
+
+    fn template(files: &[(&str, &'static str)]) -> Template {
+        let mut builder = Template::builder()
+            .id(TemplateId::new("j", "1.0.0"))
+            .matcher(TargetMatcher::builder().build())
+            .metadata(TemplateMetadata::new("J"))
+            .engine(RenderEngine::Jinja);
+        for (path, content) in files {
+            builder = builder.add_node(TemplateNode::File(FileSpec::new(
+                *path,
+                TemplateContent::Parameterized(TemplateSource::from(*content)),
+            )));
+        }
+        builder.build().unwrap()
+    }
+
+    fn render(template: &Template, ctx: &RenderContext) -> ScarffResult<ProjectStructure> {
+        MinijinjaRenderer::new().render(template, ctx, Path::new("/out"))
+    }
+
+    fn content<'a>(structure: &'a ProjectStructure, path: &str) -> &'a str {
+        structure
+            .files()
+            .find(|f| f.path.ends_with(path))
+            .unwrap()
+            .content
+            .as_text()
+            .unwrap()
+    }
+
+    #[test]
+    fn renders_macros_includes_loops_and_filters() {
+        let template = template(&[
+            (
+                "_macros/deps.jinja",
+                "{% macro dep(name, v) %}{{ name }} = \"{{ v }}\"{% endmacro %}",
+            ),
+            (
+                "Cargo.toml",
+                "{% import \"_macros/deps.jinja\" as m -%}\n\
+                 [package]\nname = \"{{ PROJECT_NAME | kebab }}\"\n\
+                 {% for d in DEPS %}{{ m.dep(d, \"1\") }}\n{% endfor %}",
+            ),
+        ]);
+        let ctx = RenderContext::new("My App").with_variable("DEPS", vec!["serde", "clap"]);
+
+        let structure = render(&template, &ctx).unwrap();
+
+        assert_eq!(
+            content(&structure, "Cargo.toml"),
+            "[package]\nname = \"my-app\"\nserde = \"1\"\nclap = \"1\"\n"
+        );
+        // The macros are only included, not written.
+        assert_eq!(structure.files().count(), 1);
+    }
+
+    #[test]
+    fn strict_context_fails_on_undefined_variables() {
+        let template = template(&[("a.txt", "{{ MISSING }}")]);
+        let err = render(&template, &RenderContext::new("x").strict(true))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("a.txt:1"), "{err}");
+
+        let lenient = render(&template, &RenderContext::new("x")).unwrap();
+        assert_eq!(content(&lenient, "a.txt"), "");
+    }
+
+    #[test]
+    fn rejects_templates_for_other_engines() {
+        let mut template = template(&[("a.txt", "x")]);
+        template.engine = RenderEngine::Simple;
+        assert!(render(&template, &RenderContext::new("x")).is_err());
+    }
+
+    #[test]
+    fn paths_are_rendered_with_jinja_syntax() {
+        let go = template(&[("cmd/{{ PROJECT_NAME | kebab }}/main.go", "package main")]);
+
+        let structure = render(&go, &RenderContext::new("My Tool")).unwrap();
+        assert_eq!(
+            structure.files().next().unwrap().path,
+            Path::new("cmd/my-tool/main.go")
+        );
+
+        let escaping = template(&[("{{ '..' }}/main.go", "")]);
+        let err = render(&escaping, &RenderContext::new("x"))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("'..' leaves the project directory"), "{err}");
+    }
+}
@@ -1,5 +1,26 @@
 //! Template rendering adapters.
+//!
+//! - [`SimpleRenderer`]: Scarff's built-in syntax, the default engine
+//! - [`MinijinjaRenderer`]: Jinja2 via MiniJinja (cargo feature `jinja`)
+//! - [`EngineRenderer`]: picks one of the above per template
 
+mod engine;
+#[cfg(feature = "jinja")]
+mod jinja;
 mod simple;
 
+pub use engine::EngineRenderer;
+#[cfg(feature = "jinja")]
+pub use jinja::MinijinjaRenderer;
 pub use simple::SimpleRenderer;
+
+/// Directories of jinja templates holding files that other files
+/// `{% include %}` or `{% import %}`. They are not written to the project.
+pub const JINJA_HELPER_DIRS: [&str; 2] = ["_macros", "_includes"];
+
+/// Whether `path`, relative to the template, is one of the
+/// [`JINJA_HELPER_DIRS`] or lies inside one.
+pub fn is_jinja_helper(path: &str) -> bool {
+    let top = path.split('/').next().unwrap_or(path);
+    JINJA_HELPER_DIRS.contains(&top)
+}
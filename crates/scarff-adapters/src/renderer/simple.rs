@@ -1,24 +1,34 @@
 //! Simple variable substitution renderer.
 
-use std::path::Path;
+use std::{borrow::Cow, path::Path};
 
 use scarff_core::{
-    application::ports::TemplateRenderer,
+    application::{ApplicationError, ports::TemplateRenderer},
     domain::{
-        DomainValidator as validator, ProjectStructure, RenderContext, Template, TemplateContent,
-        TemplateNode,
+        ContentTemplateId, DomainError, DomainValidator as validator, FileContent,
+        ProjectStructure, RelativePath, RenderContext, RenderEngine, Template, TemplateContent,
+        TemplateNode, TemplateSource,
     },
     error::ScarffResult,
 };
 use tracing::instrument;
 
+use crate::content::ContentResolvers;
+
 /// Simple renderer using basic variable substitution.
-pub struct SimpleRenderer;
+pub struct SimpleRenderer {
+    resolvers: ContentResolvers,
+}
 
 impl SimpleRenderer {
-    /// Create a new simple renderer.
+    /// Create a new simple renderer that resolves `builtin:` content only.
     pub fn new() -> Self {
-        Self
+        Self::with_resolvers(ContentResolvers::builtin())
+    }
+
+    /// Create a renderer that resolves external content through `resolvers`.
+    pub fn with_resolvers(resolvers: ContentResolvers) -> Self {
+        Self { resolvers }
     }
 }
 
@@ -39,38 +49,336 @@ impl TemplateRenderer for SimpleRenderer {
         // Validate template first
         validator::validate_template(template).map_err(scarff_core::error::ScarffError::Domain)?;
 
+        if template.engine != RenderEngine::Simple {
+            return Err(ApplicationError::RenderingFailed {
+                reason: format!(
+                    "template '{}' uses the '{}' engine; render it with EngineRenderer",
+                    template.id, template.engine
+                ),
+            }
+            .into());
+        }
+
+        // Templates that opted out of strict mode render leniently even when
+        // the caller asked for strictness.
+        let context = if context.is_strict() && !template.strict {
+            Cow::Owned(context.clone().strict(false))
+        } else {
+            Cow::Borrowed(context)
+        };
+
         let mut structure = ProjectStructure::new(output_root);
+        let mut unresolved = Vec::new();
 
-        // Render each node
-        for node in &template.tree.nodes {
+        // Render each node whose `when` condition holds
+        for node in template.tree.nodes_for(&context) {
             match node {
                 TemplateNode::File(spec) => {
-                    let content = render_content(&spec.content, context)?;
-                    structure.add_file(spec.path.as_path(), content, spec.permissions);
+                    let path = render_path(&spec.path, &context, &mut unresolved)?;
+                    match self.render_content(&spec.content, &context)? {
+                        Ok(content) => {
+                            if let Some(path) = path {
+                                structure.add_file(path.into_path_buf(), content, spec.permissions);
+                            }
+                        }
+                        // Keep going so every unresolved placeholder is reported at once.
+                        Err(DomainError::UnresolvedPlaceholders { placeholders }) => {
+                            unresolved.extend(placeholders.into_iter().map(|p| {
+                                format!("{}:{}:{}  {{{{{}}}}}", spec.path, p.line, p.column, p.name)
+                            }));
+                        }
+                        Err(err) => {
+                            return Err(ApplicationError::RenderingFailed {
+                                reason: format!("{}: {err}", spec.path),
+                            }
+                            .into());
+                        }
+                    }
                 }
                 TemplateNode::Directory(spec) => {
-                    structure.add_directory(spec.path.as_path(), spec.permissions);
+                    if let Some(path) = render_path(&spec.path, &context, &mut unresolved)? {
+                        structure.add_directory(path.into_path_buf(), spec.permissions);
+                    }
                 }
             }
         }
 
+        if !unresolved.is_empty() {
+            return Err(ApplicationError::RenderingFailed {
+                reason: format!(
+                    "{} unresolved placeholder(s):\n  {}",
+                    unresolved.len(),
+                    unresolved.join("\n  ")
+                ),
+            }
+            .into());
+        }
+
         // Validate final structure
         validator::validate_project_structure(&structure)
             .map_err(scarff_core::error::ScarffError::Domain)?;
 
         Ok(structure)
     }
+
+    fn resolve_external(&self, id: &ContentTemplateId) -> ScarffResult<TemplateSource> {
+        self.resolvers.resolve(id)
+    }
 }
 
-fn render_content(content: &TemplateContent, ctx: &RenderContext) -> ScarffResult<String> {
-    match content {
-        TemplateContent::Literal(source) => Ok(source.as_str().to_string()),
-        TemplateContent::Parameterized(source) => Ok(ctx.render(source.as_str())),
-        TemplateContent::External(_) => Err(
-            scarff_core::application::ApplicationError::RenderingFailed {
-                reason: "External templates not supported by SimpleRenderer".into(),
+impl SimpleRenderer {
+    /// Render one file's content. Resolution failures abort the render
+    /// (outer error); rendering failures are returned for the caller to
+    /// collect (inner error).
+    fn render_content(
+        &self,
+        content: &TemplateContent,
+        ctx: &RenderContext,
+    ) -> ScarffResult<Result<FileContent, DomainError>> {
+        Ok(match content {
+            TemplateContent::Literal(source) => Ok(source.as_str().into()),
+            TemplateContent::Parameterized(source) => ctx.render_source(source).map(Into::into),
+            TemplateContent::External(id) => {
+                let source = self.resolvers.resolve(id)?;
+                ctx.render_source(&source).map(Into::into)
             }
-            .into(),
-        ),
+            TemplateContent::Binary(source) => Ok(source.as_bytes().to_vec().into()),
+        })
+    }
+}
+
+/// Render a file or directory path. Unresolved placeholders are collected
+/// like those in file content; `None` means the path could not be rendered.
+fn render_path(
+    path: &RelativePath,
+    context: &RenderContext,
+    unresolved: &mut Vec<String>,
+) -> ScarffResult<Option<RelativePath>> {
+    match context.render_path(path) {
+        Ok(rendered) => Ok(Some(rendered)),
+        Err(DomainError::UnresolvedPlaceholders { placeholders }) => {
+            unresolved.extend(
+                placeholders
+                    .into_iter()
+                    .map(|p| format!("{path} (path)  {{{{{}}}}}", p.name)),
+            );
+            Ok(None)
+        }
+        Err(err) => Err(ApplicationError::RenderingFailed {
+            reason: format!("{path}: {err}"),
+        }
+        .into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scarff_core::domain::{
+        ContentTemplateId, FileSpec, RunInfo, TargetMatcher, TemplateId, TemplateMetadata,
+        TemplateSource,
+    };
+
+    fn template(strict: bool, files: &[(&str, &'static str)]) -> Template {
+        let mut builder = Template::builder()
+            .id(TemplateId::new("t", "1.0.0"))
+            .matcher(TargetMatcher::builder().build())
+            .metadata(TemplateMetadata::new("T"))
+            .strict(strict);
+        for (path, content) in files {
+            builder = builder.add_node(TemplateNode::File(FileSpec::new(
+                *path,
+                TemplateContent::Parameterized(TemplateSource::from(*content)),
+            )));
+        }
+        builder.build().unwrap()
+    }
+
+    #[test]
+    fn strict_render_lists_every_unresolved_placeholder_with_location() {
+        let template = template(
+            true,
+            &[
+                (
+                    "a.toml",
+                    "name = \"{{PROJECT_NAME}}\"\nurl = \"{{DB_URL}}\"",
+                ),
+                ("b.rs", "// {{AUTHOR}}"),
+            ],
+        );
+        let ctx = RenderContext::new("demo").strict(true);
+
+        let err = SimpleRenderer::new()
+            .render(&template, &ctx, Path::new("/out"))
+            .unwrap_err()
+            .to_string();
+
+        assert!(err.contains("2 unresolved placeholder(s)"), "{err}");
+        assert!(err.contains("a.toml:2:8  {{DB_URL}}"), "{err}");
+        assert!(err.contains("b.rs:1:4  {{AUTHOR}}"), "{err}");
+    }
+
+    #[test]
+    fn template_can_opt_out_of_strict_rendering() {
+        let template = template(false, &[("a.txt", "{{UNKNOWN}}")]);
+        let ctx = RenderContext::new("demo").strict(true);
+
+        let structure = SimpleRenderer::new()
+            .render(&template, &ctx, Path::new("/out"))
+            .unwrap();
+
+        assert_eq!(structure.files().next().unwrap().content, "{{UNKNOWN}}");
+    }
+
+    #[test]
+    fn external_content_is_resolved_and_rendered() {
+        let template = Template::builder()
+            .id(TemplateId::new("t", "1.0.0"))
+            .matcher(TargetMatcher::builder().build())
+            .metadata(TemplateMetadata::new("T"))
+            .add_node(TemplateNode::File(FileSpec::new(
+                "LICENSE",
+                TemplateContent::External(ContentTemplateId("builtin:mit")),
+            )))
+            .build()
+            .unwrap();
+        let ctx = RenderContext::new("demo")
+            .with_run_info(&RunInfo::now())
+            .strict(true);
+        let year = ctx.get("YEAR").unwrap().to_string();
+
+        let structure = SimpleRenderer::new()
+            .render(&template, &ctx, Path::new("/out"))
+            .unwrap();
+
+        let license = structure.files().next().unwrap().content.as_text().unwrap();
+        assert!(license.starts_with("MIT License"), "{license}");
+        assert!(
+            license.contains(&format!("Copyright (c) {year} The demo Authors")),
+            "{license}"
+        );
+    }
+
+    #[test]
+    fn unknown_external_content_fails_the_render() {
+        let template = Template::builder()
+            .id(TemplateId::new("t", "1.0.0"))
+            .matcher(TargetMatcher::builder().build())
+            .metadata(TemplateMetadata::new("T"))
+            .add_node(TemplateNode::File(FileSpec::new(
+                "LICENSE",
+                TemplateContent::External(ContentTemplateId("file:LICENSE")),
+            )))
+            .build()
+            .unwrap();
+
+        let err = SimpleRenderer::new()
+            .render(&template, &RenderContext::new("demo"), Path::new("/out"))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("file:LICENSE"), "{err}");
+    }
+
+    #[test]
+    fn file_and_directory_paths_are_rendered_and_checked() {
+        let mut packaged = template(
+            true,
+            &[(
+                "src/{{PROJECT_NAME_SNAKE}}/__init__.py",
+                "# {{PROJECT_NAME}}",
+            )],
+        );
+        packaged.tree.nodes.push(TemplateNode::Directory(
+            scarff_core::domain::DirectorySpec::new("cmd/{{PROJECT_NAME_KEBAB}}"),
+        ));
+        let ctx = RenderContext::new("My App").strict(true);
+
+        let structure = SimpleRenderer::new()
+            .render(&packaged, &ctx, Path::new("/out"))
+            .unwrap();
+
+        let file = structure.files().next().unwrap();
+        assert_eq!(file.path, Path::new("src/my_app/__init__.py"));
+        assert_eq!(file.content, "# My App");
+        let dir = structure.directories().next().unwrap();
+        assert_eq!(dir.path, Path::new("cmd/my-app"));
+
+        for (path, expected) in [
+            ("{{PARENT}}/x", "'..' leaves the project directory"),
+            ("src/{{EMPTY}}/x", "empty path segment"),
+            ("{{ROOT}}etc/x", "Absolute paths not allowed"),
+        ] {
+            let template = template(false, &[(path, "")]);
+            let ctx = RenderContext::new("demo")
+                .with_variable("PARENT", "..")
+                .with_variable("EMPTY", "")
+                .with_variable("ROOT", "/");
+            let err = SimpleRenderer::new()
+                .render(&template, &ctx, Path::new("/out"))
+                .unwrap_err()
+                .to_string();
+            assert!(err.contains(expected), "{path}: {err}");
+        }
+    }
+
+    #[test]
+    fn binary_content_is_copied_unrendered() {
+        let bytes = b"\x89PNG\r\n{{PROJECT_NAME}}\xff".to_vec();
+        let assets = Template::builder()
+            .id(TemplateId::new("t", "1.0.0"))
+            .matcher(TargetMatcher::builder().build())
+            .metadata(TemplateMetadata::new("T"))
+            .add_node(TemplateNode::File(FileSpec::new(
+                "assets/{{PROJECT_NAME_KEBAB}}.png",
+                TemplateContent::Binary(bytes.clone().into()),
+            )))
+            .build()
+            .unwrap();
+        let ctx = RenderContext::new("My App").strict(true);
+
+        let structure = SimpleRenderer::new()
+            .render(&assets, &ctx, Path::new("/out"))
+            .unwrap();
+
+        let file = structure.files().next().unwrap();
+        assert_eq!(file.path, Path::new("assets/my-app.png"));
+        assert_eq!(file.content.as_bytes(), bytes);
+        assert!(file.content.is_binary());
+    }
+
+    #[test]
+    fn rendered_path_collisions_fail_validation() {
+        let template = template(false, &[("{{A}}.txt", "a"), ("{{B}}.txt", "b")]);
+        let ctx = RenderContext::new("demo")
+            .with_variable("A", "same")
+            .with_variable("B", "same");
+
+        let err = SimpleRenderer::new()
+            .render(&template, &ctx, Path::new("/out"))
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("Duplicate path in template: same.txt"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn strict_render_reports_unresolved_placeholders_in_paths() {
+        let template = template(true, &[("src/{{PACKAGE}}.rs", "{{AUTHOR}}")]);
+
+        let err = SimpleRenderer::new()
+            .render(
+                &template,
+                &RenderContext::new("demo").strict(true),
+                Path::new("/out"),
+            )
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("src/{{PACKAGE}}.rs (path)  {{PACKAGE}}"),
+            "{err}"
+        );
+        assert!(err.contains("{{AUTHOR}}"), "{err}");
     }
 }
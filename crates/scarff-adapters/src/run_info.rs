@@ -0,0 +1,331 @@
+//! Run-dependent render variables from the local machine.
+//!
+//! [`SystemRunInfo`] implements the `RunInfoProvider` port: the system clock,
+//! a random id, and the git identity (`user.name`, `user.email`) read
+//! directly from git's config files. git itself is never executed and
+//! nothing touches the network.
+//!
+//! # Config files
+//!
+//! Read in git's order, later files overriding earlier ones:
+//!
+//! 1. `/etc/gitconfig` (skipped when `GIT_CONFIG_NOSYSTEM` is set)
+//! 2. `$XDG_CONFIG_HOME/git/config` (default `~/.config/git/config`)
+//! 3. `~/.gitconfig`
+//!
+//! `GIT_CONFIG_GLOBAL` replaces 2 and 3. `include` / `includeIf` directives
+//! are not followed.
+
+use std::{env, fs, path::PathBuf};
+
+use scarff_core::{application::ports::RunInfoProvider, domain::RunInfo};
+use tracing::debug;
+
+/// Clock, random id and git identity of the current machine.
+#[derive(Debug, Clone)]
+pub struct SystemRunInfo {
+    git_config_files: Vec<PathBuf>,
+}
+
+impl SystemRunInfo {
+    /// Read the git identity from git's standard config locations.
+    pub fn new() -> Self {
+        Self::with_git_config_files(default_git_config_files())
+    }
+
+    /// Read the git identity from `files`, in order of increasing precedence.
+    pub fn with_git_config_files(files: impl IntoIterator<Item = PathBuf>) -> Self {
+        Self {
+            git_config_files: files.into_iter().collect(),
+        }
+    }
+
+    fn git_identity(&self) -> GitIdentity {
+        let mut identity = GitIdentity::default();
+        for path in &self.git_config_files {
+            if let Ok(text) = fs::read_to_string(path) {
+                debug!(path = %path.display(), "reading git config");
+                identity.merge(parse_git_identity(&text));
+            }
+        }
+        identity
+    }
+}
+
+impl Default for SystemRunInfo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RunInfoProvider for SystemRunInfo {
+    fn run_info(&self) -> RunInfo {
+        let identity = self.git_identity();
+        RunInfo {
+            git_user_name: identity.name,
+            git_user_email: identity.email,
+            ..RunInfo::now()
+        }
+    }
+}
+
+fn default_git_config_files() -> Vec<PathBuf> {
+    let mut files = Vec::new();
+
+    if env::var_os("GIT_CONFIG_NOSYSTEM").is_none() {
+        files.push(PathBuf::from("/etc/gitconfig"));
+    }
+
+    if let Some(global) = env::var_os("GIT_CONFIG_GLOBAL") {
+        files.push(PathBuf::from(global));
+        return files;
+    }
+
+    let xdg = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+        .or_else(|| dirs::home_dir().map(|home| home.join(".config")));
+    if let Some(xdg) = xdg {
+        files.push(xdg.join("git").join("config"));
+    }
+    if let Some(home) = dirs::home_dir() {
+        files.push(home.join(".gitconfig"));
+    }
+
+    files
+}
+
+// ── git config parsing ────────────────────────────────────────────────────────
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct GitIdentity {
+    name: Option<String>,
+    email: Option<String>,
+}
+
+impl GitIdentity {
+    fn merge(&mut self, other: Self) {
+        if other.name.is_some() {
+            self.name = other.name;
+        }
+        if other.email.is_some() {
+            self.email = other.email;
+        }
+    }
+}
+
+/// Extract `user.name` / `user.email` from one config file. Within a file the
+/// last assignment wins, as in git.
+fn parse_git_identity(text: &str) -> GitIdentity {
+    let mut identity = GitIdentity::default();
+    let mut in_user = false;
+
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(['#', ';']) {
+            continue;
+        }
+
+        if let Some(header) = line.strip_prefix('[') {
+            // `[user]` only; `[user "x"]` is a subsection with other meaning.
+            let name = header.split(']').next().unwrap_or_default().trim();
+            in_user = name.eq_ignore_ascii_case("user");
+            continue;
+        }
+
+        if !in_user {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue; // `key` alone is a boolean; not an identity field.
+        };
+        let value = parse_value(value);
+        match key.trim().to_ascii_lowercase().as_str() {
+            "name" => identity.name = Some(value).filter(|v| !v.is_empty()),
+            "email" => identity.email = Some(value).filter(|v| !v.is_empty()),
+            _ => {}
+        }
+    }
+
+    identity
+}
+
+/// Unquote a config value, honouring escapes and trailing comments.
+fn parse_value(raw: &str) -> String {
+    let mut value = String::new();
+    let mut quoted = false;
+    // Unquoted trailing whitespace is dropped; quoted whitespace is kept.
+    let mut keep = 0;
+    let mut chars = raw.trim_start().chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => quoted = !quoted,
+            '#' | ';' if !quoted => break,
+            '\\' => match chars.next() {
+                Some('n') => value.push('\n'),
+                Some('t') => value.push('\t'),
+                Some(other) => value.push(other),
+                None => {}
+            },
+            c => value.push(c),
+        }
+        if quoted || !value.ends_with(char::is_whitespace) {
+            keep = value.len();
+        }
+    }
+
+    value.truncate(keep);
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn parses_user_section() {
+        let identity = parse_git_identity(
+            "[core]\n\tname = not-me\n\
+             [user \"work\"]\n\tname = also-not-me\n\
+             [User]\n\tName = \"Ada \\\"Countess\\\" Lovelace\" ; comment\n\
+             \temail = ada@example.com # comment\n",
+        );
+        assert_eq!(
+            identity,
+            GitIdentity {
+                name: Some("Ada \"Countess\" Lovelace".into()),
+                email: Some("ada@example.com".into()),
+            }
+        );
+    }
+
+    #[test]
+    fn later_files_override_earlier_ones() {
+        let dir = TempDir::new().unwrap();
+        let system = dir.path().join("system");
+        let global = dir.path().join("global");
+        fs::write(&system, "[user]\nname = System\nemail = sys@example.com\n").unwrap();
+        fs::write(&global, "[user]\nname = Global\n").unwrap();
+
+        let info =
+            SystemRunInfo::with_git_config_files([system, dir.path().join("missing"), global])
+                .run_info();
+
+        assert_eq!(info.git_user_name.as_deref(), Some("Global"));
+        assert_eq!(info.git_user_email.as_deref(), Some("sys@example.com"));
+        assert_eq!(info.random_id.len(), 8);
+    }
+
+    #[test]
+    fn pinned_runs_render_the_same_digest_on_any_machine() {
+        use crate::{EngineRenderer, InMemoryStore, MemoryFilesystem};
+        use scarff_core::{
+            application::{Determinism, ScaffoldOptions, ScaffoldService, ports::TemplateStore},
+            domain::{
+                ContentTemplateId, FileSpec, Language, Target, TargetMatcher, Template,
+                TemplateContent, TemplateId, TemplateMetadata, TemplateNode, TemplateSource,
+            },
+        };
+
+        let dir = TempDir::new().unwrap();
+        let machine = |name: &str| {
+            let config = dir.path().join(name);
+            fs::write(
+                &config,
+                format!("[user]\nname = {name}\nemail = {name}@example.com\n"),
+            )
+            .unwrap();
+            SystemRunInfo::with_git_config_files([config])
+        };
+        let digest = |run_info: SystemRunInfo, determinism: Determinism| {
+            let store = InMemoryStore::new();
+            store
+                .insert(
+                    Template::builder()
+                        .id(TemplateId::new("t", "1.0.0"))
+                        .matcher(TargetMatcher::default())
+                        .metadata(TemplateMetadata::new("T"))
+                        .add_node(TemplateNode::File(FileSpec::new(
+                            "LICENSE",
+                            TemplateContent::External(ContentTemplateId("builtin:mit")),
+                        )))
+                        .add_node(TemplateNode::File(FileSpec::new(
+                            "AUTHORS",
+                            TemplateContent::Parameterized(TemplateSource::from(
+                                "{{GIT_USER_NAME}} <{{GIT_USER_EMAIL}}> {{RANDOM_ID}}\n",
+                            )),
+                        )))
+                        .build()
+                        .unwrap(),
+                )
+                .unwrap();
+            let service = ScaffoldService::new(
+                Box::new(store),
+                Box::new(EngineRenderer::new()),
+                Box::new(MemoryFilesystem::new()),
+            )
+            .with_run_info_provider(Box::new(run_info));
+            let target = Target::builder().language(Language::Rust).build().unwrap();
+            let options = ScaffoldOptions::new().determinism(determinism);
+            service
+                .render(target, "app", "app", &options)
+                .unwrap()
+                .structure
+                .digest()
+        };
+        let pinned = Determinism {
+            source_date_epoch: Some(1_700_000_000),
+            seed: Some(7),
+            ..Determinism::default()
+        };
+
+        assert_eq!(
+            digest(machine("laptop"), pinned.clone()),
+            digest(machine("ci"), pinned.clone())
+        );
+        let author = Determinism {
+            git_user_name: Some("Ada".into()),
+            git_user_email: Some("ada@example.com".into()),
+            ..pinned.clone()
+        };
+        assert_eq!(
+            digest(machine("laptop"), author.clone()),
+            digest(machine("ci"), author.clone())
+        );
+        assert_ne!(digest(machine("ci"), author), digest(machine("ci"), pinned));
+    }
+
+    #[test]
+    fn prompt_context_sees_the_same_run_info_as_rendering() {
+        use crate::{EngineRenderer, InMemoryStore, MemoryFilesystem};
+        use scarff_core::{
+            application::{Determinism, ScaffoldOptions, ScaffoldService},
+            domain::{Language, Target},
+        };
+
+        let dir = TempDir::new().unwrap();
+        let config = dir.path().join("gitconfig");
+        fs::write(&config, "[user]\nname = Ada\n").unwrap();
+        let service = ScaffoldService::new(
+            Box::new(InMemoryStore::new()),
+            Box::new(EngineRenderer::new()),
+            Box::new(MemoryFilesystem::new()),
+        )
+        .with_run_info_provider(Box::new(SystemRunInfo::with_git_config_files([config])));
+        let target = Target::builder().language(Language::Rust).build().unwrap();
+        let live = service.render_context(&target, "app", &[], &ScaffoldOptions::new());
+        let pinned = ScaffoldOptions::new().determinism(Determinism {
+            seed: Some(7),
+            ..Determinism::default()
+        });
+        let first = service.render_context(&target, "app", &[], &pinned);
+        let second = service.render_context(&target, "app", &[], &pinned);
+
+        assert_eq!(live.get("GIT_USER_NAME"), Some("Ada"));
+        assert_eq!(first.get("GIT_USER_NAME"), Some(""));
+        assert!(first.get("RANDOM_ID").is_some());
+        assert_eq!(first.get("RANDOM_ID"), second.get("RANDOM_ID"));
+    }
+}
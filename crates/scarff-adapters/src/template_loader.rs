@@ -8,6 +8,10 @@
 //!
 //! ```text
 //! templates/
+//! ├── _shared/                 ← `workspace:` content; `_`-prefixed dirs are not templates
+//! ├── _partials/               ← snippets included with {{> name}}
+//! │   ├── rustfmt.toml
+//! │   └── ci/rust.yml
 //! ├── rust-cli-layered/
 //! │   ├── template.toml        ← manifest (required)
 //! │   ├── src/
@@ -25,6 +29,10 @@
 //! [template]
 //! id      = "rust-cli-layered"   # unique identifier
 //! version = "1.0.0"
+//! strict  = false                # optional; allow unresolved {{ }} (default: true)
+//! engine  = "jinja"              # optional; simple (default) | jinja
+//! extends = "rust-axum-base@1.0.0"   # optional; inherit another template
+//! remove  = ["src/legacy.rs"]        # optional; base paths to leave out
 //!
 //! [matcher]
 //! language     = "rust"          # rust | python | typescript | go
@@ -38,13 +46,154 @@
 //! author      = "Scarff"                        # optional
 //! tags        = ["rust", "cli"]                 # optional
 //!
+//! # Optional: inputs beyond the built-in variables, used as {{db}} etc.
+//! # type = string (default) | bool | int | choice | list
+//! [[variables]]
+//! name        = "db"
+//! type        = "choice"
+//! choices     = ["postgres", "sqlite"]
+//! default     = "postgres"
+//! description = "Database backend"
+//!
+//! [[variables]]
+//! name    = "db_url"
+//! default = "{{db}}://localhost/{{PROJECT_NAME_SNAKE}}"  # may reference others
+//! pattern = '^\w+://.+$'        # optional regex, must match in full
+//! when    = "db != 'none'"      # optional; asked for only when true
+//!
 //! # Optional: override per-file content type.
 //! # If omitted, files containing {{ }} are auto-detected as parameterized.
+//! # Foreign brace syntax can be escaped as \{{ … }} or wrapped in
+//! # {{{{raw}}}} … {{{{/raw}}}}.
 //! [[files]]
 //! path        = "LICENSE"
 //! type        = "external"       # literal | parameterized | external
 //! external_id = "builtin:mit"    # required when type = "external"
+//!
+//! # Optional: emit a file or directory only when a condition holds. The
+//! # condition sees the declared variables and the built-ins (LANGUAGE, KIND,
+//! # FRAMEWORK, ARCHITECTURE, …); any other name is rejected at load time.
+//! # A false directory drops its contents too.
+//! [[files]]
+//! path = "docker-compose.yml"
+//! when = "db != 'none'"
+//!
+//! [[directories]]
+//! path = "tests"
+//! when = "with_tests"
+//! ```
+//!
+//! File and directory paths are templates too: a file stored on disk as
+//! `src/{{PROJECT_NAME_SNAKE}}/__init__.py` is written to `src/my_app/__init__.py`.
+//! A rendered path must stay relative, with no `..` or empty segments.
+//!
+//! # Inheritance
+//!
+//! A template with `extends` starts from its base's files, variables and
+//! `[[format]]` rules. Its own files replace base files at the same path and
+//! add the rest; `remove` drops base files or whole directories. Bases may
+//! extend further bases, both must use the same engine, and a cycle fails
+//! every template on it. Only the flattened result is validated, so a child
+//! may consist of nothing but a manifest.
+//!
+//! # Overlays
+//!
+//! A template with an `[overlay]` section is never picked as the base for a
+//! target. It is layered on top of the base when named with `--with`
+//! (`scarff new app --with docker,ci`); its `[matcher]` selects the variant
+//! for the target, as for base templates. Its files are added to the
+//! project, its variables and `[[format]]` rules to the base's. Writing a
+//! file the project already has fails the run unless a merge entry covers
+//! it:
+//!
+//! ```toml
+//! [overlay]
+//! name = "docker"                # optional; defaults to the template id
+//!
+//! [[overlay.merge]]
+//! glob     = ".gitignore"
+//! strategy = "append"            # replace | append | keep
+//!
+//! [[overlay.merge]]
+//! glob     = "{Cargo.toml,.env.example}"
+//! strategy = "merge"             # by file name; or toml | json | lines | dotenv
+//! ```
+//!
+//! `merge` combines the two files structurally (TOML and JSON deep merge,
+//! line union for ignore files, key merge for dotenv) and keeps the
+//! existing file's order and comments.
+//!
+//! # Formatting
+//!
+//! `[[format]]` entries tidy up rendered files by glob; every matching entry
+//! applies, later ones winning. Binary files are never touched.
+//!
+//! ```toml
+//! [[format]]
+//! glob                     = "**"
+//! line_endings             = "lf"     # lf | crlf
+//! trim_trailing_whitespace = true
+//! final_newline            = true     # exactly one (true) or none (false)
+//!
+//! [[format]]
+//! glob        = "*.py"
+//! indent      = "spaces"              # spaces | tabs
+//! indent_size = 4                     # default 4
+//!
+//! [[format]]
+//! glob = "*.{bat,cmd}"
+//! line_endings = "crlf"
+//! bom = false                         # add (true) or strip (false) a UTF-8 BOM
 //! ```
+//!
+//! # Hooks
+//!
+//! `[[hooks.post]]` entries are commands run, in order, in the written
+//! project: the base template's first, then each overlay's. They run
+//! without a shell; `args` are passed as they are, after placeholders in
+//! them, the command and `working_dir` are filled in. `scarff new
+//! --no-hooks` skips them, and a failing hook stops the rest but leaves the
+//! project in place.
+//!
+//! ```toml
+//! [[hooks.post]]
+//! command = "git"
+//! args    = ["init", "--quiet"]
+//!
+//! [[hooks.post]]
+//! command     = "npm"
+//! args        = ["install"]
+//! working_dir = "web"                 # optional; relative to the project root
+//! when        = "with_frontend"       # optional; same conditions as [[files]]
+//! ```
+//!
+//! Hooks of templates outside the built-in set only run once the user has
+//! seen the commands and trusted them, or passed `--trust`; the trust lapses
+//! when the template, a file it pulls in, or a rendered command changes.
+//! Whatever the template, each command must be in the `[hooks]
+//! allowed_commands` of the user config when that is set.
+//!
+//! # Partials
+//!
+//! Files of simple-engine templates can include snippets from `_partials/`
+//! in the templates root: `{{> rustfmt.toml}}`, `{{> ci/rust.yml}}`. They are
+//! inlined when the template is loaded (a tag alone on its line takes the
+//! tag's indentation), may include each other, and are rendered with the
+//! including file's variables. An unknown partial fails the template's load.
+//! Jinja templates use `{% include %}` instead, of files in their own
+//! `_macros/` or `_includes/` directory: those are never written to the
+//! project.
+//!
+//! # Binary files
+//!
+//! A file that is not valid UTF-8 (an image, a font, a `.jar`) is copied byte
+//! for byte: it is never rendered or expanded and its `type` is ignored. Its
+//! path may still contain placeholders.
+//!
+//! External ids are resolved at render time by scheme: `builtin:<name>`
+//! (embedded snippets), `file:<path>` (relative to the templates root) and
+//! `workspace:<path>` (relative to `<templates root>/_shared/`); see
+//! [`crate::content`].
 
 use std::{
     collections::{HashMap, HashSet},
@@ -57,10 +206,14 @@ use serde::Deserialize;
 use tracing::{debug, instrument, warn};
 use walkdir::WalkDir;
 
+use crate::renderer::is_jinja_helper;
+
 use scarff_core::domain::{
-    Architecture, ContentTemplateId, DirectorySpec, DomainError, FileSpec, Framework, Language,
-    ProjectKind, RelativePath, TargetMatcher, Template, TemplateBuilder, TemplateContent,
-    TemplateId, TemplateMetadata, TemplateNode, TemplateSource, TemplateTree,
+    Architecture, BinarySource, Condition, ContentTemplateId, DirectorySpec, DomainError, FileSpec,
+    FormatOptions, FormatRule, Framework, Glob, Hook, Indent, Language, LineEnding, MergeRule,
+    MergeStrategy, Overlay, ProjectKind, RelativePath, RenderEngine, RenderValue, TargetMatcher,
+    Template, TemplateBuilder, TemplateContent, TemplateId, TemplateMetadata, TemplateNode,
+    TemplateSource, TemplateTree, VariableKind, VariableSpec, expand_partials,
 };
 
 // ── String interning ──────────────────────────────────────────────────────────
@@ -94,6 +247,16 @@ fn intern(s: &str) -> &'static str {
     leaked
 }
 
+/// Directory in the templates root holding `{{> name}}` partials.
+const PARTIALS_DIR: &str = "_partials";
+
+/// Top-level directories starting with `_` hold shared content, not templates.
+fn is_reserved_dir(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with('_'))
+}
+
 // ── Manifest types ────────────────────────────────────────────────────────────
 
 /// Deserialised representation of a `template.toml` file.
@@ -105,12 +268,22 @@ pub struct TemplateManifest {
     pub template: TemplateSection,
     pub matcher: MatcherSection,
     pub metadata: MetadataSection,
-    /// Explicit per-file type overrides.  Files not listed here are
-    /// auto-detected: content containing `{{` … `}}` is [`FileType::Parameterized`],
-    /// everything else is [`FileType::Literal`].
+    /// Explicit per-file type overrides and conditions.  Files not listed
+    /// here, or listed without a `type`, are auto-detected: content containing renderer syntax is
+    /// [`FileType::Parameterized`], everything else is [`FileType::Literal`].
     pub files: Option<Vec<FileEntry>>,
-    /// Directories that must exist even if they contain no tracked files.
+    /// Directories that must exist even if they contain no tracked files,
+    /// or that carry a `when` condition.
     pub directories: Option<Vec<DirectoryEntry>>,
+    /// Inputs the template needs beyond the built-in variables.
+    pub variables: Option<Vec<VariableEntry>>,
+    /// Tidy-up rules for rendered files, by glob.
+    pub format: Option<Vec<FormatEntry>>,
+    /// Present for overlays, which are layered onto a base template with
+    /// `--with` instead of being resolved as one.
+    pub overlay: Option<OverlaySection>,
+    /// Commands run in the project once it is written.
+    pub hooks: Option<HooksSection>,
 }
 
 /// `[template]` section — identity of the template.
@@ -120,6 +293,18 @@ pub struct TemplateSection {
     pub id: String,
     /// SemVer string, e.g. `"1.0.0"`.
     pub version: String,
+    /// Set to `false` to leave unresolved placeholders in the output instead
+    /// of failing the render. Defaults to `true`.
+    pub strict: Option<bool>,
+    /// Template language for parameterized files: `"simple"` (default) or
+    /// `"jinja"`.
+    pub engine: Option<String>,
+    /// Base template as `"id@version"`; its files, variables and format
+    /// rules are inherited.
+    pub extends: Option<String>,
+    /// Paths of the base template to leave out; a directory drops everything
+    /// under it.
+    pub remove: Option<Vec<String>>,
 }
 
 /// `[matcher]` section — criteria used to select this template.
@@ -153,12 +338,16 @@ pub struct MetadataSection {
 pub struct FileEntry {
     /// Relative path from the template root (e.g. `"src/main.rs"`).
     pub path: String,
-    /// Content handling strategy (see [`FileType`]).
+    /// Content handling strategy (see [`FileType`]); auto-detected when
+    /// omitted.
     #[serde(rename = "type")]
-    pub file_type: FileType,
+    pub file_type: Option<FileType>,
     /// Required when `type = "external"` — identifies the built-in content to
     /// embed (e.g. `"builtin:mit"`).
     pub external_id: Option<String>,
+    /// Condition on variables and target, e.g. `"db != 'none'"`; the file is
+    /// only emitted when it is true.
+    pub when: Option<String>,
 }
 
 /// Controls how a file's content is treated during scaffolding.
@@ -180,6 +369,245 @@ pub enum FileType {
 pub struct DirectoryEntry {
     /// Relative path (e.g. `"src/generated"`).
     pub path: String,
+    /// Condition on variables and target; when false neither the directory
+    /// nor anything under it is emitted.
+    pub when: Option<String>,
+}
+
+/// One entry under `[[variables]]`.
+#[derive(Debug, Deserialize, Clone)]
+pub struct VariableEntry {
+    /// Name used in templates: `{{db}}`.
+    pub name: String,
+    /// Value type; defaults to `string`.
+    #[serde(rename = "type", default)]
+    pub var_type: VariableType,
+    /// Help text shown when asking for the value.
+    pub description: Option<String>,
+    /// Value used when none is supplied; strings may reference other
+    /// variables (`"{{PROJECT_NAME_SNAKE}}_db"`).
+    pub default: Option<toml::Value>,
+    /// Whether a value must be supplied when there is no default.
+    /// Defaults to `true`.
+    pub required: Option<bool>,
+    /// Regex the value (each item, for lists) must match in full.
+    pub pattern: Option<String>,
+    /// Allowed values for `choice` and `list`.
+    pub choices: Option<Vec<String>>,
+    /// Inclusive lower bound for `int`.
+    pub min: Option<i64>,
+    /// Inclusive upper bound for `int`.
+    pub max: Option<i64>,
+    /// Condition on earlier variables, e.g. `"db != 'none'"`; the variable
+    /// is skipped when it is false.
+    pub when: Option<String>,
+}
+
+/// One entry under `[[format]]`. Every setting is optional; later entries
+/// override earlier ones for the files both match.
+#[derive(Debug, Deserialize, Clone)]
+pub struct FormatEntry {
+    /// Files the entry applies to, e.g. `"**/*.py"` or `"Makefile"`.
+    pub glob: String,
+    /// `"lf"` or `"crlf"`.
+    pub line_endings: Option<String>,
+    pub trim_trailing_whitespace: Option<bool>,
+    /// End with exactly one line break (`true`) or none (`false`).
+    pub final_newline: Option<bool>,
+    /// `"spaces"` or `"tabs"`.
+    pub indent: Option<String>,
+    /// Columns per indentation level; defaults to 4.
+    pub indent_size: Option<usize>,
+    /// Start with a UTF-8 byte order mark (`true`) or strip it (`false`).
+    pub bom: Option<bool>,
+}
+
+impl FormatEntry {
+    /// Convert to the domain rule.
+    fn to_rule(&self) -> Result<FormatRule, DomainError> {
+        let invalid = |reason: String| {
+            DomainError::InvalidTemplate(format!("[[format]] '{}': {reason}", self.glob))
+        };
+
+        let glob = Glob::new(&self.glob)?;
+        let line_ending = self
+            .line_endings
+            .as_deref()
+            .map(|s| s.parse::<LineEnding>())
+            .transpose()?;
+        let width = match self.indent_size {
+            Some(0) => return Err(invalid("indent_size must be at least 1".into())),
+            Some(width) => width,
+            None => 4,
+        };
+        let indent = match self.indent.as_deref() {
+            None => None,
+            Some(s) if s.eq_ignore_ascii_case("spaces") => Some(Indent::Spaces(width)),
+            Some(s) if s.eq_ignore_ascii_case("tabs") => Some(Indent::Tabs(width)),
+            Some(other) => {
+                return Err(invalid(format!(
+                    "unknown indent '{other}'; expected spaces or tabs"
+                )));
+            }
+        };
+
+        Ok(FormatRule::new(
+            glob,
+            FormatOptions {
+                line_ending,
+                trim_trailing_whitespace: self.trim_trailing_whitespace,
+                final_newline: self.final_newline,
+                indent,
+                bom: self.bom,
+            },
+        ))
+    }
+}
+
+/// `[overlay]` section — marks the template as an overlay.
+#[derive(Debug, Deserialize, Clone)]
+pub struct OverlaySection {
+    /// Name selected with `--with`; defaults to the template id.
+    pub name: Option<String>,
+    /// How files the project already has are merged, by glob.
+    pub merge: Option<Vec<MergeEntry>>,
+}
+
+/// One entry under `[[overlay.merge]]`; later entries win.
+#[derive(Debug, Deserialize, Clone)]
+pub struct MergeEntry {
+    /// Files the entry applies to, e.g. `".gitignore"`.
+    pub glob: String,
+    /// `"replace"`, `"append"`, `"keep"` or `"merge"` (or a merge format:
+    /// `"toml"`, `"json"`, `"lines"`, `"dotenv"`).
+    pub strategy: String,
+}
+
+impl OverlaySection {
+    /// Convert to the domain marker, naming it `id` unless `name` is set.
+    fn to_overlay(&self, id: &str) -> Result<Overlay, DomainError> {
+        let mut overlay = Overlay::new(self.name.as_deref().unwrap_or(id));
+        for entry in self.merge.iter().flatten() {
+            overlay = overlay.with_merge_rule(MergeRule::new(
+                Glob::new(&entry.glob)?,
+                entry.strategy.parse::<MergeStrategy>()?,
+            ));
+        }
+        Ok(overlay)
+    }
+}
+
+/// `[hooks]` section.
+#[derive(Debug, Deserialize, Clone)]
+pub struct HooksSection {
+    /// Run after the project is written, in order.
+    pub post: Option<Vec<HookEntry>>,
+}
+
+/// One entry under `[[hooks.post]]`.
+#[derive(Debug, Deserialize, Clone)]
+pub struct HookEntry {
+    /// Executable to run, e.g. `"git"`.
+    pub command: String,
+    /// Arguments, passed without a shell.
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Directory to run in, relative to the project root.
+    pub working_dir: Option<String>,
+    /// Condition on variables and target; the hook only runs when it is
+    /// true.
+    pub when: Option<String>,
+}
+
+impl HookEntry {
+    /// Convert to the domain hook.
+    fn to_hook(&self) -> Result<Hook, DomainError> {
+        let mut hook = Hook::new(&self.command).args(&self.args);
+        if let Some(dir) = &self.working_dir {
+            hook = hook.working_dir(RelativePath::parse(dir)?);
+        }
+        if let Some(when) = &self.when {
+            hook = hook.when(parse_condition(&format!("hook '{}'", self.command), when)?);
+        }
+        Ok(hook)
+    }
+}
+
+/// `type` of a `[[variables]]` entry.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum VariableType {
+    #[default]
+    String,
+    Bool,
+    Int,
+    Choice,
+    List,
+}
+
+impl VariableEntry {
+    /// Convert to the domain declaration. Constraint errors (bad regex, empty
+    /// choices, …) are caught by `Template::validate`.
+    fn to_spec(&self) -> Result<VariableSpec, DomainError> {
+        let choices = self.choices.clone().unwrap_or_default();
+        let kind = match self.var_type {
+            VariableType::String => VariableKind::String,
+            VariableType::Bool => VariableKind::Bool,
+            VariableType::Int => VariableKind::Int {
+                min: self.min,
+                max: self.max,
+            },
+            VariableType::Choice => VariableKind::Choice(choices),
+            VariableType::List => VariableKind::List { choices },
+        };
+
+        let mut spec = VariableSpec::new(&self.name)
+            .kind(kind)
+            .required(self.required.unwrap_or(true));
+        if let Some(description) = &self.description {
+            spec = spec.description(description);
+        }
+        if let Some(pattern) = &self.pattern {
+            spec = spec.pattern(pattern);
+        }
+        if let Some(when) = &self.when {
+            spec = spec.when(parse_condition(&format!("variable '{}'", self.name), when)?);
+        }
+        if let Some(default) = &self.default {
+            spec = spec.default_value(toml_to_render_value(default).ok_or_else(|| {
+                DomainError::InvalidTemplate(format!(
+                    "variable '{}': unsupported default value {default}",
+                    self.name
+                ))
+            })?);
+        }
+        Ok(spec)
+    }
+}
+
+/// Convert a TOML value to a render value: scalars become strings, arrays
+/// lists and tables maps. `None` for values that have no sensible string
+/// form (nested datetimes are fine; only arrays of tables are rejected).
+pub(crate) fn toml_to_render_value(value: &toml::Value) -> Option<RenderValue> {
+    Some(match value {
+        toml::Value::String(s) => RenderValue::String(s.clone()),
+        toml::Value::Integer(n) => RenderValue::String(n.to_string()),
+        toml::Value::Float(f) => RenderValue::String(f.to_string()),
+        toml::Value::Boolean(b) => RenderValue::String(b.to_string()),
+        toml::Value::Datetime(d) => RenderValue::String(d.to_string()),
+        toml::Value::Array(items) => RenderValue::List(
+            items
+                .iter()
+                .map(toml_to_render_value)
+                .collect::<Option<_>>()?,
+        ),
+        toml::Value::Table(entries) => RenderValue::Map(
+            entries
+                .iter()
+                .map(|(k, v)| toml_to_render_value(v).map(|v| (k.clone(), v)))
+                .collect::<Option<_>>()?,
+        ),
+    })
 }
 
 // ── Loader ────────────────────────────────────────────────────────────────────
@@ -243,7 +671,7 @@ impl FilesystemTemplateLoader {
             ))
         })?;
 
-        let mut templates = Vec::new();
+        let mut declared = Vec::new();
 
         for entry_result in read_dir {
             let entry = entry_result.map_err(|e| {
@@ -254,8 +682,30 @@ impl FilesystemTemplateLoader {
             if !path.is_dir() {
                 continue; // Only process subdirectories.
             }
+            if is_reserved_dir(&path) {
+                continue; // Shared content, not a template (e.g. `_shared/`).
+            }
+
+            match self.declare_template(&path) {
+                Ok(template) => declared.push(template),
+                Err(e) => {
+                    // One bad template must not block all others.
+                    warn!(
+                        dir   = %path.display(),
+                        error = %e,
+                        "skipping template directory due to load error"
+                    );
+                }
+            }
+        }
 
-            match self.load_template_from_dir(&path) {
+        // Bases are resolved among everything found, so directory order
+        // does not matter.
+        let index: HashMap<&TemplateId, &Declared> =
+            declared.iter().map(|d| (&d.template.id, d)).collect();
+        let mut templates = Vec::new();
+        for template in &declared {
+            match flatten(template, &index) {
                 Ok(template) => {
                     debug!(
                         id      = %template.id.name(),
@@ -265,9 +715,8 @@ impl FilesystemTemplateLoader {
                     templates.push(template);
                 }
                 Err(e) => {
-                    // One bad template must not block all others.
                     warn!(
-                        dir   = %path.display(),
+                        dir   = %template.dir.display(),
                         error = %e,
                         "skipping template directory due to load error"
                     );
@@ -285,8 +734,22 @@ impl FilesystemTemplateLoader {
     ///
     /// Returns an error if `template.toml` is missing, unparseable, or if any
     /// file referenced in the manifest cannot be read.
-    #[instrument(skip(self), fields(dir = %dir.display()))]
+    ///
+    /// A template that `extends` another can only be loaded through
+    /// [`load_all`](Self::load_all), which finds its base.
+    #[cfg(test)]
     fn load_template_from_dir(&self, dir: &Path) -> Result<Template, DomainError> {
+        let declared = self.declare_template(dir)?;
+        flatten(
+            &declared,
+            &HashMap::from([(&declared.template.id, &declared)]),
+        )
+    }
+
+    /// Read one template directory as written, without resolving `extends`
+    /// or validating.
+    #[instrument(skip(self), fields(dir = %dir.display()))]
+    fn declare_template(&self, dir: &Path) -> Result<Declared, DomainError> {
         let manifest_path = dir.join("template.toml");
         if !manifest_path.exists() {
             return Err(DomainError::InvalidTemplate(format!(
@@ -311,7 +774,11 @@ impl FilesystemTemplateLoader {
 
         let id = TemplateId::new(&manifest.template.id, &manifest.template.version);
         let matcher = self.parse_matcher(&manifest.matcher)?;
-        let tree = self.build_tree_from_dir(dir, &manifest)?;
+        let engine = match manifest.template.engine.as_deref() {
+            Some(engine) => engine.parse::<RenderEngine>()?,
+            None => RenderEngine::default(),
+        };
+        let tree = self.build_tree_from_dir(dir, &manifest, engine)?;
 
         let metadata = TemplateMetadata::new(manifest.metadata.name)
             .description(manifest.metadata.description.unwrap_or_default())
@@ -319,12 +786,41 @@ impl FilesystemTemplateLoader {
             .author(manifest.metadata.author.unwrap_or_else(|| "Scarff".into()))
             .tags(manifest.metadata.tags.unwrap_or_default());
 
-        TemplateBuilder::default()
+        let mut builder = TemplateBuilder::default()
             .id(id)
             .matcher(matcher)
             .metadata(metadata)
             .tree(tree)
-            .build()
+            .strict(manifest.template.strict.unwrap_or(true))
+            .engine(engine);
+        for entry in manifest.variables.iter().flatten() {
+            builder = builder.variable(entry.to_spec()?);
+        }
+        for entry in manifest.format.iter().flatten() {
+            builder = builder.format_rule(entry.to_rule()?);
+        }
+        for entry in manifest.hooks.iter().flat_map(|h| h.post.iter().flatten()) {
+            builder = builder.post_hook(entry.to_hook()?);
+        }
+        if let Some(base) = &manifest.template.extends {
+            builder = builder.extends(TemplateId::parse(base)?);
+        }
+        if let Some(overlay) = &manifest.overlay {
+            builder = builder.overlay(overlay.to_overlay(&manifest.template.id)?);
+        }
+        let remove = manifest
+            .template
+            .remove
+            .iter()
+            .flatten()
+            .map(|path| RelativePath::parse(path))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Declared {
+            dir: dir.to_path_buf(),
+            template: builder.build()?,
+            remove,
+        })
     }
 
     /// Walk `dir` and build a [`TemplateTree`] according to the manifest.
@@ -340,6 +836,7 @@ impl FilesystemTemplateLoader {
         &self,
         dir: &Path,
         manifest: &TemplateManifest,
+        engine: RenderEngine,
     ) -> Result<TemplateTree, DomainError> {
         let mut tree = TemplateTree::new();
         // Track paths we have already committed so we never push duplicates.
@@ -350,9 +847,11 @@ impl FilesystemTemplateLoader {
             for entry in dirs {
                 let path = normalize_path(&entry.path);
                 if added_paths.insert(path.clone()) {
-                    tree.push(TemplateNode::Directory(DirectorySpec::new(
-                        RelativePath::new(path),
-                    )));
+                    let mut spec = DirectorySpec::new(RelativePath::new(path.as_str()));
+                    if let Some(when) = parse_when(&path, entry.when.as_deref())? {
+                        spec = spec.when(when);
+                    }
+                    tree.push(TemplateNode::Directory(spec));
                 }
             }
         }
@@ -401,21 +900,41 @@ impl FilesystemTemplateLoader {
                 continue; // Skip symlinks and other special types.
             }
 
-            let content = fs::read_to_string(abs_path).map_err(|e| {
+            let bytes = fs::read(abs_path).map_err(|e| {
                 DomainError::InvalidTemplate(format!("failed to read file '{path_str}': {e}"))
             })?;
 
-            let template_content = self.resolve_file_content(
-                &path_str,
-                content,
-                manifest_files.get(&path_str).copied(),
-            )?;
+            let manifest_entry = manifest_files.get(&path_str).copied();
+            let file_type = manifest_entry.and_then(|e| e.file_type.as_ref());
+            let template_content = match String::from_utf8(bytes) {
+                // Not UTF-8: copied byte for byte whatever the manifest says.
+                Err(e) if file_type != Some(&FileType::External) => {
+                    TemplateContent::Binary(BinarySource::Owned(e.into_bytes()))
+                }
+                Err(_) => {
+                    self.resolve_file_content(&path_str, String::new(), manifest_entry, engine)?
+                }
+                Ok(content) => {
+                    let content = match file_type {
+                        Some(FileType::Literal) => content,
+                        _ if engine == RenderEngine::Simple => {
+                            self.expand_partials(&path_str, &content)?
+                        }
+                        _ => content,
+                    };
+                    self.resolve_file_content(&path_str, content, manifest_entry, engine)?
+                }
+            };
 
             if added_paths.insert(path_str.clone()) {
-                tree.push(TemplateNode::File(FileSpec::new(
-                    RelativePath::new(path_str),
-                    template_content,
-                )));
+                let mut spec =
+                    FileSpec::new(RelativePath::new(path_str.as_str()), template_content);
+                if let Some(when) =
+                    parse_when(&path_str, manifest_entry.and_then(|e| e.when.as_deref()))?
+                {
+                    spec = spec.when(when);
+                }
+                tree.push(TemplateNode::File(spec));
             }
         }
 
@@ -426,7 +945,7 @@ impl FilesystemTemplateLoader {
         if let Some(file_entries) = &manifest.files {
             for entry in file_entries
                 .iter()
-                .filter(|e| e.file_type == FileType::External)
+                .filter(|e| e.file_type == Some(FileType::External))
             {
                 let path_str = normalize_path(&entry.path);
                 if added_paths.contains(&path_str) {
@@ -440,10 +959,14 @@ impl FilesystemTemplateLoader {
                 })?;
                 // Safe: intern() guarantees each unique string is leaked once.
                 let static_id = intern(ext_id);
-                tree.push(TemplateNode::File(FileSpec::new(
+                let mut spec = FileSpec::new(
                     RelativePath::new(path_str.clone()),
                     TemplateContent::External(ContentTemplateId(static_id)),
-                )));
+                );
+                if let Some(when) = parse_when(&path_str, entry.when.as_deref())? {
+                    spec = spec.when(when);
+                }
+                tree.push(TemplateNode::File(spec));
                 added_paths.insert(path_str);
             }
         }
@@ -451,20 +974,42 @@ impl FilesystemTemplateLoader {
         Ok(tree)
     }
 
+    /// Inline the `{{> name}}` partials of `content`, read from
+    /// `<templates root>/_partials/`.
+    fn expand_partials(&self, path_str: &str, content: &str) -> Result<String, DomainError> {
+        let partials = self.templates_dir.join(PARTIALS_DIR);
+        expand_partials(content, &mut |name| {
+            fs::read_to_string(partials.join(name)).ok()
+        })
+        .map_err(|e| {
+            let reason = match e {
+                DomainError::InvalidTemplate(reason) => reason,
+                other => other.to_string(),
+            };
+            DomainError::InvalidTemplate(format!("'{path_str}': {reason}"))
+        })
+    }
+
     /// Determine the [`TemplateContent`] for one file.
     ///
     /// If the file appears in the manifest `[[files]]` section its explicit
-    /// `type` field wins.  Otherwise content is auto-detected: files containing
-    /// `{{` are [`TemplateContent::Parameterized`]; everything else is
-    /// [`TemplateContent::Literal`].
+    /// `type` field wins.  Otherwise content is auto-detected with the
+    /// template's engine: files containing its syntax are
+    /// [`TemplateContent::Parameterized`]; everything else is
+    /// [`TemplateContent::Literal`]. For the simple engine that is
+    /// placeholders, blocks, `\{{` escapes or `{{{{raw}}}}` blocks (see
+    /// [`TemplateSource::contains_placeholder`]); for jinja, any `{{`, `{%`
+    /// or `{#`. Jinja helper files (in `_macros/` or `_includes/`) are always
+    /// parameterized, so they can be included.
     fn resolve_file_content(
         &self,
         path_str: &str,
         content: String,
         manifest_entry: Option<&FileEntry>,
+        engine: RenderEngine,
     ) -> Result<TemplateContent, DomainError> {
-        match manifest_entry {
-            Some(entry) => match entry.file_type {
+        match manifest_entry.and_then(|entry| entry.file_type.as_ref().map(|t| (entry, t))) {
+            Some((entry, file_type)) => match file_type {
                 FileType::Literal => Ok(TemplateContent::Literal(TemplateSource::from(content))),
                 FileType::Parameterized => Ok(TemplateContent::Parameterized(
                     TemplateSource::from(content),
@@ -479,14 +1024,22 @@ impl FilesystemTemplateLoader {
                     Ok(TemplateContent::External(ContentTemplateId(intern(ext_id))))
                 }
             },
-            // Auto-detect: presence of {{ … }} marks the file as parameterized.
+            // Auto-detect: renderer syntax marks the file as parameterized.
             None => {
-                if content.contains("{{") {
-                    Ok(TemplateContent::Parameterized(TemplateSource::from(
-                        content,
-                    )))
+                let source = TemplateSource::from(content);
+                let parameterized = match engine {
+                    RenderEngine::Simple => source.contains_placeholder(),
+                    RenderEngine::Jinja => {
+                        is_jinja_helper(path_str)
+                            || ["{{", "{%", "{#"]
+                                .iter()
+                                .any(|open| source.as_str().contains(open))
+                    }
+                };
+                if parameterized {
+                    Ok(TemplateContent::Parameterized(source))
                 } else {
-                    Ok(TemplateContent::Literal(TemplateSource::from(content)))
+                    Ok(TemplateContent::Literal(source))
                 }
             }
         }
@@ -519,6 +1072,22 @@ impl FilesystemTemplateLoader {
 // These are `fn` rather than methods because they don't need `&self` and are
 // easier to unit-test in isolation.
 
+/// Parse the `when` of the `[[files]]` / `[[directories]]` entry for `path`.
+fn parse_when(path: &str, when: Option<&str>) -> Result<Option<Condition>, DomainError> {
+    when.map(|source| parse_condition(&format!("'{path}'"), source))
+        .transpose()
+}
+
+/// Parse a `when` condition, naming `owner` in the error.
+fn parse_condition(owner: &str, source: &str) -> Result<Condition, DomainError> {
+    Condition::parse(source).map_err(|e| match e {
+        DomainError::InvalidTemplate(reason) => {
+            DomainError::InvalidTemplate(format!("{owner}: {reason}"))
+        }
+        other => other,
+    })
+}
+
 /// Parse a language string from a `template.toml` `[matcher]` section.
 ///
 /// Valid values (case-insensitive): `rust`, `python`, `typescript`, `go`.
@@ -599,6 +1168,59 @@ pub fn parse_architecture(s: &str) -> Result<Architecture, DomainError> {
     }
 }
 
+/// A template as its directory declares it, before `extends` is resolved.
+struct Declared {
+    dir: PathBuf,
+    template: Template,
+    /// `remove` paths, applied to the base.
+    remove: Vec<RelativePath>,
+}
+
+/// Merge `declared` onto its chain of bases from `index`, then validate the
+/// flattened template.
+fn flatten(
+    declared: &Declared,
+    index: &HashMap<&TemplateId, &Declared>,
+) -> Result<Template, DomainError> {
+    let template = resolve_extends(declared, index, &mut Vec::new())?;
+    template.validate()?;
+    Ok(template)
+}
+
+fn resolve_extends(
+    declared: &Declared,
+    index: &HashMap<&TemplateId, &Declared>,
+    chain: &mut Vec<TemplateId>,
+) -> Result<Template, DomainError> {
+    let template = declared.template.clone();
+    let Some(base_id) = template.extends.clone() else {
+        return Ok(template);
+    };
+
+    chain.push(template.id.clone());
+    if chain.contains(&base_id) {
+        let cycle: Vec<String> = chain
+            .iter()
+            .chain([&base_id])
+            .map(ToString::to_string)
+            .collect();
+        return Err(DomainError::InvalidTemplate(format!(
+            "templates extend each other: {}",
+            cycle.join(" -> ")
+        )));
+    }
+    let base = index.get(&base_id).ok_or_else(|| {
+        DomainError::InvalidTemplate(format!(
+            "'{}' extends '{base_id}', which was not found",
+            template.id
+        ))
+    })?;
+    let base = resolve_extends(base, index, chain)?;
+    chain.pop();
+
+    template.extend(&base, &declared.remove)
+}
+
 /// Normalise a filesystem path to forward slashes so Windows and Unix paths
 /// compare identically throughout the loader.
 fn normalize_path(path: &str) -> String {
@@ -610,6 +1232,7 @@ fn normalize_path(path: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use scarff_core::domain::RenderContext;
     use std::{fs::File, io::Write};
     use tempfile::TempDir;
 
@@ -758,6 +1381,40 @@ name = "Test Template"
 
     // ── file auto-detection ───────────────────────────────────────────────
 
+    #[test]
+    fn jinja_files_are_detected_with_jinja_syntax() {
+        let manifest =
+            MINIMAL_MANIFEST.replace("version = \"1.0\"", "version = \"1.0\"\nengine = \"jinja\"");
+        let dir = make_template_dir(
+            &manifest,
+            &[
+                ("ci.yml", "{% if ci %}jobs: {}{% endif %}\n"),
+                ("name.txt", "{{ PROJECT_NAME | kebab }}\n"),
+                ("notes.txt", "{# only a comment #}\n"),
+                ("plain.txt", "no syntax here\n"),
+                ("_includes/header.txt", "no syntax, but included\n"),
+            ],
+        );
+
+        let template = FilesystemTemplateLoader::new(dir.path())
+            .load_template_from_dir(dir.path())
+            .unwrap();
+
+        for path in ["ci.yml", "name.txt", "notes.txt", "_includes/header.txt"] {
+            assert!(
+                matches!(
+                    find_file(&template, path).content,
+                    TemplateContent::Parameterized(_)
+                ),
+                "{path}"
+            );
+        }
+        assert!(matches!(
+            find_file(&template, "plain.txt").content,
+            TemplateContent::Literal(_)
+        ));
+    }
+
     #[test]
     fn auto_detects_parameterized_file() {
         let root = TempDir::new().unwrap();
@@ -797,6 +1454,151 @@ name = "Test Template"
         );
     }
 
+    #[test]
+    fn non_utf8_files_load_as_binary() {
+        let root = TempDir::new().unwrap();
+        let slot = root.path().join("t");
+        let temp_tmpl = make_template_dir(
+            &format!(
+                "{MINIMAL_MANIFEST}\n[[files]]\npath = \"logo.png\"\ntype = \"parameterized\"\n"
+            ),
+            &[],
+        );
+        let bytes = b"\x89PNG\r\n\x1a\n{{x}}\xff\xfe".to_vec();
+        fs::write(temp_tmpl.path().join("logo.png"), &bytes).unwrap();
+        fs_copy_dir(temp_tmpl.path(), &slot);
+
+        let templates = FilesystemTemplateLoader::new(root.path())
+            .load_all()
+            .unwrap();
+
+        let TemplateContent::Binary(source) = &find_file(&templates[0], "logo.png").content else {
+            panic!("expected Binary");
+        };
+        assert_eq!(source.as_bytes(), bytes);
+    }
+
+    #[test]
+    fn auto_detection_understands_foreign_and_escaped_braces() {
+        let root = TempDir::new().unwrap();
+        let slot = root.path().join("t");
+        let temp_tmpl = make_template_dir(
+            MINIMAL_MANIFEST,
+            &[
+                ("chart.yaml", "image: {{ .Values.image }}"),
+                ("ci.yml", "os: $\\{{ matrix.os }}"),
+            ],
+        );
+        fs_copy_dir(temp_tmpl.path(), &slot);
+
+        let templates = FilesystemTemplateLoader::new(root.path())
+            .load_all()
+            .unwrap();
+
+        assert!(matches!(
+            find_file(&templates[0], "chart.yaml").content,
+            TemplateContent::Literal(_)
+        ));
+        assert!(matches!(
+            find_file(&templates[0], "ci.yml").content,
+            TemplateContent::Parameterized(_)
+        ));
+    }
+
+    #[test]
+    fn variables_section_is_parsed_and_validated() {
+        let loader = FilesystemTemplateLoader::new(".");
+        let manifest = format!(
+            "{MINIMAL_MANIFEST}\n\
+             [[variables]]\nname = \"db\"\ntype = \"choice\"\nchoices = [\"postgres\", \"sqlite\"]\ndefault = \"sqlite\"\n\
+             [[variables]]\nname = \"port\"\ntype = \"int\"\ndefault = 3000\nmax = 65535\n\
+             [[variables]]\nname = \"tags\"\ntype = \"list\"\ndefault = [\"a\", \"b\"]\n\
+             [[variables]]\nname = \"pool\"\nwhen = \"db != 'sqlite'\"\n"
+        );
+        let dir = make_template_dir(&manifest, &[("a.txt", "a")]);
+
+        let template = loader.load_template_from_dir(dir.path()).unwrap();
+
+        let names: Vec<&str> = template.variables.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, ["db", "port", "tags", "pool"]);
+        assert_eq!(
+            template.variables[3].when.as_ref().map(|c| c.as_str()),
+            Some("db != 'sqlite'")
+        );
+        assert_eq!(
+            template.variables[1].kind,
+            VariableKind::Int {
+                min: None,
+                max: Some(65535)
+            }
+        );
+        assert_eq!(
+            template.variables[2].default,
+            Some(RenderValue::from(vec!["a", "b"]))
+        );
+
+        let bad = make_template_dir(
+            &format!(
+                "{MINIMAL_MANIFEST}\n[[variables]]\nname = \"db\"\ntype = \"choice\"\nchoices = [\"pg\"]\ndefault = \"mysql\"\n"
+            ),
+            &[("a.txt", "a")],
+        );
+        let err = loader.load_template_from_dir(bad.path()).unwrap_err();
+        assert!(err.to_string().contains("invalid default"), "{err}");
+
+        let forward = make_template_dir(
+            &format!(
+                "{MINIMAL_MANIFEST}\n[[variables]]\nname = \"a\"\nwhen = \"b\"\n[[variables]]\nname = \"b\"\n"
+            ),
+            &[("a.txt", "a")],
+        );
+        let err = loader.load_template_from_dir(forward.path()).unwrap_err();
+        assert!(err.to_string().contains("not declared before it"), "{err}");
+    }
+
+    #[test]
+    fn engine_key_selects_template_language() {
+        let root = TempDir::new().unwrap();
+        let jinja = make_template_dir(
+            &MINIMAL_MANIFEST.replace("version = \"1.0\"", "version = \"1.0\"\nengine = \"jinja\""),
+            &[("a.txt", "a")],
+        );
+        fs_copy_dir(jinja.path(), &root.path().join("jinja"));
+
+        let templates = FilesystemTemplateLoader::new(root.path())
+            .load_all()
+            .unwrap();
+        assert_eq!(templates[0].engine, RenderEngine::Jinja);
+
+        let loader = FilesystemTemplateLoader::new(root.path());
+        let bad = make_template_dir(
+            &MINIMAL_MANIFEST.replace("version = \"1.0\"", "version = \"1.0\"\nengine = \"erb\""),
+            &[("a.txt", "a")],
+        );
+        assert!(loader.load_template_from_dir(bad.path()).is_err());
+    }
+
+    #[test]
+    fn templates_are_strict_unless_manifest_opts_out() {
+        let root = TempDir::new().unwrap();
+        let strict = make_template_dir(MINIMAL_MANIFEST, &[("a.txt", "a")]);
+        fs_copy_dir(strict.path(), &root.path().join("strict"));
+        let lenient = make_template_dir(
+            &MINIMAL_MANIFEST.replace("version = \"1.0\"", "version = \"1.0\"\nstrict = false"),
+            &[("a.txt", "a")],
+        );
+        fs_copy_dir(lenient.path(), &root.path().join("lenient"));
+
+        let mut templates = FilesystemTemplateLoader::new(root.path())
+            .load_all()
+            .unwrap();
+        templates.sort_by_key(|t| t.strict);
+        assert_eq!(
+            templates.iter().map(|t| t.strict).collect::<Vec<_>>(),
+            [false, true]
+        );
+    }
+
     #[test]
     fn manifest_override_forces_literal_even_with_braces() {
         let manifest = r#"
@@ -833,8 +1635,419 @@ type = "literal"
         );
     }
 
+    #[test]
+    fn when_conditions_select_files_and_directories() {
+        let manifest = r#"
+[template]
+id = "t"
+version = "1.0"
+
+[matcher]
+language = "rust"
+
+[metadata]
+name = "T"
+
+[[variables]]
+name    = "db"
+default = "none"
+
+[[variables]]
+name    = "with_tests"
+default = "false"
+
+[[files]]
+path = "docker-compose.yml"
+when = "db != 'none'"
+
+[[directories]]
+path = "tests"
+when = "with_tests"
+"#;
+        let root = TempDir::new().unwrap();
+        let temp_tmpl = make_template_dir(
+            manifest,
+            &[
+                ("docker-compose.yml", "image: {{db}}"),
+                ("tests/it.rs", "fn it() {}"),
+                ("README.md", "readme"),
+            ],
+        );
+        fs_copy_dir(temp_tmpl.path(), &root.path().join("t"));
+
+        let templates = FilesystemTemplateLoader::new(root.path())
+            .load_all()
+            .unwrap();
+        let tree = &templates[0].tree;
+        assert!(matches!(
+            find_file(&templates[0], "docker-compose.yml").content,
+            TemplateContent::Parameterized(_)
+        ));
+
+        let emitted = |ctx: &RenderContext| -> Vec<String> {
+            let mut paths: Vec<String> = tree
+                .nodes_for(ctx)
+                .into_iter()
+                .map(|node| match node {
+                    TemplateNode::File(f) => f.path.to_string(),
+                    TemplateNode::Directory(d) => d.path.to_string(),
+                })
+                .collect();
+            paths.sort();
+            paths
+        };
+
+        let none = RenderContext::new("app")
+            .with_variable("db", "none")
+            .with_variable("with_tests", "false");
+        assert_eq!(emitted(&none), ["README.md"]);
+
+        let all = RenderContext::new("app")
+            .with_variable("db", "postgres")
+            .with_variable("with_tests", "true");
+        assert_eq!(
+            emitted(&all),
+            ["README.md", "docker-compose.yml", "tests", "tests/it.rs"]
+        );
+    }
+
+    #[test]
+    fn invalid_when_condition_names_the_path() {
+        let manifest =
+            format!("{MINIMAL_MANIFEST}\n[[files]]\npath = \"a.txt\"\nwhen = \"db ==\"\n");
+        let dir = make_template_dir(&manifest, &[("a.txt", "a")]);
+
+        let err = FilesystemTemplateLoader::new(dir.path())
+            .load_template_from_dir(dir.path())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("'a.txt': invalid condition"), "{err}");
+    }
+
+    #[test]
+    fn format_rules_are_parsed_in_order() {
+        let manifest = format!(
+            "{MINIMAL_MANIFEST}
+[[format]]
+glob = \"**\"
+line_endings = \"crlf\"
+final_newline = true
+indent = \"spaces\"
+
+[[format]]
+glob = \"Makefile\"
+indent = \"tabs\"
+indent_size = 8
+"
+        );
+        let loader = FilesystemTemplateLoader::new(".");
+        let dir = make_template_dir(&manifest, &[("Makefile", "all:\n")]);
+
+        let template = loader.load_template_from_dir(dir.path()).unwrap();
+
+        assert_eq!(template.format.len(), 2);
+        let make = template.format_options(Path::new("Makefile"));
+        assert_eq!(make.indent, Some(Indent::Tabs(8)));
+        assert_eq!(make.line_ending, Some(LineEnding::CrLf));
+        assert_eq!(make.final_newline, Some(true));
+        assert_eq!(
+            template.format_options(Path::new("src/main.rs")).indent,
+            Some(Indent::Spaces(4))
+        );
+
+        for (entry, expected) in [
+            ("glob = \"*.{a\"", "unclosed '{'"),
+            (
+                "glob = \"**\"\nline_endings = \"cr\"",
+                "unknown line ending 'cr'",
+            ),
+            ("glob = \"**\"\nindent = \"tab\"", "unknown indent 'tab'"),
+            ("glob = \"**\"\nindent_size = 0", "at least 1"),
+        ] {
+            let bad = make_template_dir(
+                &format!("{MINIMAL_MANIFEST}\n[[format]]\n{entry}\n"),
+                &[("a", "")],
+            );
+            let err = loader
+                .load_template_from_dir(bad.path())
+                .unwrap_err()
+                .to_string();
+            assert!(err.contains(expected), "{entry}: {err}");
+        }
+    }
+
+    #[test]
+    fn overlay_section_marks_template_as_overlay() {
+        let loader = FilesystemTemplateLoader::new(".");
+        let base = make_template_dir(MINIMAL_MANIFEST, &[("a", "")]);
+        assert!(
+            !loader
+                .load_template_from_dir(base.path())
+                .unwrap()
+                .is_overlay()
+        );
+
+        let manifest = format!(
+            "{MINIMAL_MANIFEST}
+[overlay]
+
+[[overlay.merge]]
+glob = \".gitignore\"
+strategy = \"append\"
+
+[[overlay.merge]]
+glob = \"Cargo.toml\"
+strategy = \"merge\"
+"
+        );
+        let dir = make_template_dir(&manifest, &[("Dockerfile", "FROM scratch\n")]);
+        let template = loader.load_template_from_dir(dir.path()).unwrap();
+        let overlay = template.overlay.expect("overlay");
+        assert_eq!(overlay.name, "tpl");
+        assert_eq!(
+            overlay.strategy_for(Path::new(".gitignore")),
+            Some(MergeStrategy::Append)
+        );
+        assert_eq!(
+            overlay.strategy_for(Path::new("Cargo.toml")),
+            Some(MergeStrategy::Merge(None))
+        );
+
+        let bad = make_template_dir(
+            &format!(
+                "{MINIMAL_MANIFEST}\n[overlay]\nname = \"ci\"\n[[overlay.merge]]\nglob = \"*\"\nstrategy = \"union\"\n"
+            ),
+            &[("a", "")],
+        );
+        let err = loader
+            .load_template_from_dir(bad.path())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("unknown merge strategy 'union'"), "{err}");
+    }
+
+    #[test]
+    fn post_hooks_are_parsed_in_order() {
+        let manifest = format!(
+            "{MINIMAL_MANIFEST}
+[[hooks.post]]
+command = \"git\"
+args = [\"init\"]
+
+[[hooks.post]]
+command = \"npm\"
+args = [\"install\"]
+working_dir = \"web\"
+when = \"frontend && LANGUAGE == 'rust'\"
+
+[[variables]]
+name = \"frontend\"
+default = \"false\"
+"
+        );
+        let dir = make_template_dir(&manifest, &[("a", "")]);
+        let template = FilesystemTemplateLoader::new(".")
+            .load_template_from_dir(dir.path())
+            .unwrap();
+
+        let hooks: Vec<String> = template.post_hooks.iter().map(Hook::to_string).collect();
+        assert_eq!(hooks, ["git init", "npm install"]);
+        assert_eq!(
+            template.post_hooks[1].working_dir,
+            Some(RelativePath::parse("web").unwrap())
+        );
+        assert!(template.post_hooks[1].when.is_some());
+
+        let bad = make_template_dir(
+            &format!("{MINIMAL_MANIFEST}\n[[hooks.post]]\ncommand = \"x\"\nwhen = \"a ==\"\n"),
+            &[("a", "")],
+        );
+        let err = FilesystemTemplateLoader::new(".")
+            .load_template_from_dir(bad.path())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("hook 'x'"), "{err}");
+
+        let typo = make_template_dir(
+            &format!("{MINIMAL_MANIFEST}\n[[hooks.post]]\ncommand = \"x\"\nwhen = \"frontnd\"\n"),
+            &[("a", "")],
+        );
+        let err = FilesystemTemplateLoader::new(".")
+            .load_template_from_dir(typo.path())
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains(
+                "references 'frontnd', which is neither a declared variable nor a built-in"
+            ),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn partials_are_inlined_from_the_partials_dir() {
+        let root = TempDir::new().unwrap();
+        let partials = root.path().join(PARTIALS_DIR);
+        fs::create_dir_all(partials.join("ci")).unwrap();
+        fs::write(partials.join("rustfmt.toml"), "edition = \"2024\"\n").unwrap();
+        fs::write(partials.join("ci/rust.yml"), "- run: cargo test\n").unwrap();
+        let t = make_template_dir(
+            MINIMAL_MANIFEST,
+            &[
+                ("rustfmt.toml", "{{> rustfmt.toml}}"),
+                (
+                    "ci.yml",
+                    "# {{PROJECT_NAME}}\nsteps:\n  {{> ci/rust.yml}}\n",
+                ),
+            ],
+        );
+        fs_copy_dir(t.path(), &root.path().join("t"));
+
+        let templates = FilesystemTemplateLoader::new(root.path())
+            .load_all()
+            .unwrap();
+
+        assert_eq!(templates.len(), 1, "_partials/ is not a template");
+        let TemplateContent::Literal(rustfmt) = &find_file(&templates[0], "rustfmt.toml").content
+        else {
+            panic!("plain partial content should be literal");
+        };
+        assert_eq!(rustfmt.as_str(), "edition = \"2024\"\n");
+        let TemplateContent::Parameterized(ci) = &find_file(&templates[0], "ci.yml").content else {
+            panic!("ci.yml should be parameterized");
+        };
+        assert_eq!(
+            ci.as_str(),
+            "# {{PROJECT_NAME}}\nsteps:\n  - run: cargo test\n"
+        );
+    }
+
+    #[test]
+    fn unknown_partial_fails_the_load() {
+        let dir = make_template_dir(MINIMAL_MANIFEST, &[("a.txt", "{{> nope.txt}}")]);
+
+        let err = FilesystemTemplateLoader::new(dir.path())
+            .load_template_from_dir(dir.path())
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("'a.txt': unknown partial 'nope.txt' at 1:1"),
+            "{err}"
+        );
+    }
+
     // ── external files ────────────────────────────────────────────────────
 
+    #[test]
+    fn extends_merges_the_base_into_the_child() {
+        let root = TempDir::new().unwrap();
+        let write = |dir: &str, manifest: &str, files: &[(&str, &str)]| {
+            fs_copy_dir(
+                make_template_dir(manifest, files).path(),
+                &root.path().join(dir),
+            );
+        };
+        write(
+            "base",
+            &format!("{MINIMAL_MANIFEST}\n[[variables]]\nname = \"db\"\ndefault = \"sqlite\"\n")
+                .replace("id      = \"tpl\"", "id      = \"base\""),
+            &[
+                ("Cargo.toml", "[package]\n"),
+                ("src/main.rs", "fn main() {}\n"),
+                ("src/legacy/mod.rs", "// old\n"),
+            ],
+        );
+        write(
+            "child",
+            &format!("{MINIMAL_MANIFEST}\n[[variables]]\nname = \"db\"\ndefault = \"postgres\"\n")
+                .replace(
+                    "id      = \"tpl\"",
+                    "id      = \"child\"\nextends = \"base@1.0\"\nremove = [\"src/legacy\"]",
+                )
+                .replace("[matcher]", "[matcher]\nkind = \"cli\""),
+            &[("src/main.rs", "fn main() { run() }\n"), ("src/app.rs", "")],
+        );
+
+        let templates = FilesystemTemplateLoader::new(root.path())
+            .load_all()
+            .unwrap();
+        let child = templates.iter().find(|t| t.id.name() == "child").unwrap();
+
+        let paths: Vec<&str> = child.tree.nodes.iter().map(|n| n.path().as_str()).collect();
+        assert!(paths.contains(&"Cargo.toml"), "{paths:?}");
+        assert!(paths.contains(&"src/app.rs"), "{paths:?}");
+        assert!(
+            !paths.iter().any(|p| p.starts_with("src/legacy")),
+            "{paths:?}"
+        );
+        let TemplateContent::Literal(main) = &find_file(child, "src/main.rs").content else {
+            panic!("expected Literal");
+        };
+        assert_eq!(main.as_str(), "fn main() { run() }\n");
+        assert_eq!(child.variables.len(), 1);
+        assert_eq!(child.extends, Some(TemplateId::new("base", "1.0")));
+        assert_eq!(templates.len(), 2);
+    }
+
+    #[test]
+    fn broken_inheritance_skips_only_the_affected_templates() {
+        let root = TempDir::new().unwrap();
+        for (id, extends) in [
+            ("a", Some("b@1.0")),
+            ("b", Some("a@1.0")),
+            ("orphan", Some("missing@1.0")),
+            ("ok", None),
+        ] {
+            let mut manifest =
+                MINIMAL_MANIFEST.replace("id      = \"tpl\"", &format!("id = \"{id}\""));
+            if let Some(base) = extends {
+                manifest = manifest.replace(
+                    "version = \"1.0\"",
+                    &format!("version = \"1.0\"\nextends = \"{base}\""),
+                );
+            }
+            fs_copy_dir(
+                make_template_dir(&manifest, &[("a.txt", "")]).path(),
+                &root.path().join(id),
+            );
+        }
+
+        let templates = FilesystemTemplateLoader::new(root.path())
+            .load_all()
+            .unwrap();
+
+        let ids: Vec<&str> = templates.iter().map(|t| t.id.name()).collect();
+        assert_eq!(ids, ["ok"]);
+
+        let declared = |id: &str| {
+            FilesystemTemplateLoader::new(root.path())
+                .declare_template(&root.path().join(id))
+                .unwrap()
+        };
+        let (a, b) = (declared("a"), declared("b"));
+        let index = HashMap::from([(&a.template.id, &a), (&b.template.id, &b)]);
+        let err = flatten(&a, &index).unwrap_err().to_string();
+        assert!(err.contains("a@1.0 -> b@1.0 -> a@1.0"), "{err}");
+        let err = flatten(&declared("orphan"), &index)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("'missing@1.0', which was not found"), "{err}");
+    }
+
+    #[test]
+    fn underscore_directories_are_not_loaded_as_templates() {
+        let root = TempDir::new().unwrap();
+        let t = make_template_dir(MINIMAL_MANIFEST, &[("a.txt", "a")]);
+        fs_copy_dir(t.path(), &root.path().join("real"));
+        fs::create_dir_all(root.path().join("_shared")).unwrap();
+        fs::write(root.path().join("_shared/README.md"), "shared").unwrap();
+
+        let templates = FilesystemTemplateLoader::new(root.path())
+            .load_all()
+            .unwrap();
+        assert_eq!(templates.len(), 1);
+    }
+
     #[test]
     fn external_file_not_on_disk_is_added_from_manifest() {
         let manifest = r#"
@@ -34,6 +34,15 @@ impl InMemoryStore {
         Ok(store)
     }
 
+    /// Create a store holding `templates`.
+    pub fn with_templates(templates: impl IntoIterator<Item = Template>) -> ScarffResult<Self> {
+        let store = Self::new();
+        for template in templates {
+            store.insert(template)?;
+        }
+        Ok(store)
+    }
+
     /// Load built-in templates.
     pub fn load_builtin(&self) -> ScarffResult<()> {
         let templates = builtin_templates::all_templates()?;
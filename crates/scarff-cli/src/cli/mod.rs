@@ -3,6 +3,8 @@
 //! This module is the *only* place that knows about argument names, aliases,
 //! help text, and value enums.  No business logic lives here.
 
+use std::path::PathBuf;
+
 use clap::{Args, Parser, Subcommand, ValueEnum};
 
 pub mod global;
@@ -48,11 +50,12 @@ pub enum Commands {
         visible_alias = "n",
         about = "Create a new project",
         after_help = "EXAMPLES:\n\
+            \x20 scarff new my-project                       # interactive wizard\n\
             \x20 scarff new my-project --lang rust   --type cli\n\
             \x20 scarff new my-api     --lang python --type backend --framework fastapi\n\
             \x20 scarff new my-app     --lang typescript --type frontend --framework react"
     )]
-    New(NewArgs),
+    New(Box<NewArgs>),
 
     /// List available templates.
     #[command(
@@ -107,16 +110,18 @@ pub struct NewArgs {
     #[arg(value_name = "NAME", help = "Project name or path")]
     pub name: String,
 
-    // for MVP we make it compulsory
-    /// Programming language.
+    /// Programming language. When omitted on a terminal, a wizard asks for
+    /// the language and any other unset part of the target; otherwise it is
+    /// implied by `--framework` or taken from `defaults.language` in the
+    /// config.
     #[arg(
         short = 'l',
         long = "lang",
         value_name = "LANGUAGE",
         value_enum,
-        help = "Programming language"
+        help = "Programming language (omit to choose interactively)"
     )]
-    pub language: Language,
+    pub language: Option<Language>,
 
     /// Project type.
     #[arg(
@@ -171,6 +176,16 @@ pub struct NewArgs {
     #[arg(long = "dry-run", help = "Show what would be created without creating")]
     pub dry_run: bool,
 
+    /// Don't run the commands the template declares in `[[hooks.post]]`
+    /// (`git init`, `npm install`, …) after writing the project.
+    #[arg(long = "no-hooks", help = "Skip the template's post-generation hooks")]
+    pub no_hooks: bool,
+
+    /// Run hooks from templates outside the built-in set without asking,
+    /// and remember them as trusted until the template changes.
+    #[arg(long, help = "Trust the template's hooks without asking")]
+    pub trust: bool,
+
     /// Use a specific template ID, bypassing automatic matching.
     #[arg(
         long = "template",
@@ -178,6 +193,102 @@ pub struct NewArgs {
         help = "Specific template ID to use"
     )]
     pub template: Option<String>,
+
+    /// Print a stable content hash of the generated project.
+    ///
+    /// Combine with `--seed` and `SOURCE_DATE_EPOCH` so the hash depends only
+    /// on the template, the inputs and the scarff version: pinned runs also
+    /// ignore the machine's git identity. Also works with `--dry-run`.
+    #[arg(
+        long = "print-digest",
+        help = "Print a stable SHA-256 digest of the generated files"
+    )]
+    pub print_digest: bool,
+
+    /// Seed for random template variables (`RANDOM_ID`).
+    #[arg(
+        long = "seed",
+        value_name = "N",
+        help = "Seed random variables for reproducible output"
+    )]
+    pub seed: Option<u64>,
+
+    /// Fixed generation time in seconds since the Unix epoch.
+    #[arg(
+        long = "source-date-epoch",
+        env = "SOURCE_DATE_EPOCH",
+        value_name = "SECONDS",
+        help = "Fixed timestamp for YEAR / GENERATED_AT (reproducible builds)"
+    )]
+    pub source_date_epoch: Option<u64>,
+
+    /// `GIT_USER_NAME` for the templates, instead of git's `user.name`.
+    /// Runs pinned with `--seed` or `SOURCE_DATE_EPOCH` ignore git's config,
+    /// so this is the only way to give them an author.
+    #[arg(
+        long = "git-user-name",
+        env = "GIT_AUTHOR_NAME",
+        value_name = "NAME",
+        help = "Author name for templates (default: git user.name)"
+    )]
+    pub git_user_name: Option<String>,
+
+    /// `GIT_USER_EMAIL` for the templates, instead of git's `user.email`.
+    #[arg(
+        long = "git-user-email",
+        env = "GIT_AUTHOR_EMAIL",
+        value_name = "EMAIL",
+        help = "Author email for templates (default: git user.email)"
+    )]
+    pub git_user_email: Option<String>,
+
+    /// npm scope for TypeScript (`acme` gives `@acme/<name>`) or module
+    /// path prefix for Go (`github.com/acme`), used in `PACKAGE_NAME`.
+    #[arg(
+        long = "scope",
+        value_name = "SCOPE",
+        help = "npm scope or Go module prefix for PACKAGE_NAME"
+    )]
+    pub scope: Option<String>,
+
+    /// Overlays to layer onto the template, e.g. `--with docker,ci`.
+    /// Repeatable; applied in the order given.
+    #[arg(
+        long = "with",
+        value_name = "OVERLAY",
+        value_delimiter = ',',
+        help = "Add overlays such as docker or ci (comma-separated, repeatable)"
+    )]
+    pub overlays: Vec<String>,
+
+    /// Template variable values, e.g. `--var db=postgres`. Repeatable;
+    /// overrides values from `--vars-file`.
+    #[arg(
+        long = "var",
+        value_name = "KEY=VALUE",
+        value_parser = parse_key_value,
+        help = "Set a template variable (repeatable)"
+    )]
+    pub vars: Vec<(String, String)>,
+
+    /// TOML or JSON file of template variable values, such as the
+    /// `.scarff-answers.toml` written by an earlier run.
+    #[arg(
+        long = "vars-file",
+        value_name = "PATH",
+        help = "Read template variables from a TOML/JSON file"
+    )]
+    pub vars_file: Option<PathBuf>,
+}
+
+/// Split a `KEY=VALUE` argument at the first `=`.
+fn parse_key_value(arg: &str) -> Result<(String, String), String> {
+    match arg.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_string(), value.to_string()))
+        }
+        _ => Err(format!("expected KEY=VALUE, got '{arg}'")),
+    }
 }
 
 // ── list ──────────────────────────────────────────────────────────────────────
@@ -428,12 +539,41 @@ mod tests {
             "scarff", "new", "test", "-l", "ts", "-t", "cli", "-a", "layered",
         ]);
         if let Commands::New(args) = cli.command {
-            assert_eq!(args.language, Language::TypeScript);
+            assert_eq!(args.language, Some(Language::TypeScript));
         } else {
             panic!("expected New command");
         }
     }
 
+    #[test]
+    fn var_flags_split_at_first_equals() {
+        let cli = Cli::parse_from([
+            "scarff",
+            "new",
+            "app",
+            "-l",
+            "rust",
+            "--var",
+            "db=postgres",
+            "--var",
+            "dsn=a=b",
+        ]);
+        let Commands::New(args) = cli.command else {
+            panic!("expected New command");
+        };
+        assert_eq!(
+            args.vars,
+            [
+                ("db".to_string(), "postgres".to_string()),
+                ("dsn".to_string(), "a=b".to_string()),
+            ]
+        );
+
+        assert!(
+            Cli::try_parse_from(["scarff", "new", "app", "-l", "rust", "--var", "db"]).is_err()
+        );
+    }
+
     #[test]
     fn quiet_and_verbose_conflict() {
         // clap should reject --quiet --verbose together
@@ -8,16 +8,26 @@
 //! 5. Transparent infer  — `UserChoices` tracks what the user provided
 //! 6. Override wins      — explicit flags fed directly to the builder
 
-use std::path::{Path, PathBuf};
+use std::{
+    collections::HashMap,
+    io::IsTerminal,
+    path::{Path, PathBuf},
+};
 
 use tracing::{debug, info, instrument};
 
-use scarff_adapters::{InMemoryStore, LocalFilesystem, SimpleRenderer};
+use scarff_adapters::{
+    ContentResolvers, EngineRenderer, InMemoryStore, LocalFilesystem, ProcessHookRunner,
+    SystemRunInfo,
+    answers::{self, ANSWERS_FILE, Answers},
+    builtin_templates, merge, post_process,
+};
 use scarff_core::{
-    application::ScaffoldService,
+    application::{Determinism, ScaffoldOptions, ScaffoldOutcome, ScaffoldService, TemplateHooks},
     domain::{
         Architecture as CoreArch, Framework as CoreFramework, Language as CoreLanguage,
-        ProjectKind as CoreKind, Target, capabilities::FRAMEWORK_REGISTRY,
+        Permissions, ProjectKind as CoreKind, RenderValue, Target, VariableSpec,
+        capabilities::FRAMEWORK_REGISTRY,
     },
 };
 
@@ -26,6 +36,8 @@ use crate::{
     config::AppConfig,
     error::{CliError, CliResult},
     output::OutputManager,
+    prompt::{self, Unanswered},
+    trust::{Trust, TrustStore},
 };
 
 // ── UserChoices ───────────────────────────────────────────────────────────────
@@ -46,10 +58,14 @@ struct UserChoices {
 /// Dispatch sequence:
 /// 1. Parse and validate the project name / output path
 /// 2. Convert CLI args to a core `Target` (with full inference)
-/// 3. Confirm with user unless `--yes` or `--quiet`
-/// 4. Early-exit if `--dry-run`
-/// 5. Execute scaffolding via `ScaffoldService`
-/// 6. Print next-steps guidance
+/// 3. Ask for unsupplied template variables, then confirm, unless `--yes`
+///    or `--quiet`
+/// 4. Early-exit if `--dry-run` (rendering only for `--print-digest`)
+/// 5. Render via `ScaffoldService` and check the hooks against the
+///    allowlist, asking to trust those of templates outside the built-in
+///    set; then write the project, recording the answers used
+/// 6. Run the approved post-generation hooks, unless `--no-hooks`
+/// 7. Print next-steps guidance (and the digest for `--print-digest`)
 #[instrument(skip_all, fields(project = %args.name))]
 pub fn execute(
     args: NewArgs,
@@ -69,9 +85,19 @@ pub fn execute(
     let (project_name, output_dir) = resolve_project_path(&args.name)?;
     validate_project_name(&project_name)?;
 
-    // Step 2 — Build and fully validate the domain target.
+    // Step 2 — Check path existence before asking the user anything.
+    // RFC §6.2: errors must occur before filesystem writes — and before
+    // the user is asked to confirm, which is also a form of output.
+    let project_path = output_dir.join(&project_name);
+    if project_path.exists() && !args.force {
+        return Err(CliError::ProjectExists { path: project_path });
+    }
+
+    // Step 3 — Build and fully validate the domain target, running the
+    // wizard when `--lang` is omitted on a terminal.
     // All domain errors surface here, before we show anything.
-    let (target, choices) = build_target(&args, &config)?;
+    let wizard = !global.quiet && !args.yes && can_prompt();
+    let (target, choices) = build_target(&args, &config, wizard)?;
 
     info!(
         language = %target.language(),
@@ -80,22 +106,40 @@ pub fn execute(
         "Target built"
     );
 
-    // Step 3 — Check path existence before asking the user anything.
-    // RFC §6.2: errors must occur before filesystem writes — and before
-    // the user is asked to confirm, which is also a form of output.
-    let project_path = output_dir.join(&project_name);
-    if project_path.exists() && !args.force {
-        return Err(CliError::ProjectExists { path: project_path });
-    }
+    // The overlays may come from `--vars-file` as well as `--with`.
+    let mut options = scaffold_options(&args)?;
 
     // Step 4 — Show the resolved configuration.
     // RFC §8: dry-run output must be identical to a real run, so we always
     // show this panel — the dry-run short-circuit comes later.
     if !global.quiet {
-        show_configuration(&target, &choices, &project_name, &project_path, &output)?;
+        show_configuration(
+            &target,
+            &choices,
+            &options.overlays,
+            &project_name,
+            &project_path,
+            &output,
+        )?;
     }
 
-    // Step 5 — Confirm (skipped by --yes, --default, or --dry-run).
+    // One scan: the templates that run and the trust decision for their
+    // hooks must come from the same directory.
+    let discovered = builtin_templates::discover().map_err(|e| CliError::Core(e.into()))?;
+    let bundled = discovered
+        .as_ref()
+        .is_some_and(builtin_templates::Discovered::is_bundled);
+    let service = build_service(discovered)?;
+
+    // Step 5 — Ask for template variables (skipped by --yes / --quiet, which
+    // take the defaults), then confirm (also skipped by --dry-run).
+    if !global.quiet && !args.yes {
+        let variables = service
+            .variables_for(&target, &options)
+            .map_err(CliError::Core)?;
+        let answers = ask_for_variables(&service, &variables, &target, &project_name, &options)?;
+        options = options.variables(answers);
+    }
     if !global.quiet && !args.yes && !args.dry_run && !confirm()? {
         return Err(CliError::Cancelled);
     }
@@ -115,23 +159,54 @@ pub fn execute(
         if let Some(fw) = target.framework() {
             output.info(&format!("  Framework:    {fw}"))?;
         }
+        if !options.overlays.is_empty() {
+            output.info(&format!("  Overlays:     {}", options.overlays.join(", ")))?;
+        }
+        if args.print_digest {
+            let mut outcome = service
+                .render(target, &project_name, &output_dir, &options)
+                .map_err(CliError::Core)?;
+            record_answers(&mut outcome, &options)?;
+            output.result(&outcome.structure.digest())?;
+        }
         return Ok(());
     }
 
-    // Step 7 — Execute scaffolding.
-    output.header(&format!("Creating project '{project_name}'..."))?;
-
-    let store = Box::new(InMemoryStore::with_builtin().map_err(CliError::Core)?);
-    let renderer = Box::new(SimpleRenderer::new());
-    let filesystem = Box::new(LocalFilesystem::new());
-    let service = ScaffoldService::new(store, renderer, filesystem);
-
-    service
-        .scaffold(target.clone(), &project_name, &output_dir)
+    // Step 7 — Render, and settle which hooks may run, then write.
+    // RFC §6.2: a disallowed hook must fail the run before any file exists,
+    // and asking for trust is part of confirming.
+    let mut outcome = service
+        .render(target.clone(), &project_name, &output_dir, &options)
         .map_err(CliError::Core)?;
+    record_answers(&mut outcome, &options)?;
+    let approved = if args.no_hooks {
+        Vec::new()
+    } else {
+        let ask = !global.quiet && !args.yes && can_prompt();
+        approve_hooks(&outcome, &config, bundled, args.trust, ask, &output)?
+    };
+
+    output.header(&format!("Creating project '{project_name}'..."))?;
+    service.write(&outcome).map_err(CliError::Core)?;
 
     // Step 8 — Success output.
     output.success(&format!("Project '{project_name}' created successfully!"))?;
+    if args.print_digest {
+        output.result(&outcome.structure.digest())?;
+    }
+
+    // Step 9 — Post-generation hooks. A failure is reported, but the project
+    // stays: it was written successfully.
+    if !outcome.hooks.is_empty() {
+        if args.no_hooks {
+            let count: usize = outcome.hooks.iter().map(|group| group.hooks.len()).sum();
+            output.info(&format!(
+                "Skipped {count} post-generation hook(s) (--no-hooks)."
+            ))?;
+        } else {
+            run_hooks(&service, &outcome, &approved, &output)?;
+        }
+    }
 
     if !global.quiet {
         output.print("")?;
@@ -141,6 +216,266 @@ pub fn execute(
     Ok(())
 }
 
+/// The hook groups of `outcome` that may run.
+///
+/// Every command must be in the config's `[hooks] allowed_commands`, checked
+/// for all hooks before any runs. Templates outside the built-in set must
+/// also be trusted with their current content: recorded earlier, given with
+/// `--trust`, or granted when asked after seeing the commands. `--yes` does
+/// not count; without a way to ask, untrusted hooks are skipped.
+///
+/// Runs before the project is written, so a refusal leaves nothing behind.
+fn approve_hooks<'a>(
+    outcome: &'a ScaffoldOutcome,
+    config: &AppConfig,
+    bundled: bool,
+    trust: bool,
+    ask: bool,
+    output: &OutputManager,
+) -> CliResult<Vec<&'a TemplateHooks>> {
+    for group in &outcome.hooks {
+        if let Some(hook) = group
+            .hooks
+            .iter()
+            .find(|hook| !config.hooks.allows(&hook.command))
+        {
+            return Err(CliError::HookNotAllowed {
+                command: hook.command.clone(),
+                template: group.template.to_string(),
+            });
+        }
+    }
+    if bundled {
+        return Ok(outcome.hooks.iter().collect());
+    }
+
+    let mut store = TrustStore::load(TrustStore::default_path())?;
+    let mut approved = Vec::new();
+    for group in &outcome.hooks {
+        let id = group.template.to_string();
+        let state = store.check(&id, &group.digest);
+        if state == Trust::Trusted {
+            approved.push(group);
+            continue;
+        }
+
+        let granted = trust || {
+            if ask {
+                let intro = match state {
+                    Trust::Changed => "has changed since you trusted it and wants to run",
+                    _ => "wants to run",
+                };
+                output.print(&format!("Template '{id}' {intro}:"))?;
+                print_hooks(group, output)?;
+                ask_yes_no("Run these commands? [y/N] ", false)?
+            } else {
+                false
+            }
+        };
+        if granted {
+            store.trust(&id, &group.digest)?;
+            debug!(template = %id, path = %store.path().display(), "Hooks trusted");
+            approved.push(group);
+        } else {
+            output.warning(&format!(
+                "Not running the hooks of '{id}', which {}{}:",
+                if state == Trust::Changed {
+                    "changed since it was trusted"
+                } else {
+                    "is not trusted"
+                },
+                if ask {
+                    ""
+                } else {
+                    "; re-run with --trust to allow them"
+                }
+            ))?;
+            print_hooks(group, output)?;
+        }
+    }
+    Ok(approved)
+}
+
+/// List a group's commands, one per line, with where each would run.
+fn print_hooks(group: &TemplateHooks, output: &OutputManager) -> CliResult<()> {
+    for hook in &group.hooks {
+        match &hook.working_dir {
+            Some(dir) => output.print(&format!("  {hook}    (in {})", dir.as_str()))?,
+            None => output.print(&format!("  {hook}"))?,
+        }
+    }
+    Ok(())
+}
+
+/// Run the approved hooks in order, streaming their output. The first
+/// failure stops the rest and is returned; the project is left in place.
+fn run_hooks(
+    service: &ScaffoldService,
+    outcome: &ScaffoldOutcome,
+    approved: &[&TemplateHooks],
+    output: &OutputManager,
+) -> CliResult<()> {
+    let root = outcome.structure.root();
+    let hooks: Vec<_> = approved.iter().flat_map(|group| &group.hooks).collect();
+    for (done, hook) in hooks.iter().enumerate() {
+        output.info(&format!("Running {hook}"))?;
+        // The hook runs to completion even if its output cannot be shown;
+        // the first write error is reported after it.
+        let mut print_error = None;
+        let mut forward = |line: &str| {
+            if print_error.is_none() {
+                print_error = output.print(&format!("  {line}")).err();
+            }
+        };
+        let result = service.run_hook(root, hook, &mut forward);
+        if let Err(e) = result {
+            let skipped = hooks.len() - done - 1;
+            let mut note = format!("The project was kept at {}", root.display());
+            if skipped > 0 {
+                note.push_str(&format!("; {skipped} later hook(s) were not run"));
+            }
+            output.warning(&note)?;
+            return Err(CliError::Core(e));
+        }
+        if let Some(e) = print_error {
+            return Err(e.into());
+        }
+    }
+    Ok(())
+}
+
+/// Per-run options from the flags. `--var` values override `--vars-file`;
+/// `--with` overlays are applied after those the file lists.
+fn scaffold_options(args: &NewArgs) -> CliResult<ScaffoldOptions> {
+    let file = match &args.vars_file {
+        Some(path) => answers::load_answers(path).map_err(CliError::Core)?,
+        None => Answers::default(),
+    };
+    let flag_vars = args
+        .vars
+        .iter()
+        .map(|(key, value)| (key.clone(), value.into()));
+
+    let options = ScaffoldOptions::new()
+        .determinism(Determinism {
+            source_date_epoch: args.source_date_epoch,
+            seed: args.seed,
+            git_user_name: args.git_user_name.clone(),
+            git_user_email: args.git_user_email.clone(),
+        })
+        .variables(file.values)
+        .variables(flag_vars);
+    let options = file
+        .overlays
+        .iter()
+        .chain(&args.overlays)
+        .map(|name| name.trim())
+        .filter(|name| !name.is_empty())
+        .fold(options, ScaffoldOptions::overlay);
+    // `--scope` wins over the file's.
+    Ok(match args.scope.as_ref().or(file.scope.as_ref()) {
+        Some(scope) => options.package_scope(scope),
+        None => options,
+    })
+}
+
+/// Ask for the template and overlay variables that have no value yet.
+///
+/// Without a terminal to ask on, supplied values and defaults are used; the
+/// run fails listing every required variable that has neither.
+fn ask_for_variables(
+    service: &ScaffoldService,
+    variables: &[VariableSpec],
+    target: &Target,
+    project_name: &str,
+    options: &ScaffoldOptions,
+) -> CliResult<HashMap<String, RenderValue>> {
+    if variables.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let supplied = &options.variables;
+    let context = service.render_context(target, project_name, variables, options);
+
+    let interactive = can_prompt();
+    #[cfg(feature = "interactive")]
+    if interactive {
+        return prompt::ask_variables(
+            variables,
+            context,
+            supplied,
+            &mut prompt::TerminalPrompter::new(),
+        );
+    }
+
+    let mut unanswered = Unanswered::default();
+    prompt::ask_variables(variables, context, supplied, &mut unanswered)?;
+    if unanswered.names.is_empty() {
+        return Ok(HashMap::new());
+    }
+    Err(CliError::CannotPrompt {
+        variables: unanswered.names,
+        reason: if interactive {
+            "this build has no interactive prompts"
+        } else {
+            "stdin is not a terminal"
+        },
+    })
+}
+
+/// Add [`ANSWERS_FILE`] to the project, so CI can replay this run with
+/// `--vars-file`. Runs that used no variables, overlays or scope have
+/// nothing to replay and get none.
+///
+/// A template shipping a file of the same name fails the run instead of
+/// being overwritten.
+fn record_answers(outcome: &mut ScaffoldOutcome, options: &ScaffoldOptions) -> CliResult<()> {
+    if outcome.answers.is_empty() && options.overlays.is_empty() && options.package_scope.is_none()
+    {
+        return Ok(());
+    }
+    let text = answers::answers_toml(
+        &outcome.template,
+        &Answers {
+            values: outcome.answers.clone(),
+            overlays: options.overlays.clone(),
+            scope: options.package_scope.clone(),
+        },
+    );
+    outcome
+        .structure
+        .add_file(ANSWERS_FILE, text, Permissions::read_write());
+    outcome
+        .structure
+        .validate()
+        .map_err(|e| CliError::Core(e.into()))
+}
+
+/// Whether questions can be asked: prompts read stdin and draw on stderr.
+fn can_prompt() -> bool {
+    std::io::stdin().is_terminal() && std::io::stderr().is_terminal()
+}
+
+/// Wire the production adapters into a [`ScaffoldService`] serving the
+/// `discovered` templates, resolving their content under its root.
+fn build_service(discovered: Option<builtin_templates::Discovered>) -> CliResult<ScaffoldService> {
+    let (store, resolvers) = match discovered {
+        Some(found) => (
+            InMemoryStore::with_templates(found.templates).map_err(CliError::Core)?,
+            ContentResolvers::standard(&found.root),
+        ),
+        None => (InMemoryStore::new(), ContentResolvers::default()),
+    };
+    let store = Box::new(store);
+    let renderer = Box::new(EngineRenderer::with_resolvers(resolvers));
+    let filesystem = Box::new(LocalFilesystem::new());
+
+    Ok(ScaffoldService::new(store, renderer, filesystem)
+        .with_run_info_provider(Box::new(SystemRunInfo::new()))
+        .with_post_processors(post_process::builtin_processors())
+        .with_mergers(merge::builtin_mergers())
+        .with_hook_runner(Box::new(ProcessHookRunner::new())))
+}
+
 // ── Path resolution ───────────────────────────────────────────────────────────
 
 pub fn resolve_project_path(name: &str) -> CliResult<(String, PathBuf)> {
@@ -192,27 +527,70 @@ fn validate_project_name(name: &str) -> CliResult<()> {
 
 /// Translate CLI arguments into a validated `Target`, recording which fields
 /// the user explicitly provided vs what the domain builder will infer.
-fn build_target(args: &NewArgs, _config: &AppConfig) -> CliResult<(Target, UserChoices)> {
+///
+/// Without `--lang`, the wizard asks for everything the flags leave open
+/// when `wizard` is set; otherwise the language falls back to
+/// `defaults.language` from the config file.
+fn build_target(
+    args: &NewArgs,
+    config: &AppConfig,
+    wizard: bool,
+) -> CliResult<(Target, UserChoices)> {
     let mut choices = UserChoices::default();
+    let config_language = config
+        .defaults
+        .language
+        .as_deref()
+        .and_then(|s| s.parse::<CoreLanguage>().ok());
+
+    #[cfg(feature = "interactive")]
+    if wizard && args.language.is_none() {
+        let draft = crate::prompt::TargetDraft {
+            language: None,
+            kind: args.kind.map(convert_kind),
+            framework: args
+                .framework
+                .as_deref()
+                .map(parse_framework_any_language)
+                .transpose()?,
+            architecture: args.architecture.map(convert_architecture),
+        };
+        let target = crate::prompt::ask_target(
+            draft,
+            config_language,
+            &mut crate::prompt::TerminalPrompter::new(),
+        )?;
+        // Every field was either passed or picked by the user.
+        let choices = UserChoices {
+            language: true,
+            kind: true,
+            framework: true,
+            architecture: true,
+        };
+        return Ok((target, choices));
+    }
+    #[cfg(not(feature = "interactive"))]
+    let _ = wizard;
 
-    //todo:  Language — required unless --default falls back to config.
-    // let lang_cli = if args.default && args.language.is_none() {
-    //     config
-    //         .defaults
-    //         .language
-    //         .as_deref()
-    //         .and_then(|s| s.parse::<Language>().ok())
-    //         .unwrap_or(Language::Rust)
-    // } else {
-    //     args.language.ok_or_else(|| CliError::MissingRequiredFlag {
-    //         flag: "--lang / -l".into(),
-    //         hint: "Specify a language (e.g. --lang rust) or use --default.".into(),
-    //     })?
-    // };
-    // choices.language = args.language.is_some();
-
-    let lang = convert_language(args.language);
-    choices.language = true;
+    let implied_language = match (args.language, args.framework.as_deref()) {
+        (None, Some(fw)) => Some(parse_framework_any_language(fw)?.language()),
+        _ => None,
+    };
+    let lang = match args.language {
+        Some(lang) => {
+            choices.language = true;
+            convert_language(lang)
+        }
+        None => implied_language
+            .or(config_language)
+            .ok_or_else(|| CliError::InvalidInput {
+                message:
+                    "--lang is required when scarff cannot ask (no terminal, --yes or --quiet) \
+                      and the config sets no defaults.language"
+                        .into(),
+                source: None,
+            })?,
+    };
     let mut builder = Target::builder().language(lang);
 
     if let Some(kind) = args.kind {
@@ -225,7 +603,7 @@ fn build_target(args: &NewArgs, _config: &AppConfig) -> CliResult<(Target, UserC
     // Framework — optional.
     if let Some(ref fw_str) = args.framework {
         choices.framework = true;
-        let fw = parse_framework(args.language, fw_str)?;
+        let fw = parse_framework(lang, fw_str)?;
         builder = builder
             .framework(fw)
             .map_err(|e| CliError::Core(e.into()))?;
@@ -277,8 +655,7 @@ fn convert_architecture(arch: Architecture) -> CoreArch {
 /// This function consults the capability registry rather than a hand-maintained
 /// match table. Adding a new framework only requires updating
 /// `FRAMEWORK_REGISTRY` in `capabilities.rs`; this function needs no change.
-fn parse_framework(lang: Language, fw: &str) -> CliResult<CoreFramework> {
-    let core_lang = convert_language(lang);
+fn parse_framework(core_lang: CoreLanguage, fw: &str) -> CliResult<CoreFramework> {
     let fw_lower = fw.to_ascii_lowercase();
 
     // Walk the registry and find the first framework belonging to this
@@ -297,12 +674,29 @@ fn parse_framework(lang: Language, fw: &str) -> CliResult<CoreFramework> {
 
         CliError::FrameworkNotAvailable {
             framework: fw.into(),
-            language: lang.to_string(),
+            language: core_lang.to_string(),
             available,
         }
     })
 }
 
+/// Parse a framework given without `--lang`; its language follows from it.
+fn parse_framework_any_language(fw: &str) -> CliResult<CoreFramework> {
+    let fw_lower = fw.to_ascii_lowercase();
+    FRAMEWORK_REGISTRY
+        .iter()
+        .find(|def| def.framework.as_str() == fw_lower.as_str())
+        .map(|def| def.framework)
+        .ok_or_else(|| CliError::FrameworkNotAvailable {
+            framework: fw.into(),
+            language: "any language".into(),
+            available: FRAMEWORK_REGISTRY
+                .iter()
+                .map(|def| def.framework.as_str())
+                .collect(),
+        })
+}
+
 // ── UI helpers ────────────────────────────────────────────────────────────────
 
 // ── Output helpers ────────────────────────────────────────────────────────────
@@ -313,6 +707,7 @@ fn parse_framework(lang: Language, fw: &str) -> CliResult<CoreFramework> {
 fn show_configuration(
     target: &Target,
     choices: &UserChoices,
+    overlays: &[String],
     name: &str,
     project_path: &Path,
     out: &OutputManager,
@@ -346,6 +741,9 @@ fn show_configuration(
         target.architecture(),
         inferred(choices.architecture)
     ))?;
+    if !overlays.is_empty() {
+        out.print(&format!("  Overlays:     {}", overlays.join(", ")))?;
+    }
     out.print(&format!("  Location:     {}", project_path.display()))?;
     out.print("")?;
 
@@ -424,9 +822,14 @@ fn show_next_steps(target: &Target, name: &str, out: &OutputManager) -> CliResul
 }
 
 fn confirm() -> CliResult<bool> {
+    ask_yes_no("Continue? [Y/n] ", true)
+}
+
+/// Ask `question` on stdout; an empty answer means `default`.
+fn ask_yes_no(question: &str, default: bool) -> CliResult<bool> {
     use std::io::{self, Write};
 
-    print!("Continue? [Y/n] ");
+    print!("{question}");
 
     // Map the flush error — panicking here (via unwrap) is wrong when stdout
     // may be closed because the caller piped our output to a closed reader.
@@ -444,7 +847,10 @@ fn confirm() -> CliResult<bool> {
         })?;
 
     let trimmed = input.trim().to_lowercase();
-    Ok(trimmed.is_empty() || trimmed == "y" || trimmed == "yes")
+    Ok(match trimmed.as_str() {
+        "" => default,
+        answer => answer == "y" || answer == "yes",
+    })
 }
 
 // ── Tests ─────────────────────────────────────────────────────────────────────
@@ -454,6 +860,7 @@ mod tests {
     use super::*;
     use scarff_core::domain::{
         Architecture as CoreArch, Framework as CoreFramework, Language as CoreLanguage,
+        ProjectStructure, TemplateId,
         value_objects::{GoFramework, PythonFramework, RustFramework, TypeScriptFramework},
     };
 
@@ -530,37 +937,37 @@ mod tests {
 
     #[test]
     fn axum_parses_for_rust() {
-        let fw = parse_framework(Language::Rust, "axum").unwrap();
+        let fw = parse_framework(CoreLanguage::Rust, "axum").unwrap();
         assert_eq!(fw, CoreFramework::Rust(RustFramework::Axum));
     }
 
     #[test]
     fn actix_parses_for_rust() {
-        let fw = parse_framework(Language::Rust, "actix").unwrap();
+        let fw = parse_framework(CoreLanguage::Rust, "actix").unwrap();
         assert_eq!(fw, CoreFramework::Rust(RustFramework::Actix));
     }
 
     #[test]
     fn fastapi_parses_for_python() {
-        let fw = parse_framework(Language::Python, "fastapi").unwrap();
+        let fw = parse_framework(CoreLanguage::Python, "fastapi").unwrap();
         assert_eq!(fw, CoreFramework::Python(PythonFramework::FastApi));
     }
 
     #[test]
     fn gin_parses_for_go() {
-        let fw = parse_framework(Language::Go, "gin").unwrap();
+        let fw = parse_framework(CoreLanguage::Go, "gin").unwrap();
         assert_eq!(fw, CoreFramework::Go(GoFramework::Gin));
     }
 
     #[test]
     fn nestjs_parses_for_typescript() {
-        let fw = parse_framework(Language::TypeScript, "nestjs").unwrap();
+        let fw = parse_framework(CoreLanguage::TypeScript, "nestjs").unwrap();
         assert_eq!(fw, CoreFramework::TypeScript(TypeScriptFramework::NestJs));
     }
 
     #[test]
     fn svelte_parses_for_typescript() {
-        let fw = parse_framework(Language::TypeScript, "svelte").unwrap();
+        let fw = parse_framework(CoreLanguage::TypeScript, "svelte").unwrap();
         assert_eq!(fw, CoreFramework::TypeScript(TypeScriptFramework::Svelte));
     }
 
@@ -568,14 +975,14 @@ mod tests {
     fn wrong_language_framework_is_error() {
         // Django is a Python framework, not a Rust one.
         assert!(matches!(
-            parse_framework(Language::Rust, "django"),
+            parse_framework(CoreLanguage::Rust, "django"),
             Err(CliError::FrameworkNotAvailable { .. })
         ));
     }
 
     #[test]
     fn unknown_framework_gives_actionable_error_with_available_list() {
-        let err = parse_framework(Language::Rust, "unknown-fw").unwrap_err();
+        let err = parse_framework(CoreLanguage::Rust, "unknown-fw").unwrap_err();
         match err {
             CliError::FrameworkNotAvailable {
                 framework,
@@ -594,9 +1001,9 @@ mod tests {
     #[test]
     fn framework_matching_is_case_insensitive() {
         // Registry stores lowercase; we normalise input to lowercase before matching.
-        assert!(parse_framework(Language::Rust, "AXUM").is_ok());
-        assert!(parse_framework(Language::Rust, "Axum").is_ok());
-        assert!(parse_framework(Language::Python, "FastAPI").is_ok());
+        assert!(parse_framework(CoreLanguage::Rust, "AXUM").is_ok());
+        assert!(parse_framework(CoreLanguage::Rust, "Axum").is_ok());
+        assert!(parse_framework(CoreLanguage::Python, "FastAPI").is_ok());
     }
 
     // ── convert_language covers all variants ──────────────────────────────────
@@ -613,4 +1020,49 @@ mod tests {
     fn onion_converts_to_clean() {
         assert_eq!(convert_architecture(Architecture::Onion), CoreArch::Clean);
     }
+
+    // ── record_answers ────────────────────────────────────────────────────────
+
+    fn outcome(structure: ProjectStructure) -> ScaffoldOutcome {
+        ScaffoldOutcome {
+            structure,
+            template: TemplateId::new("t", "1.0.0"),
+            overlays: Vec::new(),
+            answers: [("db".to_string(), RenderValue::from("postgres"))].into(),
+            hooks: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn answers_are_part_of_the_written_project() {
+        let structure =
+            ProjectStructure::new("app").with_file("README.md", "", Permissions::read_write());
+        let mut outcome = outcome(structure);
+        let before = outcome.structure.digest();
+
+        record_answers(&mut outcome, &ScaffoldOptions::new()).unwrap();
+
+        let file = outcome
+            .structure
+            .files()
+            .find(|f| f.path == Path::new(ANSWERS_FILE))
+            .expect("answers file added");
+        assert!(
+            file.content
+                .as_text()
+                .unwrap()
+                .contains("db = \"postgres\"")
+        );
+        assert_ne!(outcome.structure.digest(), before);
+    }
+
+    #[test]
+    fn template_file_named_like_the_answers_file_fails_the_run() {
+        let structure =
+            ProjectStructure::new("app").with_file(ANSWERS_FILE, "mine", Permissions::read_write());
+
+        let err = record_answers(&mut outcome(structure), &ScaffoldOptions::new()).unwrap_err();
+
+        assert!(err.to_string().contains(ANSWERS_FILE), "{err}");
+    }
 }
@@ -7,15 +7,25 @@
 //!
 //! 1. CLI flags (handled at the call-site, not here)
 //! 2. Environment variables (TODO: implement)
-//! 3. Config file (TODO: implement file reading)
+//! 3. Config file (`--config`, or [`AppConfig::config_path`] if it exists);
+//!    sections and keys it leaves out keep their defaults
 //! 4. Built-in defaults (always present)
+//!
+//! ```toml
+//! [hooks]
+//! # Executables template hooks may run; anything else fails the run.
+//! # Omit to allow any command.
+//! allowed_commands = ["git", "cargo", "npm"]
+//! ```
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+use anyhow::Context as _;
 use serde::{Deserialize, Serialize};
 
 /// Application configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AppConfig {
     /// Default values for new projects.
     pub defaults: Defaults,
@@ -23,9 +33,12 @@ pub struct AppConfig {
     pub output: OutputConfig,
     /// Template settings.
     pub templates: TemplateConfig,
+    /// Post-generation hook settings.
+    pub hooks: HooksConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Defaults {
     pub language: Option<String>,
     pub kind: Option<String>,
@@ -34,34 +47,53 @@ pub struct Defaults {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct OutputConfig {
     pub no_color: bool,
     pub format: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct TemplateConfig {
     pub registry_url: Option<String>,
     pub local_path: Option<PathBuf>,
 }
 
-impl Default for AppConfig {
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HooksConfig {
+    /// Executables hooks may run, compared with the command exactly as the
+    /// template writes it (`"git"` does not allow `"/tmp/git"`). `None`
+    /// allows any command.
+    pub allowed_commands: Option<Vec<String>>,
+}
+
+impl HooksConfig {
+    /// Whether hooks may run `command`.
+    pub fn allows(&self, command: &str) -> bool {
+        self.allowed_commands
+            .as_ref()
+            .is_none_or(|allowed| allowed.iter().any(|c| c == command))
+    }
+}
+
+impl Default for Defaults {
     fn default() -> Self {
         Self {
-            defaults: Defaults {
-                language: Some("rust".into()),
-                kind: Some("cli".into()),
-                architecture: Some("layered".into()),
-                framework: None,
-            },
-            output: OutputConfig {
-                no_color: false,
-                format: "human".into(),
-            },
-            templates: TemplateConfig {
-                registry_url: None,
-                local_path: None,
-            },
+            language: Some("rust".into()),
+            kind: Some("cli".into()),
+            architecture: Some("layered".into()),
+            framework: None,
+        }
+    }
+}
+
+impl Default for OutputConfig {
+    fn default() -> Self {
+        Self {
+            no_color: false,
+            format: "human".into(),
         }
     }
 }
@@ -69,16 +101,28 @@ impl Default for AppConfig {
 impl AppConfig {
     /// Load configuration, starting from defaults.
     ///
-    /// The `config_file` parameter is the path the user passed via `--config`
-    /// (or `None` to use the default location).  File reading is not yet
-    /// implemented; this always returns the built-in defaults.
+    /// The `config_file` parameter is the path the user passed via `--config`,
+    /// which must exist; with `None` the default location is read if there is
+    /// a file there.
     pub fn load(config_file: Option<&PathBuf>) -> anyhow::Result<Self> {
-        // The `config_file` parameter is intentionally unused until file-
-        // reading is implemented.  Prefix with `_` to avoid an unused-variable
-        // warning while making the intent clear.
-        let _config_file = config_file;
-        // TODO: read from TOML file, merge env vars, merge CLI overrides.
-        Ok(Self::default())
+        // TODO: merge env vars.
+        match config_file {
+            Some(path) => Self::from_file(path),
+            None => {
+                let path = Self::config_path();
+                if path.is_file() {
+                    Self::from_file(&path)
+                } else {
+                    Ok(Self::default())
+                }
+            }
+        }
+    }
+
+    fn from_file(path: &Path) -> anyhow::Result<Self> {
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("cannot read config file {}", path.display()))?;
+        toml::from_str(&raw).with_context(|| format!("invalid config file {}", path.display()))
     }
 
     /// Path to the default configuration file.
@@ -113,6 +157,27 @@ mod tests {
         assert_eq!(cfg.defaults.kind.as_deref(), Some("cli"));
     }
 
+    #[test]
+    fn config_file_keeps_defaults_for_what_it_leaves_out() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(
+            &path,
+            "[output]\nno_color = true\n\n[hooks]\nallowed_commands = [\"git\"]\n",
+        )
+        .unwrap();
+
+        let cfg = AppConfig::load(Some(&path)).unwrap();
+        assert!(cfg.output.no_color);
+        assert_eq!(cfg.output.format, "human");
+        assert_eq!(cfg.defaults.language.as_deref(), Some("rust"));
+        assert!(cfg.hooks.allows("git"));
+        assert!(!cfg.hooks.allows("/tmp/git"));
+        assert!(AppConfig::default().hooks.allows("anything"));
+
+        assert!(AppConfig::load(Some(&dir.path().join("missing.toml"))).is_err());
+    }
+
     #[test]
     fn config_path_is_absolute_or_relative() {
         // Just assert it doesn't panic and returns a non-empty path.
@@ -95,6 +95,13 @@ pub enum CliError {
         source: std::io::Error,
     },
 
+    /// Template variables need values but cannot be asked for.
+    #[error("No value for template variable(s) {}: {reason}", variables.join(", "))]
+    CannotPrompt {
+        variables: Vec<String>,
+        reason: &'static str,
+    },
+
     /// Operation cancelled by user.
     #[error("Operation cancelled")]
     Cancelled,
@@ -111,6 +118,10 @@ pub enum CliError {
     #[error("Feature not available: {feature}")]
     FeatureNotAvailable { feature: &'static str },
 
+    /// A template hook runs a command the config does not allow.
+    #[error("Hook command '{command}' of template '{template}' is not allowed")]
+    HookNotAllowed { command: String, template: String },
+
     /// External command failed.
     #[error("External command failed: {command}")]
     ExternalCommandFailed {
@@ -222,6 +233,19 @@ impl CliError {
                 "Check available disk space".into(),
             ],
 
+            Self::CannotPrompt { variables, .. } => vec![
+                format!(
+                    "Pass them on the command line: {}",
+                    variables
+                        .iter()
+                        .map(|v| format!("--var {v}=..."))
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                ),
+                "Or read them from a file: --vars-file answers.toml".into(),
+                "Or accept the template defaults: --yes".into(),
+            ],
+
             Self::Cancelled => vec![
                 "Operation was cancelled".into(),
                 "No changes were made".into(),
@@ -244,6 +268,16 @@ impl CliError {
                 "Install with the feature enabled: cargo install scarff-cli --features {}".into(),
             ],
 
+            Self::HookNotAllowed { command, .. } => vec![
+                format!(
+                    "Add '{}' to [hooks] allowed_commands in {}",
+                    command,
+                    crate::config::AppConfig::config_path().display()
+                ),
+                "Or skip the template's hooks: --no-hooks".into(),
+                "Nothing was written and no hooks were run".into(),
+            ],
+
             Self::ExternalCommandFailed { command, .. } => vec![
                 format!("External command failed: {}", command),
                 "Ensure the command is installed and in your PATH".into(),
@@ -267,15 +301,18 @@ impl CliError {
                 CoreCategory::Validation => ErrorCategory::UserError,
                 CoreCategory::Compatibility => ErrorCategory::UserError,
                 CoreCategory::NotFound => ErrorCategory::NotFound,
+                CoreCategory::External => ErrorCategory::External,
                 CoreCategory::Internal => ErrorCategory::Internal,
-                CoreCategory::Configuration => todo!(),
+                CoreCategory::Configuration => ErrorCategory::Configuration,
             },
             Self::IoError { .. } => ErrorCategory::Internal,
+            Self::CannotPrompt { .. } => ErrorCategory::UserError,
             Self::Cancelled => ErrorCategory::UserError,
             Self::TemplateNotFound { .. } => ErrorCategory::NotFound,
             Self::AmbiguousTemplate { .. } => ErrorCategory::UserError,
             Self::FeatureNotAvailable { .. } => ErrorCategory::Configuration,
-            Self::ExternalCommandFailed { .. } => ErrorCategory::Internal,
+            Self::HookNotAllowed { .. } => ErrorCategory::Configuration,
+            Self::ExternalCommandFailed { .. } => ErrorCategory::External,
         }
     }
 
@@ -287,12 +324,14 @@ impl CliError {
     /// | User error    |  2   |
     /// | Not found     |  3   |
     /// | Configuration |  4   |
+    /// | External      |  5   |
     /// | Internal      |  1   |
     pub fn exit_code(&self) -> u8 {
         match self.category() {
             ErrorCategory::UserError => 2,
             ErrorCategory::NotFound => 3,
             ErrorCategory::Configuration => 4,
+            ErrorCategory::External => 5,
             ErrorCategory::Internal => 1,
         }
     }
@@ -380,6 +419,7 @@ impl CliError {
             ErrorCategory::UserError => tracing::warn!("User error: {}", self),
             ErrorCategory::NotFound => tracing::warn!("Not found: {}", self),
             ErrorCategory::Configuration => tracing::error!("Configuration error: {}", self),
+            ErrorCategory::External => tracing::warn!("External command failed: {}", self),
             ErrorCategory::Internal => tracing::error!("Internal error: {}", self),
         }
 
@@ -398,6 +438,8 @@ pub enum ErrorCategory {
     NotFound,
     /// Configuration error.
     Configuration,
+    /// A command run on the user's behalf (a hook, an external tool) failed.
+    External,
     /// Internal/system error.
     Internal,
 }
@@ -524,6 +566,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn exit_code_core_configuration() {
+        let unconfigured = ScarffError::from(
+            scarff_core::application::ApplicationError::AdapterNotConfigured { name: "HookRunner" },
+        );
+        assert_eq!(CliError::Core(unconfigured).exit_code(), 4);
+    }
+
+    #[test]
+    fn exit_code_external() {
+        let hook_failed =
+            ScarffError::from(scarff_core::application::ApplicationError::HookFailed {
+                command: "npm install".into(),
+                reason: "exited with status 1".into(),
+            });
+        assert_eq!(CliError::Core(hook_failed).exit_code(), 5);
+    }
+
     #[test]
     fn exit_code_internal() {
         assert_eq!(
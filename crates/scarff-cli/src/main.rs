@@ -13,13 +13,14 @@
 //!
 //! ## Exit codes
 //!
-//! | Code | Meaning                 |
-//! |------|-------------------------|
-//! |  0   | Success                 |
-//! |  1   | Internal / system error |
-//! |  2   | User / input error      |
-//! |  3   | Resource not found      |
-//! |  4   | Configuration error     |
+//! | Code | Meaning                           |
+//! |------|-----------------------------------|
+//! |  0   | Success                           |
+//! |  1   | Internal / system error           |
+//! |  2   | User / input error                |
+//! |  3   | Resource not found                |
+//! |  4   | Configuration error               |
+//! |  5   | A hook or external command failed |
 
 use std::process::ExitCode;
 
@@ -40,6 +41,8 @@ mod config;
 mod error;
 mod logging;
 mod output;
+mod prompt;
+mod trust;
 
 fn main() -> ExitCode {
     // Load .env before anything else — including tracing init.
@@ -98,7 +101,7 @@ fn main() -> ExitCode {
 #[instrument(skip_all)]
 fn run(cli: Cli, config: AppConfig, output: OutputManager) -> CliResult<()> {
     match cli.command {
-        Commands::New(cmd) => commands::new::execute(cmd, cli.global, config, output),
+        Commands::New(cmd) => commands::new::execute(*cmd, cli.global, config, output),
         Commands::List(cmd) => commands::list::execute(cmd, cli.global, output),
         Commands::Init(cmd) => commands::init::execute(cmd, cli.global, config, output),
         Commands::Completions(cmd) => commands::completions::execute(cmd),
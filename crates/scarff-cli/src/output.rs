@@ -49,6 +49,12 @@ impl OutputManager {
         self.term.write_line(msg)
     }
 
+    /// Machine-readable command result (e.g. `--print-digest`); printed
+    /// even in quiet mode, without decoration.
+    pub fn result(&self, msg: &str) -> io::Result<()> {
+        self.term.write_line(msg)
+    }
+
     /// Success indicator: `✓ <msg>`.
     pub fn success(&self, msg: &str) -> io::Result<()> {
         if self.quiet {
@@ -0,0 +1,490 @@
+//! Interactive questions: the target wizard and template-declared variables.
+//!
+//! [`ask_target`] fills in the parts of the target the user did not pass as
+//! flags, offering only what the capability registry allows for the answers
+//! so far, so every combination it can produce is valid.
+//!
+//! [`ask_variables`] walks a template's `[[variables]]` in declaration order
+//! and asks for every one the user has not supplied via `--var` /
+//! `--vars-file`, skipping those whose `when` condition is false given the
+//! answers so far. How a question is put is up to the [`Prompter`]:
+//!
+//! - [`TerminalPrompter`] (feature `interactive`) asks on the terminal:
+//!   yes/no for `bool`, a selection for `choice` and `list` with choices,
+//!   text input otherwise. Defaults are pre-filled.
+//! - [`Unanswered`] asks nothing; it records which questions *would* be
+//!   asked, so non-interactive runs can fail with the full list.
+
+use std::collections::HashMap;
+
+use scarff_core::domain::{RenderContext, RenderValue, VariableSpec};
+
+use crate::error::CliResult;
+
+/// Puts questions to the user.
+pub trait Prompter {
+    /// Ask for `spec`, offering `default`. The returned value is checked by
+    /// the core again, but should already satisfy [`VariableSpec::parse`].
+    fn ask(&mut self, spec: &VariableSpec, default: Option<&RenderValue>)
+    -> CliResult<RenderValue>;
+
+    /// Pick one of `items`, with `items[default]` pre-selected. Returns the
+    /// index picked.
+    #[cfg_attr(not(feature = "interactive"), allow(dead_code))]
+    fn select(&mut self, prompt: &str, items: &[String], default: usize) -> CliResult<usize>;
+}
+
+/// Ask for every variable in `specs` that applies and has no value in
+/// `supplied`, in declaration order.
+///
+/// `context` should hold the built-ins; supplied values and earlier answers
+/// are added to it as the walk proceeds, so defaults and conditions see
+/// them. Returns only the newly given answers.
+pub fn ask_variables(
+    specs: &[VariableSpec],
+    mut context: RenderContext,
+    supplied: &HashMap<String, RenderValue>,
+    prompter: &mut dyn Prompter,
+) -> CliResult<HashMap<String, RenderValue>> {
+    for (name, value) in supplied {
+        context = context.with_variable(name.clone(), value.clone());
+    }
+
+    let mut answers = HashMap::new();
+    for spec in specs {
+        let value = match supplied.get(&spec.name) {
+            // Normalize (`yes` → `true`) so conditions compare canonical values;
+            // invalid input is reported by the core later.
+            Some(value) => spec.parse(value).unwrap_or_else(|_| value.clone()),
+            None if !spec.applies(&context) => continue,
+            None => {
+                let default = spec.default_in(&context);
+                let answer = prompter.ask(spec, default.as_ref())?;
+                answers.insert(spec.name.clone(), answer.clone());
+                answer
+            }
+        };
+        context = context.with_variable(spec.name.clone(), value);
+    }
+
+    Ok(answers)
+}
+
+/// Answers each variable with its default, recording those that would have
+/// to be asked for: required ones without a default.
+#[derive(Debug, Default)]
+pub struct Unanswered {
+    pub names: Vec<String>,
+}
+
+impl Prompter for Unanswered {
+    fn ask(
+        &mut self,
+        spec: &VariableSpec,
+        default: Option<&RenderValue>,
+    ) -> CliResult<RenderValue> {
+        match default {
+            Some(default) => Ok(default.clone()),
+            None => {
+                if spec.required {
+                    self.names.push(spec.name.clone());
+                }
+                Ok(String::new().into())
+            }
+        }
+    }
+
+    fn select(&mut self, prompt: &str, _items: &[String], default: usize) -> CliResult<usize> {
+        self.names.push(prompt.to_string());
+        Ok(default)
+    }
+}
+
+#[cfg(any(feature = "interactive", test))]
+pub use wizard::{TargetDraft, ask_target};
+
+#[cfg(any(feature = "interactive", test))]
+mod wizard {
+    use scarff_core::domain::{
+        Architecture, Framework, Language, ProjectKind, Target,
+        capabilities::{self, LANGUAGE_REGISTRY},
+    };
+
+    use super::Prompter;
+    use crate::error::{CliError, CliResult};
+
+    /// The parts of a target given as flags; `None` parts are asked for.
+    #[derive(Debug, Default, Clone, Copy)]
+    pub struct TargetDraft {
+        pub language: Option<Language>,
+        pub kind: Option<ProjectKind>,
+        pub framework: Option<Framework>,
+        pub architecture: Option<Architecture>,
+    }
+
+    /// Ask for the missing parts of `draft` in order language → kind →
+    /// framework → architecture, and build the target.
+    ///
+    /// Each list is filtered by the earlier answers (kinds the language
+    /// supports, frameworks supporting the kind, compatible architectures),
+    /// with the registry default pre-selected. `preferred_language` (e.g. from
+    /// the config file) is pre-selected instead of the default language.
+    pub fn ask_target(
+        draft: TargetDraft,
+        preferred_language: Option<Language>,
+        prompter: &mut dyn Prompter,
+    ) -> CliResult<Target> {
+        // A framework implies its language.
+        let language = match draft.language.or(draft.framework.map(|f| f.language())) {
+            Some(language) => language,
+            None => {
+                let languages: Vec<Language> =
+                    LANGUAGE_REGISTRY.iter().map(|d| d.language).collect();
+                let preferred = preferred_language
+                    .filter(|p| languages.contains(p))
+                    .unwrap_or_else(capabilities::default_language);
+                let default = languages.iter().position(|&l| l == preferred).unwrap_or(0);
+                languages[pick(prompter, "Language", &languages, default)?]
+            }
+        };
+
+        let kind = match draft.kind {
+            Some(kind) => kind,
+            None => {
+                let kinds = capabilities::find_language(language)
+                    .map(|d| d.supported_kinds.to_vec())
+                    .unwrap_or_default();
+                let default = capabilities::infer_kind(language, draft.framework);
+                let default = kinds.iter().position(|&k| k == default).unwrap_or(0);
+                kinds[pick(prompter, "Project type", &kinds, default)?]
+            }
+        };
+
+        let framework = match draft.framework {
+            Some(framework) => Some(framework),
+            None => {
+                let mut frameworks: Vec<Option<Framework>> =
+                    capabilities::frameworks_for(language, kind)
+                        .into_iter()
+                        .map(Some)
+                        .collect();
+                if !kind.requires_framework() {
+                    frameworks.insert(0, None);
+                }
+                // The registry default if the pair has one, else the first.
+                let inferred = capabilities::infer_framework(language, kind);
+                let default = frameworks.iter().position(|&f| f == inferred).unwrap_or(0);
+                match frameworks.len() {
+                    0 | 1 => frameworks.first().copied().flatten(),
+                    _ => {
+                        let labels: Vec<String> = frameworks
+                            .iter()
+                            .map(|f| f.map_or_else(|| "none".to_string(), |f| f.to_string()))
+                            .collect();
+                        frameworks[prompter.select("Framework", &labels, default)?]
+                    }
+                }
+            }
+        };
+
+        let architecture = match draft.architecture {
+            Some(architecture) => architecture,
+            None => {
+                let archs = capabilities::architectures_for(language, kind, framework);
+                let default = capabilities::infer_architecture(language, kind, framework);
+                let default = archs.iter().position(|&a| a == default).unwrap_or(0);
+                archs[pick(prompter, "Architecture", &archs, default)?]
+            }
+        };
+
+        let mut builder = Target::builder()
+            .language(language)
+            .kind(kind)
+            .map_err(|e| CliError::Core(e.into()))?
+            .architecture(architecture);
+        if let Some(framework) = framework {
+            builder = builder
+                .framework(framework)
+                .map_err(|e| CliError::Core(e.into()))?;
+        }
+        builder.build().map_err(|e| CliError::Core(e.into()))
+    }
+
+    fn pick<T: ToString>(
+        prompter: &mut dyn Prompter,
+        prompt: &str,
+        items: &[T],
+        default: usize,
+    ) -> CliResult<usize> {
+        let labels: Vec<String> = items.iter().map(ToString::to_string).collect();
+        prompter.select(prompt, &labels, default)
+    }
+}
+
+#[cfg(feature = "interactive")]
+pub use terminal::TerminalPrompter;
+
+#[cfg(feature = "interactive")]
+mod terminal {
+    use dialoguer::{Confirm, Input, MultiSelect, Select, theme::ColorfulTheme};
+    use scarff_core::domain::{RenderValue, VariableKind, VariableSpec};
+
+    use super::Prompter;
+    use crate::error::{CliError, CliResult};
+
+    /// Asks on the controlling terminal.
+    #[derive(Default)]
+    pub struct TerminalPrompter {
+        theme: ColorfulTheme,
+    }
+
+    impl TerminalPrompter {
+        pub fn new() -> Self {
+            Self::default()
+        }
+    }
+
+    impl Prompter for TerminalPrompter {
+        fn ask(
+            &mut self,
+            spec: &VariableSpec,
+            default: Option<&RenderValue>,
+        ) -> CliResult<RenderValue> {
+            let prompt = match &spec.description {
+                Some(description) => format!("{description} ({})", spec.name),
+                None => spec.name.clone(),
+            };
+            let default_text = default.map(ToString::to_string);
+
+            let value = match &spec.kind {
+                VariableKind::Bool => Confirm::with_theme(&self.theme)
+                    .with_prompt(prompt)
+                    .default(default_text.as_deref() == Some("true"))
+                    .interact()
+                    .map(|yes| RenderValue::from(yes.to_string())),
+                VariableKind::Choice(choices) => {
+                    let selected = default_text
+                        .as_ref()
+                        .and_then(|d| choices.iter().position(|c| c == d))
+                        .unwrap_or(0);
+                    Select::with_theme(&self.theme)
+                        .with_prompt(prompt)
+                        .items(choices)
+                        .default(selected)
+                        .interact()
+                        .map(|i| RenderValue::from(choices[i].as_str()))
+                }
+                VariableKind::List { choices } if !choices.is_empty() => {
+                    let checked: Vec<bool> = choices
+                        .iter()
+                        .map(|c| {
+                            default.is_some_and(|d| {
+                                d.iter_items()
+                                    .iter()
+                                    .any(|(_, item)| item.to_string() == *c)
+                            })
+                        })
+                        .collect();
+                    MultiSelect::with_theme(&self.theme)
+                        .with_prompt(prompt)
+                        .items(choices)
+                        .defaults(&checked)
+                        .interact()
+                        .map(|picked| {
+                            RenderValue::List(
+                                picked
+                                    .into_iter()
+                                    .map(|i| RenderValue::from(choices[i].as_str()))
+                                    .collect(),
+                            )
+                        })
+                }
+                _ => {
+                    let mut input = Input::<String>::with_theme(&self.theme)
+                        .with_prompt(prompt)
+                        .allow_empty(!spec.required)
+                        .validate_with(|text: &String| {
+                            spec.parse(&text.as_str().into()).map(|_| ())
+                        });
+                    if let Some(text) = default_text {
+                        input = input.default(text);
+                    }
+                    input.interact_text().map(RenderValue::from)
+                }
+            };
+
+            value.map_err(|dialoguer::Error::IO(source)| CliError::IoError {
+                message: format!("failed to read a value for '{}'", spec.name),
+                source,
+            })
+        }
+
+        fn select(&mut self, prompt: &str, items: &[String], default: usize) -> CliResult<usize> {
+            Select::with_theme(&self.theme)
+                .with_prompt(prompt)
+                .items(items)
+                .default(default)
+                .interact()
+                .map_err(|dialoguer::Error::IO(source)| CliError::IoError {
+                    message: format!("failed to read a choice for '{prompt}'"),
+                    source,
+                })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scarff_core::domain::{Architecture, Condition, Framework, Language, VariableKind};
+
+    /// Answers from a script and records what was asked.
+    struct Scripted {
+        answers: Vec<&'static str>,
+        asked: Vec<(String, Option<RenderValue>)>,
+    }
+
+    impl Prompter for Scripted {
+        fn ask(
+            &mut self,
+            spec: &VariableSpec,
+            default: Option<&RenderValue>,
+        ) -> CliResult<RenderValue> {
+            self.asked.push((spec.name.clone(), default.cloned()));
+            Ok(self.answers.remove(0).into())
+        }
+
+        /// Picks the item named by the next answer.
+        fn select(&mut self, prompt: &str, items: &[String], default: usize) -> CliResult<usize> {
+            self.asked
+                .push((prompt.to_string(), Some(items[default].as_str().into())));
+            let answer = self.answers.remove(0);
+            Ok(items.iter().position(|i| i == answer).expect(answer))
+        }
+    }
+
+    fn specs() -> Vec<VariableSpec> {
+        vec![
+            VariableSpec::new("db")
+                .kind(VariableKind::Choice(vec!["none".into(), "postgres".into()]))
+                .default_value("none"),
+            VariableSpec::new("db_name")
+                .default_value("{{PROJECT_NAME_SNAKE}}_{{db}}")
+                .when(Condition::parse("db != 'none'").unwrap()),
+            VariableSpec::new("docker").kind(VariableKind::Bool),
+        ]
+    }
+
+    #[test]
+    fn dependent_questions_follow_earlier_answers() {
+        let mut prompter = Scripted {
+            answers: vec!["postgres", "app_db", "true"],
+            asked: Vec::new(),
+        };
+
+        let answers = ask_variables(
+            &specs(),
+            RenderContext::new("My App"),
+            &HashMap::new(),
+            &mut prompter,
+        )
+        .unwrap();
+
+        assert_eq!(answers.len(), 3);
+        assert_eq!(
+            prompter.asked[1],
+            ("db_name".to_string(), Some("my_app_postgres".into()))
+        );
+    }
+
+    #[test]
+    fn wizard_offers_registry_defaults_and_builds_a_valid_target() {
+        use scarff_core::domain::{PythonFramework, TypeScriptFramework};
+
+        let mut prompter = Scripted {
+            answers: vec!["python", "web-backend", "fastapi", "layered"],
+            asked: Vec::new(),
+        };
+        let target = ask_target(TargetDraft::default(), None, &mut prompter).unwrap();
+
+        assert_eq!(
+            target.framework(),
+            Some(Framework::Python(PythonFramework::FastApi))
+        );
+        let defaults: Vec<_> = prompter.asked.iter().map(|(_, d)| d.clone()).collect();
+        assert_eq!(
+            defaults,
+            [
+                Some("rust".into()),
+                Some("web-backend".into()),
+                Some("fastapi".into()),
+                Some("layered".into()),
+            ]
+        );
+
+        // Flags are not asked again; the framework implies the language.
+        let mut unanswered = Unanswered::default();
+        let target = ask_target(
+            TargetDraft {
+                framework: Some(Framework::TypeScript(TypeScriptFramework::NestJs)),
+                ..TargetDraft::default()
+            },
+            None,
+            &mut unanswered,
+        )
+        .unwrap();
+        assert_eq!(target.language(), Language::TypeScript);
+        assert_eq!(unanswered.names, ["Project type", "Architecture"]);
+
+        // "none" is offered for kinds that work without a framework, but the
+        // registry default is still the one pre-selected.
+        let mut prompter = Scripted {
+            answers: vec!["worker", "none"],
+            asked: Vec::new(),
+        };
+        let target = ask_target(
+            TargetDraft {
+                language: Some(Language::Python),
+                architecture: Some(Architecture::Layered),
+                ..TargetDraft::default()
+            },
+            None,
+            &mut prompter,
+        )
+        .unwrap();
+        assert_eq!(target.framework(), None);
+        assert_eq!(
+            prompter.asked[1],
+            ("Framework".to_string(), Some("fastapi".into()))
+        );
+    }
+
+    #[test]
+    fn supplied_values_are_not_asked_and_gate_conditions() {
+        let mut unanswered = Unanswered::default();
+        let supplied = HashMap::from([("db".to_string(), RenderValue::from("none"))]);
+
+        ask_variables(
+            &specs(),
+            RenderContext::new("app"),
+            &supplied,
+            &mut unanswered,
+        )
+        .unwrap();
+
+        assert_eq!(unanswered.names, ["docker"]);
+
+        // Defaults and optional variables need no answer.
+        let mut unanswered = Unanswered::default();
+        let mut specs = specs();
+        specs.push(VariableSpec::new("notes").required(false));
+        ask_variables(
+            &specs,
+            RenderContext::new("app"),
+            &HashMap::new(),
+            &mut unanswered,
+        )
+        .unwrap();
+        assert_eq!(unanswered.names, ["docker"]);
+    }
+}
@@ -0,0 +1,137 @@
+//! Which templates the user has trusted to run post-generation hooks.
+//!
+//! Hooks from templates outside the built-in set only run once the user
+//! agrees, after seeing the exact commands, or passes `--trust`. The
+//! decision is recorded against the template's id *and* the
+//! [`digest`](scarff_core::application::TemplateHooks::digest) of its
+//! content (external files included) and rendered commands, so it lapses by
+//! itself as soon as either changes:
+//!
+//! ```toml
+//! # <config dir>/trusted-hooks.toml
+//! [templates]
+//! "rust-cli@1.0.0" = "sha256:3f1a…"
+//! ```
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{CliError, CliResult};
+
+/// What the store knows about a template's hooks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trust {
+    /// Trusted with exactly this content.
+    Trusted,
+    /// Trusted once, but the template has changed since.
+    Changed,
+    /// Never trusted.
+    Unknown,
+}
+
+/// Trust decisions, by template id, persisted as TOML.
+#[derive(Debug, Default)]
+pub struct TrustStore {
+    path: PathBuf,
+    file: TrustFile,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct TrustFile {
+    #[serde(default)]
+    templates: BTreeMap<String, String>,
+}
+
+impl TrustStore {
+    /// `trusted-hooks.toml` beside the config file.
+    pub fn default_path() -> PathBuf {
+        directories::ProjectDirs::from("com", "scarff", "scarff")
+            .map(|d| d.config_dir().join("trusted-hooks.toml"))
+            .unwrap_or_else(|| PathBuf::from(".scarff-trusted-hooks.toml"))
+    }
+
+    /// Read the decisions stored at `path`; none if there is no file yet.
+    pub fn load(path: impl Into<PathBuf>) -> CliResult<Self> {
+        let path = path.into();
+        let file = match fs::read_to_string(&path) {
+            Ok(raw) => toml::from_str(&raw).map_err(|e| CliError::ConfigError {
+                message: format!("invalid trust file {}: {e}", path.display()),
+                source: Some(Box::new(e)),
+            })?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => TrustFile::default(),
+            Err(e) => {
+                return Err(CliError::IoError {
+                    message: format!("cannot read {}", path.display()),
+                    source: e,
+                });
+            }
+        };
+        Ok(Self { path, file })
+    }
+
+    /// Whether the template `id` with content `digest` is trusted.
+    pub fn check(&self, id: &str, digest: &str) -> Trust {
+        match self.file.templates.get(id) {
+            Some(trusted) if trusted == digest => Trust::Trusted,
+            Some(_) => Trust::Changed,
+            None => Trust::Unknown,
+        }
+    }
+
+    /// Trust the template `id` with content `digest`, replacing any earlier
+    /// decision for it, and save.
+    pub fn trust(&mut self, id: &str, digest: &str) -> CliResult<()> {
+        self.file
+            .templates
+            .insert(id.to_string(), digest.to_string());
+        self.save()
+    }
+
+    /// Where the decisions are stored.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn save(&self) -> CliResult<()> {
+        let io_error = |message: String| move |source| CliError::IoError { message, source };
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(io_error(format!("cannot create {}", parent.display())))?;
+        }
+        let raw = toml::to_string_pretty(&self.file).map_err(|e| CliError::ConfigError {
+            message: format!("cannot serialise trust file: {e}"),
+            source: Some(Box::new(e)),
+        })?;
+        fs::write(&self.path, raw)
+            .map_err(io_error(format!("cannot write {}", self.path.display())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trust_lapses_when_the_digest_changes() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("nested/trusted-hooks.toml");
+
+        let mut store = TrustStore::load(&path).unwrap();
+        assert_eq!(store.check("t@1.0", "sha256:a"), Trust::Unknown);
+        store.trust("t@1.0", "sha256:a").unwrap();
+
+        let mut store = TrustStore::load(&path).unwrap();
+        assert_eq!(store.check("t@1.0", "sha256:a"), Trust::Trusted);
+        assert_eq!(store.check("t@1.0", "sha256:b"), Trust::Changed);
+        assert_eq!(store.check("u@1.0", "sha256:a"), Trust::Unknown);
+
+        store.trust("t@1.0", "sha256:b").unwrap();
+        let store = TrustStore::load(&path).unwrap();
+        assert_eq!(store.check("t@1.0", "sha256:a"), Trust::Changed);
+    }
+}
@@ -19,6 +19,10 @@ pub enum ApplicationError {
     #[error("Template rendering failed: {reason}")]
     RenderingFailed { reason: String },
 
+    /// External content could not be resolved.
+    #[error("Cannot resolve content '{id}': {reason}")]
+    ContentResolution { id: String, reason: String },
+
     /// Filesystem operation failed.
     #[error("Filesystem error at {path}: {reason}")]
     FilesystemError { path: PathBuf, reason: String },
@@ -42,6 +46,10 @@ pub enum ApplicationError {
     /// Rollback failed (best-effort cleanup failed).
     #[error("Rollback failed for {path}: {reason}")]
     RollbackFailed { path: PathBuf, reason: String },
+
+    /// A post-generation hook could not be started or exited unsuccessfully.
+    #[error("Hook `{command}` failed: {reason}")]
+    HookFailed { command: String, reason: String },
 }
 
 impl ApplicationError {
@@ -53,6 +61,10 @@ impl ApplicationError {
                 "Try: scarff list-templates to see available templates".into(),
                 "Or specify a template explicitly with --template".into(),
             ],
+            Self::ContentResolution { id, .. } => vec![
+                format!("Check the external_id '{}' in template.toml", id),
+                "Known schemes: builtin:, file: (templates root), workspace: (_shared/)".into(),
+            ],
             Self::FilesystemError { path, .. } => vec![
                 format!("Failed to access: {}", path.display()),
                 "Check that you have write permissions".into(),
@@ -71,6 +83,11 @@ impl ApplicationError {
                 "Use --force to overwrite (destructive)".into(),
                 "Choose a different project name".into(),
             ],
+            Self::HookFailed { .. } => vec![
+                "The project was written; run the command yourself once the problem is fixed"
+                    .into(),
+                "Use --no-hooks to skip template hooks".into(),
+            ],
             _ => vec!["Check the error details above".into()],
         }
     }
@@ -78,8 +95,11 @@ impl ApplicationError {
     /// Get error category.
     pub fn category(&self) -> ErrorCategory {
         match self {
-            Self::TemplateResolution { .. } => ErrorCategory::NotFound,
+            Self::TemplateResolution { .. } | Self::ContentResolution { .. } => {
+                ErrorCategory::NotFound
+            }
             Self::FilesystemError { .. } | Self::RollbackFailed { .. } => ErrorCategory::Internal,
+            Self::HookFailed { .. } => ErrorCategory::External,
             Self::StoreLockError => ErrorCategory::Internal,
             Self::AdapterNotConfigured { .. } => ErrorCategory::Configuration,
             Self::ValidationFailed(_) => ErrorCategory::Validation,
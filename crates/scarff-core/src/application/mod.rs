@@ -14,7 +14,11 @@ pub mod services;
 
 // Re-export main services
 pub use services::{
+    Determinism,
+    ScaffoldOptions,
+    ScaffoldOutcome,
     ScaffoldService,
+    TemplateHooks,
     TemplateInfo, // DTO for template metadata
     TemplateService,
 };
@@ -9,10 +9,18 @@
 //!   - `Filesystem`: File operations
 //!   - `TemplateStore`: Template storage/retrieval
 //!   - `TemplateRenderer`: Template rendering
+//!   - `ContentResolver`: Content for `External` files (`builtin:`, `file:`, …)
+//!   - `RunInfoProvider`: Clock, git identity and random id for render variables
+//!   - `PostProcessor`: Line endings, whitespace and the like in rendered files
+//!   - `FileMerger`: Structured merge of files several templates write
+//!   - `HookRunner`: Commands templates run in the written project
 //!
 //! - **Driving (Input) Ports**: Called by external world, implemented by application
 //!   - (Defined in CLI layer, implemented by services)
 
 pub mod output;
 
-pub use output::{Filesystem, TemplateRenderer, TemplateStore};
+pub use output::{
+    ContentResolver, FileMerger, Filesystem, HookRunner, PostProcessor, RunInfoProvider,
+    TemplateRenderer, TemplateStore,
+};
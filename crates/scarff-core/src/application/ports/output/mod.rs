@@ -3,7 +3,10 @@
 //! These traits define what the application needs from external systems.
 //! The `scarff-adapters` crate provides implementations.
 
-use crate::domain::{ProjectStructure, RenderContext, Target, Template, TemplateId};
+use crate::domain::{
+    ContentTemplateId, FileToWrite, FormatOptions, Hook, MergeFormat, ProjectStructure,
+    RenderContext, RunInfo, Target, Template, TemplateId, TemplateSource,
+};
 use crate::error::ScarffResult;
 use std::path::Path;
 
@@ -22,8 +25,8 @@ pub trait Filesystem: Send + Sync {
     /// Create a directory and all parent directories.
     fn create_dir_all(&self, path: &Path) -> ScarffResult<()>;
 
-    /// Write content to a file.
-    fn write_file(&self, path: &Path, content: &str) -> ScarffResult<()>;
+    /// Write content to a file. Text is passed as its UTF-8 bytes.
+    fn write_file(&self, path: &Path, content: &[u8]) -> ScarffResult<()>;
 
     /// Set file permissions.
     fn set_permissions(&self, path: &Path, executable: bool) -> ScarffResult<()>;
@@ -61,9 +64,9 @@ pub trait TemplateStore: Send + Sync {
 /// Port for template rendering.
 ///
 /// Implemented by:
-/// - `scarff_adapters::renderer::SimpleRenderer` (variable substitution)
-/// - `scarff_adapters::renderer::TeraRenderer` (future: Tera templates)
-/// - `scarff_adapters::renderer::HandlebarsRenderer` (future: Handlebars)
+/// - `scarff_adapters::renderer::SimpleRenderer` (built-in syntax)
+/// - `scarff_adapters::renderer::MinijinjaRenderer` (Jinja2, feature `jinja`)
+/// - `scarff_adapters::renderer::EngineRenderer` (dispatches on `Template::engine`)
 pub trait TemplateRenderer: Send + Sync {
     /// Render a template into a project structure.
     ///
@@ -78,4 +81,111 @@ pub trait TemplateRenderer: Send + Sync {
         context: &RenderContext,
         output_root: &Path,
     ) -> ScarffResult<ProjectStructure>;
+
+    /// Fetch the content an [`External`] reference stands for, as
+    /// [`render`](Self::render) would before rendering it.
+    ///
+    /// Used to fingerprint templates whose content lives partly outside them.
+    ///
+    /// [`External`]: crate::domain::TemplateContent::External
+    fn resolve_external(&self, id: &ContentTemplateId) -> ScarffResult<TemplateSource>;
+}
+
+/// Port for resolving [`TemplateContent::External`] references.
+///
+/// Each resolver serves one id scheme (`builtin:mit` → `"builtin"`); renderers
+/// dispatch on [`ContentTemplateId::scheme`]. The returned source is rendered
+/// with the same context as parameterized files.
+///
+/// Implemented by:
+/// - `scarff_adapters::content::BuiltinResolver` (`builtin:`, embedded snippets)
+/// - `scarff_adapters::content::DirectoryResolver` (`file:` and `workspace:`)
+///
+/// [`TemplateContent::External`]: crate::domain::TemplateContent::External
+pub trait ContentResolver: Send + Sync {
+    /// The id scheme this resolver handles, without the trailing `:`.
+    fn scheme(&self) -> &str;
+
+    /// Fetch the content for `id`.
+    ///
+    /// Fails with `ApplicationError::ContentResolution` if the id is unknown.
+    fn resolve(&self, id: &ContentTemplateId) -> ScarffResult<TemplateSource>;
+}
+
+/// Port for tidying up rendered files before they are written.
+///
+/// The service runs every registered processor, in order, on each
+/// [`FileToWrite`] of the rendered structure, with the template's
+/// [`FormatOptions`] for that file's path (`[[format]]` in `template.toml`).
+/// A processor leaves the file alone when its setting is `None`.
+///
+/// Implemented by:
+/// - `scarff_adapters::post_process::{ByteOrderMark, TrimTrailingWhitespace,
+///   Indentation, FinalNewline, LineEndings}`; `builtin_processors()`
+///   returns them in that order
+///
+/// [`FileToWrite`]: crate::domain::FileToWrite
+pub trait PostProcessor: Send + Sync {
+    /// Short name used in logs and errors.
+    fn name(&self) -> &str;
+
+    /// Rewrite `file` according to `options`.
+    fn process(&self, file: &mut FileToWrite, options: &FormatOptions) -> ScarffResult<()>;
+}
+
+/// Port for combining two versions of a file structurally.
+///
+/// Used when an overlay writes a file the project already has and declares
+/// `strategy = "merge"` (or a format name) for it. The service dispatches on
+/// [`format`](Self::format); without a merger for a format, such merges fail
+/// the run.
+///
+/// Implemented by:
+/// - `scarff_adapters::merge::{TomlMerger, JsonMerger, LineSetMerger,
+///   DotenvMerger}`; `builtin_mergers()` returns all four
+pub trait FileMerger: Send + Sync {
+    /// The format this merger handles.
+    fn format(&self) -> MergeFormat;
+
+    /// Merge `overlay` into `base`, keeping `base`'s order and formatting.
+    ///
+    /// Fails with `ApplicationError::RenderingFailed` if either side does not
+    /// parse.
+    fn merge(&self, base: &str, overlay: &str) -> ScarffResult<String>;
+}
+
+/// Port for running post-generation hooks (`[[hooks.post]]` in
+/// `template.toml`) once the project is written.
+///
+/// Runners execute the command directly, without a shell, and report its
+/// output line by line while it runs so long commands (`npm install`) show
+/// progress.
+///
+/// Implemented by:
+/// - `scarff_adapters::hooks::ProcessHookRunner` (child processes)
+pub trait HookRunner: Send + Sync {
+    /// Run `hook` in `dir`, passing each line it writes to stdout or stderr
+    /// to `output` as it arrives.
+    ///
+    /// Fails with `ApplicationError::HookFailed` if the command cannot be
+    /// started or exits unsuccessfully.
+    fn run(&self, hook: &Hook, dir: &Path, output: &mut dyn FnMut(&str)) -> ScarffResult<()>;
+}
+
+/// Port for the run-dependent render variables (`YEAR`, `GENERATED_AT`,
+/// `SCARFF_VERSION`, `GIT_USER_NAME`, `GIT_USER_EMAIL`, `RANDOM_ID`).
+///
+/// Implemented by:
+/// - `scarff_adapters::run_info::SystemRunInfo` (clock, git config files, RNG)
+/// - [`RunInfo`] itself, which always returns a copy of its values; use it to
+///   pin every variable in tests and reproducible builds
+pub trait RunInfoProvider: Send + Sync {
+    /// Gather the values for one scaffold run.
+    fn run_info(&self) -> RunInfo;
+}
+
+impl RunInfoProvider for RunInfo {
+    fn run_info(&self) -> RunInfo {
+        self.clone()
+    }
 }
@@ -6,5 +6,7 @@
 pub mod scaffold_service;
 pub mod template_service;
 
-pub use scaffold_service::{ScaffoldService, TemplateInfo};
+pub use scaffold_service::{
+    Determinism, ScaffoldOptions, ScaffoldOutcome, ScaffoldService, TemplateHooks, TemplateInfo,
+};
 pub use template_service::TemplateService;
@@ -1,21 +1,35 @@
 //! Scaffold Service - main application orchestrator.
 //!
 //! This service coordinates the entire scaffolding workflow:
-//! 1. Resolve template for target
-//! 2. Render template with context
+//! 1. Resolve template (and any `--with` overlays) for target
+//! 2. Render template with context, layering the overlays on top
 //! 3. Write to filesystem
+//! 4. Run the templates' post-generation hooks, when the caller asks
 //!
 //! It implements the driving port (incoming) and uses driven ports (outgoing).
 
-use std::path::Path;
+use sha2::{Digest, Sha256};
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+    time::{Duration, UNIX_EPOCH},
+};
 use tracing::{info, instrument, warn};
 
 use crate::{
     application::{
         ApplicationError,
-        ports::{Filesystem, TemplateRenderer, TemplateStore},
+        ports::{
+            FileMerger, Filesystem, HookRunner, PostProcessor, RunInfoProvider, TemplateRenderer,
+            TemplateStore,
+        },
+    },
+    domain::{
+        ContentTemplateId, DomainValidator as validator, FormatRule, FsEntry, Hook, PackageNames,
+        ProjectStructure, RenderContext, RenderValue, RunInfo, Target, Template, TemplateId,
+        TemplateSource, VariableSpec,
+        entities::{format::format_options_for, variable},
     },
-    domain::{DomainValidator as validator, ProjectStructure, RenderContext, Target, Template},
     error::{ScarffError, ScarffResult},
 };
 
@@ -29,6 +43,173 @@ pub struct TemplateInfo {
     pub kind: String,
     pub architecture: String,
     pub framework: Option<String>,
+    /// Feature name for overlays, `None` for base templates.
+    pub overlay: Option<String>,
+}
+
+/// Settings that make a scaffold run reproducible.
+///
+/// Once the clock or the seed is pinned, the run is deterministic: nothing
+/// else is taken from the machine either. The git identity
+/// (`GIT_USER_NAME`, `GIT_USER_EMAIL`) is then only what is given here,
+/// empty otherwise. With both set, identical inputs and the same scarff
+/// version render byte-identical projects: [`ProjectStructure::digest`] is
+/// then stable across runs and machines.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Determinism {
+    /// Fixed generation time, in seconds since the Unix epoch. Follows the
+    /// [`SOURCE_DATE_EPOCH`](https://reproducible-builds.org/specs/source-date-epoch/)
+    /// convention; drives `YEAR` and `GENERATED_AT`.
+    pub source_date_epoch: Option<u64>,
+
+    /// Seed for random variables (`RANDOM_ID`).
+    pub seed: Option<u64>,
+
+    /// `GIT_USER_NAME` to use instead of the machine's git config.
+    pub git_user_name: Option<String>,
+
+    /// `GIT_USER_EMAIL` to use instead of the machine's git config.
+    pub git_user_email: Option<String>,
+}
+
+impl Determinism {
+    /// Whether the clock or the seed is pinned.
+    pub fn is_enabled(&self) -> bool {
+        self.source_date_epoch.is_some() || self.seed.is_some()
+    }
+
+    /// Pin the parts of `info` this configuration fixes.
+    pub fn apply(&self, mut info: RunInfo) -> RunInfo {
+        if self.is_enabled() {
+            info.git_user_name = None;
+            info.git_user_email = None;
+        }
+        if let Some(name) = &self.git_user_name {
+            info.git_user_name = Some(name.clone());
+        }
+        if let Some(email) = &self.git_user_email {
+            info.git_user_email = Some(email.clone());
+        }
+        if let Some(epoch) = self.source_date_epoch {
+            info.generated_at = UNIX_EPOCH + Duration::from_secs(epoch);
+        }
+        if let Some(seed) = self.seed {
+            info.random_id = format!("{:08x}", splitmix64(seed) >> 32);
+        }
+        info
+    }
+}
+
+/// One step of SplitMix64: a fixed, well-mixed function of the seed, so
+/// nearby seeds still give unrelated ids.
+fn splitmix64(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Per-run options for [`ScaffoldService::scaffold`].
+#[derive(Debug, Clone, Default)]
+pub struct ScaffoldOptions {
+    /// Fixed clock and random seed; defaults to neither.
+    pub determinism: Determinism,
+
+    /// Values supplied by the user (`--var`, `--vars-file`).
+    ///
+    /// Declared template variables are parsed and validated against their
+    /// spec; any other key is passed to the render context as-is.
+    pub variables: HashMap<String, RenderValue>,
+
+    /// npm scope (TypeScript) or module path prefix (Go) for `PACKAGE_NAME`;
+    /// see [`PackageNames::derive`].
+    pub package_scope: Option<String>,
+
+    /// Overlays to layer onto the base template (`--with docker,ci`), by
+    /// name, in the order they are applied.
+    pub overlays: Vec<String>,
+}
+
+impl ScaffoldOptions {
+    /// Options with every setting at its default.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the determinism configuration.
+    pub fn determinism(mut self, determinism: Determinism) -> Self {
+        self.determinism = determinism;
+        self
+    }
+
+    /// Supply one variable value, replacing any earlier value for `name`.
+    pub fn variable(mut self, name: impl Into<String>, value: impl Into<RenderValue>) -> Self {
+        self.variables.insert(name.into(), value.into());
+        self
+    }
+
+    /// Set the npm scope or Go module prefix.
+    pub fn package_scope(mut self, scope: impl Into<String>) -> Self {
+        self.package_scope = Some(scope.into());
+        self
+    }
+
+    /// Add an overlay by name; adding the same name twice has no effect.
+    pub fn overlay(mut self, name: impl Into<String>) -> Self {
+        let name = name.into();
+        if !self.overlays.contains(&name) {
+            self.overlays.push(name);
+        }
+        self
+    }
+
+    /// Supply several variable values; later values win.
+    pub fn variables(mut self, values: impl IntoIterator<Item = (String, RenderValue)>) -> Self {
+        self.variables.extend(values);
+        self
+    }
+}
+
+/// What a scaffold run produced.
+#[derive(Debug, Clone)]
+pub struct ScaffoldOutcome {
+    /// The rendered project.
+    pub structure: ProjectStructure,
+
+    /// Template the project was rendered from.
+    pub template: TemplateId,
+
+    /// Overlays layered on top, in the order they were applied.
+    pub overlays: Vec<TemplateId>,
+
+    /// Every variable value the run used: declared variables after defaults
+    /// and validation, plus any extra user-supplied values.
+    ///
+    /// Feeding these back through [`ScaffoldOptions::variables`] with the same
+    /// template replays the generation.
+    pub answers: BTreeMap<String, RenderValue>,
+
+    /// Post-generation hooks whose `when` holds, by template: the
+    /// template's, then each overlay's. Templates without such hooks are
+    /// left out. Nothing runs them until [`ScaffoldService::run_hook`] is
+    /// called.
+    pub hooks: Vec<TemplateHooks>,
+}
+
+/// The post-generation hooks one template contributes to a run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateHooks {
+    /// Template the hooks come from.
+    pub template: TemplateId,
+
+    /// [`Template::digest`] of that template, external content included,
+    /// combined with the rendered hooks. Ties a decision to trust the hooks
+    /// to exactly what runs: it changes with the template's content and
+    /// with any value that ends up in a command line.
+    pub digest: String,
+
+    /// The hooks, in order, with placeholders filled in.
+    pub hooks: Vec<Hook>,
 }
 
 /// Main scaffolding service.
@@ -38,6 +219,10 @@ pub struct ScaffoldService {
     store: Box<dyn TemplateStore>,
     renderer: Box<dyn TemplateRenderer>,
     filesystem: Box<dyn Filesystem>,
+    run_info: Option<Box<dyn RunInfoProvider>>,
+    post_processors: Vec<Box<dyn PostProcessor>>,
+    mergers: Vec<Box<dyn FileMerger>>,
+    hook_runner: Option<Box<dyn HookRunner>>,
 }
 
 impl ScaffoldService {
@@ -63,12 +248,69 @@ impl ScaffoldService {
             store,
             renderer,
             filesystem,
+            run_info: None,
+            post_processors: Vec::new(),
+            mergers: Vec::new(),
+            hook_runner: None,
         }
     }
 
+    /// Supply the run-dependent render variables (clock, git identity,
+    /// random id).
+    ///
+    /// Without a provider, [`RunInfo::now`] is used: real clock, no git
+    /// identity.
+    pub fn with_run_info_provider(mut self, provider: Box<dyn RunInfoProvider>) -> Self {
+        self.run_info = Some(provider);
+        self
+    }
+
+    /// Add a processor for rendered files (accumulates; run in order).
+    ///
+    /// Without processors, `[[format]]` rules in templates have no effect.
+    pub fn with_post_processor(mut self, processor: Box<dyn PostProcessor>) -> Self {
+        self.post_processors.push(processor);
+        self
+    }
+
+    /// Add several processors for rendered files, run in the given order.
+    pub fn with_post_processors(
+        mut self,
+        processors: impl IntoIterator<Item = Box<dyn PostProcessor>>,
+    ) -> Self {
+        self.post_processors.extend(processors);
+        self
+    }
+
+    /// Add a structured merger (for one format; a later merger for the same
+    /// format is ignored).
+    ///
+    /// Without mergers, overlays can only `replace`, `append` or `keep`
+    /// files the project already has.
+    pub fn with_merger(mut self, merger: Box<dyn FileMerger>) -> Self {
+        self.mergers.push(merger);
+        self
+    }
+
+    /// Add several structured mergers.
+    pub fn with_mergers(mut self, mergers: impl IntoIterator<Item = Box<dyn FileMerger>>) -> Self {
+        self.mergers.extend(mergers);
+        self
+    }
+
+    /// Run post-generation hooks with `runner`.
+    ///
+    /// Without a runner, [`run_hook`](Self::run_hook) fails.
+    pub fn with_hook_runner(mut self, runner: Box<dyn HookRunner>) -> Self {
+        self.hook_runner = Some(runner);
+        self
+    }
+
     /// Scaffold a new project.
     ///
     /// This is the main use case - creates a project from a target configuration.
+    /// Returns the structure that was written, e.g. for
+    /// [`ProjectStructure::digest`], together with the answers used.
     #[instrument(
         skip_all,
         fields(
@@ -82,7 +324,35 @@ impl ScaffoldService {
         target: Target,
         project_name: impl AsRef<str>,
         output_path: impl AsRef<Path>,
-    ) -> ScarffResult<()> {
+        options: &ScaffoldOptions,
+    ) -> ScarffResult<ScaffoldOutcome> {
+        let outcome = self.render(target, project_name, output_path, options)?;
+        self.write(&outcome)?;
+        Ok(outcome)
+    }
+
+    /// Write a project [`render`](Self::render)ed earlier, with rollback on
+    /// failure. `render` then `write` is [`scaffold`](Self::scaffold), split
+    /// so the caller can check the outcome (e.g. approve its hooks) before
+    /// anything touches the filesystem.
+    pub fn write(&self, outcome: &ScaffoldOutcome) -> ScarffResult<()> {
+        self.write_structure(&outcome.structure)?;
+
+        info!("Scaffold completed successfully");
+        Ok(())
+    }
+
+    /// Resolve and render the project without touching the filesystem.
+    ///
+    /// Produces exactly what [`scaffold`](Self::scaffold) would write; used
+    /// for dry runs.
+    pub fn render(
+        &self,
+        target: Target,
+        project_name: impl AsRef<str>,
+        output_path: impl AsRef<Path>,
+        options: &ScaffoldOptions,
+    ) -> ScarffResult<ScaffoldOutcome> {
         info!(
             "Scaffolding {} {} project",
             target.language(),
@@ -95,27 +365,166 @@ impl ScaffoldService {
         // 1. Validate target
         validator::validate_target(&target).map_err(ScarffError::Domain)?;
 
-        // 2. Resolve template
+        // 2. Resolve template and overlays
         let template = self.resolve_template(&target)?;
         info!(template_name = %template.metadata.name, "Template resolved");
+        let overlays = self.resolve_overlays(&target, &options.overlays)?;
+        let layers: Vec<&Template> = std::iter::once(&template).chain(&overlays).collect();
+        let variables = layered_variables(&layers);
+        for overlay in &overlays {
+            overlay
+                .check_conditions(|name| variables.iter().any(|spec| spec.name == name))
+                .map_err(ScarffError::Domain)?;
+        }
 
         // 3. Create render context
-        let context = RenderContext::new(project_name);
-        // TODO: depending on target.language render project_name to fit norm
+        let context = self.render_context(&target, project_name, &variables, options);
+        let mut answers: BTreeMap<_, _> = options
+            .variables
+            .iter()
+            .filter(|(name, _)| !variables.iter().any(|spec| &spec.name == *name))
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
 
-        // 4. Render template
-        let structure = self.renderer.render(&template, &context, output_path)?;
-        // info!(
-        //     files = structure.file_count(),
-        //     directories = structure.directory_count(),
-        //     "Template rendered"
-        // );
+        // Declared variables are checked as a whole before any file is
+        // rendered, so a bad value never leaves a half-written project.
+        let context =
+            variable::bind(&variables, &options.variables, context).map_err(ScarffError::Domain)?;
+        for spec in variables.iter().filter(|s| s.applies(&context)) {
+            if let Some(value) = context.value(&spec.name) {
+                answers.insert(spec.name.clone(), value.clone());
+            }
+        }
 
-        // 5. Write to filesystem
-        self.write_structure(&structure)?;
+        let hooks = applicable_hooks(&layers, &context, &|id| self.renderer.resolve_external(id))?;
+
+        // 4. Render template, then each overlay on top of it
+        let mut structure = self.renderer.render(&template, &context, output_path)?;
+        let merge = |format, _: &Path, base: &str, overlay: &str| {
+            self.mergers
+                .iter()
+                .find(|merger| merger.format() == format)
+                .ok_or_else(|| format!("no {format} merger is configured"))?
+                .merge(base, overlay)
+                .map_err(|e| match e {
+                    ScarffError::Application(ApplicationError::RenderingFailed { reason }) => {
+                        reason
+                    }
+                    other => other.to_string(),
+                })
+        };
+        for overlay in &overlays {
+            let layer = self.renderer.render(overlay, &context, output_path)?;
+            if let Some(spec) = &overlay.overlay {
+                structure
+                    .layer(layer, spec, &merge)
+                    .map_err(ScarffError::Domain)?;
+            }
+            info!(overlay = %overlay.id, "Overlay applied");
+        }
 
-        info!("Scaffold completed successfully");
-        Ok(())
+        // 5. Tidy up rendered files
+        let format: Vec<FormatRule> = layers
+            .iter()
+            .flat_map(|layer| layer.format.iter().cloned())
+            .collect();
+        self.post_process(&format, &mut structure)?;
+
+        Ok(ScaffoldOutcome {
+            structure,
+            template: template.id.clone(),
+            overlays: overlays.iter().map(|o| o.id.clone()).collect(),
+            answers,
+            hooks,
+        })
+    }
+
+    /// Run one of [`ScaffoldOutcome::hooks`] in the project written at
+    /// `root`, passing its output to `output` line by line.
+    ///
+    /// A failing hook leaves the project in place; whether to go on with
+    /// the remaining hooks is up to the caller.
+    ///
+    /// # Errors
+    ///
+    /// [`ApplicationError::AdapterNotConfigured`] without a
+    /// [`HookRunner`], otherwise the runner's errors.
+    pub fn run_hook(
+        &self,
+        root: &Path,
+        hook: &Hook,
+        output: &mut dyn FnMut(&str),
+    ) -> ScarffResult<()> {
+        let runner = self
+            .hook_runner
+            .as_ref()
+            .ok_or(ApplicationError::AdapterNotConfigured { name: "HookRunner" })?;
+        let dir: PathBuf = match &hook.working_dir {
+            Some(dir) => root.join(dir.as_path()),
+            None => root.to_path_buf(),
+        };
+        info!(hook = %hook, dir = %dir.display(), "Running hook");
+        runner.run(hook, &dir, output)
+    }
+
+    /// The template [`scaffold`](Self::scaffold) would use for `target`, e.g.
+    /// to ask for its declared variables up front.
+    pub fn template_for(&self, target: &Target) -> ScarffResult<Template> {
+        validator::validate_target(target).map_err(ScarffError::Domain)?;
+        self.resolve_template(target)
+    }
+
+    /// Every variable [`scaffold`](Self::scaffold) would bind for `target`
+    /// with the overlays in `options`: the template's, then those the
+    /// overlays add. Used to ask for them up front.
+    pub fn variables_for(
+        &self,
+        target: &Target,
+        options: &ScaffoldOptions,
+    ) -> ScarffResult<Vec<VariableSpec>> {
+        let template = self.template_for(target)?;
+        let overlays = self.resolve_overlays(target, &options.overlays)?;
+        let layers: Vec<&Template> = std::iter::once(&template).chain(&overlays).collect();
+        Ok(layered_variables(&layers))
+    }
+
+    /// The context `variables` are bound against: run info (after
+    /// `options.determinism`), target and package-name built-ins, and the
+    /// supplied values that `variables` does not declare.
+    ///
+    /// Declared values are left to the caller, so prompts can offer the same
+    /// defaults [`render`](Self::render) would fall back to.
+    pub fn render_context(
+        &self,
+        target: &Target,
+        project_name: &str,
+        variables: &[VariableSpec],
+        options: &ScaffoldOptions,
+    ) -> RenderContext {
+        let run_info = self
+            .run_info
+            .as_ref()
+            .map_or_else(RunInfo::now, |provider| provider.run_info());
+        let run_info = options.determinism.apply(run_info);
+        // Strict: a typo'd placeholder must fail the run, not ship as `{{DB_URL}}`.
+        // Templates can still opt out with `strict = false`.
+        let mut context = RenderContext::new(project_name)
+            .with_run_info(&run_info)
+            .with_target(target)
+            .with_package_names(&PackageNames::derive(
+                project_name,
+                target.language(),
+                options.package_scope.as_deref(),
+            ))
+            .strict(true);
+
+        // Undeclared values go in first so declared defaults can refer to them.
+        for (name, value) in &options.variables {
+            if !variables.iter().any(|spec| &spec.name == name) {
+                context = context.with_variable(name.clone(), value.clone());
+            }
+        }
+        context
     }
 
     /// List all available templates.
@@ -141,6 +550,7 @@ impl ScaffoldService {
                     .architecture
                     .map_or_else(|| "any".to_string(), |a| a.to_string()),
                 framework: t.matcher.framework.map(|f| f.to_string()),
+                overlay: t.overlay.map(|o| o.name),
             })
             .collect())
     }
@@ -168,6 +578,7 @@ impl ScaffoldService {
                     .architecture
                     .map_or_else(|| "any".to_string(), |a| a.to_string()),
                 framework: t.matcher.framework.map(|f| f.to_string()),
+                overlay: t.overlay.map(|o| o.name),
             })
             .collect())
     }
@@ -178,7 +589,12 @@ impl ScaffoldService {
 
     /// Resolve the best matching template for a target.
     fn resolve_template(&self, target: &Target) -> ScarffResult<Template> {
-        let matches = self.store.find(target)?;
+        let matches: Vec<Template> = self
+            .store
+            .find(target)?
+            .into_iter()
+            .filter(|t| !t.is_overlay())
+            .collect();
 
         if matches.is_empty() {
             return Err(ApplicationError::TemplateResolution {
@@ -187,33 +603,57 @@ impl ScaffoldService {
             .into());
         }
 
-        if matches.len() == 1 {
-            return Ok(matches.into_iter().next().unwrap());
-        }
+        most_specific(matches, "templates")
+    }
 
-        // Multiple matches - select by specificity
-        let max_specificity = matches
-            .iter()
-            .map(|t| t.matcher.specificity())
-            .max()
-            .unwrap();
+    /// Resolve the best matching overlay for each of `names`, in order.
+    fn resolve_overlays(&self, target: &Target, names: &[String]) -> ScarffResult<Vec<Template>> {
+        if names.is_empty() {
+            return Ok(Vec::new());
+        }
+        let candidates = self.store.find(target)?;
+
+        let mut overlays = Vec::with_capacity(names.len());
+        for name in names {
+            let matches: Vec<Template> = candidates
+                .iter()
+                .filter(|t| t.overlay.as_ref().is_some_and(|o| &o.name == name))
+                .cloned()
+                .collect();
+            if matches.is_empty() {
+                return Err(ApplicationError::TemplateResolution {
+                    reason: format!("No '{name}' overlay matches target: {target}"),
+                }
+                .into());
+            }
+            overlays.push(most_specific(matches, &format!("'{name}' overlays"))?);
+        }
+        Ok(overlays)
+    }
 
-        let best_matches: Vec<_> = matches
-            .into_iter()
-            .filter(|t| t.matcher.specificity() == max_specificity)
-            .collect();
+    /// Run the post-processors on every file with its `[[format]]` options.
+    fn post_process(
+        &self,
+        format: &[FormatRule],
+        structure: &mut ProjectStructure,
+    ) -> ScarffResult<()> {
+        if self.post_processors.is_empty() || format.is_empty() {
+            return Ok(());
+        }
 
-        if best_matches.len() > 1 {
-            return Err(ApplicationError::TemplateResolution {
-                reason: format!(
-                    "Ambiguous: {} templates match with equal specificity",
-                    best_matches.len()
-                ),
+        for entry in &mut structure.entries {
+            let FsEntry::File(file) = entry else {
+                continue;
+            };
+            let options = format_options_for(format, &file.path);
+            if options.is_empty() {
+                continue;
+            }
+            for processor in &self.post_processors {
+                processor.process(file, &options)?;
             }
-            .into());
         }
-
-        Ok(best_matches.into_iter().next().unwrap())
+        Ok(())
     }
 
     /// Write project structure to filesystem with rollback on failure.
@@ -260,7 +700,7 @@ impl ScaffoldService {
                         self.filesystem.create_dir_all(parent)?;
                     }
 
-                    self.filesystem.write_file(&path, &file.content)?;
+                    self.filesystem.write_file(&path, file.content.as_bytes())?;
 
                     if file.permissions.executable_flag() {
                         self.filesystem.set_permissions(&path, true)?;
@@ -285,3 +725,264 @@ impl ScaffoldService {
         }
     }
 }
+
+/// The one template of `matches` (non-empty) with the highest specificity.
+fn most_specific(matches: Vec<Template>, what: &str) -> ScarffResult<Template> {
+    if matches.len() == 1 {
+        return Ok(matches.into_iter().next().unwrap());
+    }
+
+    // Multiple matches - select by specificity
+    let max_specificity = matches
+        .iter()
+        .map(|t| t.matcher.specificity())
+        .max()
+        .unwrap();
+
+    let best_matches: Vec<_> = matches
+        .into_iter()
+        .filter(|t| t.matcher.specificity() == max_specificity)
+        .collect();
+
+    if best_matches.len() > 1 {
+        return Err(ApplicationError::TemplateResolution {
+            reason: format!(
+                "Ambiguous: {} {what} match with equal specificity",
+                best_matches.len()
+            ),
+        }
+        .into());
+    }
+
+    Ok(best_matches.into_iter().next().unwrap())
+}
+
+/// Variables declared by `layers`, base first; an overlay redeclaring a name
+/// shares the earlier declaration.
+fn layered_variables(layers: &[&Template]) -> Vec<VariableSpec> {
+    let mut variables: Vec<VariableSpec> = Vec::new();
+    for spec in layers.iter().flat_map(|layer| &layer.variables) {
+        if !variables.iter().any(|v| v.name == spec.name) {
+            variables.push(spec.clone());
+        }
+    }
+    variables
+}
+
+/// The post-generation hooks of each of `layers` that apply to `context`,
+/// rendered with it. `external` fetches external content for the digests.
+fn applicable_hooks(
+    layers: &[&Template],
+    context: &RenderContext,
+    external: &dyn Fn(&ContentTemplateId) -> ScarffResult<TemplateSource>,
+) -> ScarffResult<Vec<TemplateHooks>> {
+    let mut groups = Vec::new();
+    for layer in layers {
+        let hooks = layer
+            .post_hooks
+            .iter()
+            .filter(|hook| hook.applies(context))
+            .map(|hook| hook.render(context))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(ScarffError::Domain)?;
+        if !hooks.is_empty() {
+            let template_digest = layer.digest(external)?;
+            groups.push(TemplateHooks {
+                template: layer.id.clone(),
+                digest: hooks_digest(&template_digest, &hooks),
+                hooks,
+            });
+        }
+    }
+    Ok(groups)
+}
+
+/// `template_digest` extended with `hooks` as they will run, formatted like
+/// it.
+fn hooks_digest(template_digest: &str, hooks: &[Hook]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(template_digest.as_bytes());
+    for hook in hooks {
+        // Display quotes every word, so one line per hook is unambiguous.
+        let dir = hook.working_dir.as_ref().map_or("", |dir| dir.as_str());
+        hasher.update(format!("\n{dir}\0{hook}").as_bytes());
+    }
+    let hex: String = hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    format!("sha256:{hex}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn determinism_pins_clock_and_random_id() {
+        let pinned = Determinism {
+            source_date_epoch: Some(1_700_000_000),
+            seed: Some(42),
+            ..Determinism::default()
+        };
+
+        let first = pinned.apply(RunInfo::now());
+        let second = pinned.apply(RunInfo::now());
+
+        assert_eq!(first, second);
+        assert_eq!(
+            first.generated_at,
+            UNIX_EPOCH + Duration::from_secs(1_700_000_000)
+        );
+        assert_eq!(first.random_id.len(), 8);
+        assert_ne!(
+            first.random_id,
+            Determinism {
+                seed: Some(43),
+                ..pinned.clone()
+            }
+            .apply(RunInfo::now())
+            .random_id
+        );
+    }
+
+    #[test]
+    fn overlay_variables_follow_the_base_and_share_names() {
+        use crate::domain::{
+            Condition, FileSpec, Overlay, TargetMatcher, TemplateContent, TemplateMetadata,
+            TemplateNode, TemplateSource,
+        };
+
+        let template = |name: &str, variables: &[&str]| {
+            let mut builder = Template::builder()
+                .id(TemplateId::new(name, "1.0"))
+                .matcher(TargetMatcher::default())
+                .metadata(TemplateMetadata::new(name))
+                .add_node(TemplateNode::File(FileSpec::new(
+                    "a",
+                    TemplateContent::Literal(TemplateSource::from("")),
+                )));
+            for variable in variables {
+                builder = builder.variable(VariableSpec::new(*variable));
+            }
+            builder.build().unwrap()
+        };
+        let base = template("base", &["db", "port"]);
+        let mut docker = template("docker", &["port", "registry"]);
+        docker.overlay = Some(Overlay::new("docker"));
+
+        let names: Vec<String> = layered_variables(&[&base, &docker])
+            .into_iter()
+            .map(|spec| spec.name)
+            .collect();
+        assert_eq!(names, ["db", "port", "registry"]);
+
+        // An overlay's conditions may use the base's variables, so they are
+        // checked against the layers rather than by `validate`.
+        let with_file = |when: &str| {
+            let mut overlay = docker.clone();
+            overlay.tree.nodes.push(TemplateNode::File(
+                FileSpec::new(
+                    "compose.yml",
+                    TemplateContent::Literal(TemplateSource::from("")),
+                )
+                .when(Condition::parse(when).unwrap()),
+            ));
+            overlay
+        };
+        let declared = |name: &str| names.iter().any(|n| n == name);
+        assert!(with_file("db == 'postgres'").validate().is_ok());
+        assert!(
+            with_file("db == 'postgres'")
+                .check_conditions(declared)
+                .is_ok()
+        );
+        assert!(
+            with_file("KIND == 'cli'")
+                .check_conditions(declared)
+                .is_ok()
+        );
+        let err = with_file("dbs").check_conditions(declared).unwrap_err();
+        assert!(err.to_string().contains("'compose.yml'"), "{err}");
+
+        let mut base = base;
+        base.tree = with_file("dbs").tree;
+        base.overlay = None;
+        assert!(base.validate().is_err());
+    }
+
+    #[test]
+    fn hooks_that_apply_are_rendered_in_layer_order() {
+        use crate::domain::{
+            Condition, FileSpec, TargetMatcher, TemplateContent, TemplateMetadata, TemplateNode,
+            TemplateSource,
+        };
+
+        let template = |name: &str, hooks: Vec<Hook>| {
+            let mut builder = Template::builder()
+                .id(TemplateId::new(name, "1.0"))
+                .matcher(TargetMatcher::default())
+                .metadata(TemplateMetadata::new(name))
+                .add_node(TemplateNode::File(FileSpec::new(
+                    "a",
+                    TemplateContent::Literal(TemplateSource::from("")),
+                )));
+            for hook in hooks {
+                builder = builder.post_hook(hook);
+            }
+            builder.build().unwrap()
+        };
+        let base = template(
+            "base",
+            vec![
+                Hook::new("git").arg("init"),
+                Hook::new("cargo")
+                    .arg("fmt")
+                    .when(Condition::parse("fmt").unwrap()),
+            ],
+        );
+        let docker = template(
+            "docker",
+            vec![Hook::new("docker").args(["build", "-t", "{{PROJECT_NAME}}", "."])],
+        );
+        let context = RenderContext::new("my-app");
+        let no_external = |_: &ContentTemplateId| -> ScarffResult<TemplateSource> {
+            unreachable!("the templates have no external content")
+        };
+
+        let groups = applicable_hooks(&[&base, &docker], &context, &no_external).unwrap();
+        let hooks: Vec<(String, String)> = groups
+            .iter()
+            .flat_map(|group| {
+                group
+                    .hooks
+                    .iter()
+                    .map(|hook| (group.template.name().to_string(), hook.to_string()))
+            })
+            .collect();
+        assert_eq!(
+            hooks,
+            [
+                ("base".to_string(), "git init".to_string()),
+                ("docker".to_string(), "docker build -t my-app .".to_string()),
+            ]
+        );
+        // The digest follows the rendered command lines, not just the
+        // template: another project name is another command to trust.
+        let other =
+            applicable_hooks(&[&docker], &RenderContext::new("other"), &no_external).unwrap();
+        assert_eq!(
+            groups[1].digest,
+            applicable_hooks(&[&docker], &context, &no_external).unwrap()[0].digest
+        );
+        assert_ne!(groups[1].digest, other[0].digest);
+        assert_ne!(docker.digest(no_external).unwrap(), groups[1].digest);
+    }
+
+    #[test]
+    fn default_determinism_changes_nothing() {
+        let info = RunInfo::now();
+        assert_eq!(Determinism::default().apply(info.clone()), info);
+    }
+}
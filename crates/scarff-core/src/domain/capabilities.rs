@@ -44,6 +44,12 @@ pub struct LanguageDef {
 
     /// The kind to infer when the user omits `--kind`.
     pub default_kind: ProjectKind,
+
+    /// Whether this language is offered first when the user names none.
+    ///
+    /// Exactly one entry should be `true`; `assert_registry_integrity`
+    /// enforces this.
+    pub is_default: bool,
 }
 
 /// Single source of truth for language capabilities.
@@ -59,6 +65,7 @@ pub static LANGUAGE_REGISTRY: &[LanguageDef] = &[
             ProjectKind::Worker,
         ],
         default_kind: ProjectKind::Cli,
+        is_default: true,
     },
     LanguageDef {
         language: Language::Python,
@@ -69,6 +76,7 @@ pub static LANGUAGE_REGISTRY: &[LanguageDef] = &[
             ProjectKind::Worker,
         ],
         default_kind: ProjectKind::WebBackend,
+        is_default: false,
     },
     LanguageDef {
         language: Language::TypeScript,
@@ -79,6 +87,7 @@ pub static LANGUAGE_REGISTRY: &[LanguageDef] = &[
             ProjectKind::Worker,
         ],
         default_kind: ProjectKind::WebFrontend,
+        is_default: false,
     },
     LanguageDef {
         language: Language::Go,
@@ -88,6 +97,7 @@ pub static LANGUAGE_REGISTRY: &[LanguageDef] = &[
             ProjectKind::Worker,
         ],
         default_kind: ProjectKind::Cli,
+        is_default: false,
     },
 ];
 
@@ -279,6 +289,16 @@ pub fn find_language(language: Language) -> Option<&'static LanguageDef> {
         .find(|def| def.language == language)
 }
 
+/// The language to offer first when the user names none: the entry where
+/// `is_default = true`.
+pub fn default_language() -> Language {
+    LANGUAGE_REGISTRY
+        .iter()
+        .find(|def| def.is_default)
+        .map(|def| def.language)
+        .unwrap_or(Language::Rust)
+}
+
 /// Check whether a language supports a project kind.
 ///
 /// Replaces `Language::supports()`.
@@ -295,6 +315,28 @@ pub fn framework_supports_kind(framework: Framework, kind: ProjectKind) -> bool
         .unwrap_or(false)
 }
 
+/// Frameworks of `language` that support `kind`, in registry order (so the
+/// `is_default` entry comes first).
+pub fn frameworks_for(language: Language, kind: ProjectKind) -> Vec<Framework> {
+    FRAMEWORK_REGISTRY
+        .iter()
+        .filter(|def| def.framework.language() == language && def.supported_kinds.contains(&kind))
+        .map(|def| def.framework)
+        .collect()
+}
+
+/// Architectures a (language, kind, optional framework) target accepts.
+pub fn architectures_for(
+    language: Language,
+    kind: ProjectKind,
+    framework: Option<Framework>,
+) -> Vec<Architecture> {
+    Architecture::ALL
+        .into_iter()
+        .filter(|arch| arch.is_compatible_with(language, kind, framework))
+        .collect()
+}
+
 /// Infer the project kind from (language, optional framework).
 ///
 /// This is the answer to "given `--language rust --framework axum`, what kind
@@ -446,6 +488,13 @@ pub fn assert_registry_integrity() {
         );
     }
 
+    // Exactly one default language.
+    let default_languages = LANGUAGE_REGISTRY.iter().filter(|d| d.is_default).count();
+    assert!(
+        default_languages == 1,
+        "Expected exactly one default language, found {default_languages}"
+    );
+
     // At most one is_default=true per (language, kind) pair.
     for lang_def in LANGUAGE_REGISTRY {
         for &kind in lang_def.supported_kinds {
@@ -797,4 +846,42 @@ mod tests {
     fn go_fullstack_is_invalid() {
         assert!(validate_language_kind(Language::Go, ProjectKind::Fullstack).is_err());
     }
+
+    // ── frameworks_for / architectures_for ────────────────────────────────────
+
+    #[test]
+    fn every_offered_combination_builds_a_valid_target() {
+        use crate::domain::Target;
+
+        for lang in LANGUAGE_REGISTRY {
+            for &kind in lang.supported_kinds {
+                let mut frameworks: Vec<Option<Framework>> = frameworks_for(lang.language, kind)
+                    .into_iter()
+                    .map(Some)
+                    .collect();
+                if !kind.requires_framework() {
+                    frameworks.push(None);
+                }
+                assert!(!frameworks.is_empty(), "{:?} {kind:?}", lang.language);
+
+                for framework in frameworks {
+                    for arch in architectures_for(lang.language, kind, framework) {
+                        let mut builder = Target::builder()
+                            .language(lang.language)
+                            .kind(kind)
+                            .unwrap()
+                            .architecture(arch);
+                        if let Some(fw) = framework {
+                            builder = builder.framework(fw).unwrap();
+                        }
+                        assert!(
+                            builder.build().is_ok(),
+                            "{:?} {kind:?} {framework:?} {arch:?}",
+                            lang.language
+                        );
+                    }
+                }
+            }
+        }
+    }
 }
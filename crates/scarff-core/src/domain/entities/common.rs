@@ -35,6 +35,36 @@ impl RelativePath {
         }
     }
 
+    /// Strict constructor for paths produced by rendering a template path.
+    ///
+    /// Besides being relative, the path must stay inside the project: no
+    /// `..` segments and no empty segments (`src//mod.rs`, which an empty
+    /// variable produces). Both `/` and `\` separate segments.
+    pub fn parse(path: &str) -> Result<Self, DomainError> {
+        let invalid = |reason: &str| DomainError::InvalidPath {
+            path: path.to_string(),
+            reason: reason.to_string(),
+        };
+
+        if path.is_empty() {
+            return Err(invalid("path is empty"));
+        }
+        if path.starts_with(['/', '\\']) || Path::new(path).is_absolute() {
+            return Err(DomainError::AbsolutePathNotAllowed {
+                path: path.to_string(),
+            });
+        }
+        for segment in path.split(['/', '\\']) {
+            match segment {
+                "" => return Err(invalid("empty path segment")),
+                ".." => return Err(invalid("'..' leaves the project directory")),
+                _ => {}
+            }
+        }
+
+        Ok(Self(PathBuf::from(path)))
+    }
+
     /// Join a segment, maintaining relative invariant.
     pub fn join(&self, segment: impl AsRef<Path>) -> Result<Self, DomainError> {
         let segment = segment.as_ref();
@@ -0,0 +1,406 @@
+//! `when = "..."` conditions in `template.toml`.
+//!
+//! A small boolean expression language over render variables:
+//!
+//! | Syntax | Meaning |
+//! |--------|---------|
+//! | `db` | `db` is truthy (same rules as `{{#if db}}`) |
+//! | `db == 'postgres'`, `db != 'none'` | String comparison |
+//! | `'auth' in features` | Membership in a list (or comma-separated string) |
+//! | `!a`, `not a` | Negation |
+//! | `a && b`, `a and b` | Conjunction |
+//! | `a \|\| b`, `a or b` | Disjunction |
+//! | `( … )` | Grouping |
+//!
+//! Literals are quoted with `'` or `"`; bare numbers and `true`/`false` are
+//! literals too. Dotted names (`owner.name`) descend into map values. An
+//! undefined variable compares as the empty string, so it is falsy.
+
+use std::{fmt, str::FromStr};
+
+use super::template::{RenderContext, RenderValue};
+use crate::domain::{error::DomainError, render::is_truthy};
+
+/// A parsed `when` expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Condition {
+    source: String,
+    expr: Expr,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Expr {
+    Operand(Operand),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Compare(Operand, CompareOp, Operand),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Operand {
+    Variable(String),
+    Literal(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CompareOp {
+    Eq,
+    Ne,
+    In,
+}
+
+impl Condition {
+    /// Parse `source`.
+    ///
+    /// # Errors
+    ///
+    /// [`DomainError::InvalidTemplate`] describing the first syntax error.
+    pub fn parse(source: &str) -> Result<Self, DomainError> {
+        let invalid = |reason: String| {
+            DomainError::InvalidTemplate(format!("invalid condition '{source}': {reason}"))
+        };
+
+        let tokens = tokenize(source).map_err(invalid)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let expr = parser.or().map_err(invalid)?;
+        if let Some(token) = parser.peek() {
+            return Err(invalid(format!("unexpected {token}")));
+        }
+
+        Ok(Self {
+            source: source.trim().to_string(),
+            expr,
+        })
+    }
+
+    /// The expression as written.
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// Top-level names of the variables the expression reads (`owner` for
+    /// `owner.name`), in order of first use.
+    pub fn variables(&self) -> Vec<&str> {
+        let mut names = Vec::new();
+        self.expr.collect_variables(&mut names);
+        names
+    }
+
+    /// Evaluate against the variables of `context`.
+    pub fn evaluate(&self, context: &RenderContext) -> bool {
+        self.expr.evaluate(context)
+    }
+}
+
+impl FromStr for Condition {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+// ── Evaluation ────────────────────────────────────────────────────────────────
+
+impl Expr {
+    fn evaluate(&self, context: &RenderContext) -> bool {
+        match self {
+            Self::Operand(operand) => operand.resolve(context).is_some_and(|v| is_truthy(&v)),
+            Self::Not(inner) => !inner.evaluate(context),
+            Self::And(lhs, rhs) => lhs.evaluate(context) && rhs.evaluate(context),
+            Self::Or(lhs, rhs) => lhs.evaluate(context) || rhs.evaluate(context),
+            Self::Compare(lhs, op, rhs) => {
+                let lhs = lhs
+                    .resolve(context)
+                    .map(|v| v.to_string())
+                    .unwrap_or_default();
+                let rhs = rhs.resolve(context);
+                match op {
+                    CompareOp::Eq => rhs.map(|v| v.to_string()).unwrap_or_default() == lhs,
+                    CompareOp::Ne => rhs.map(|v| v.to_string()).unwrap_or_default() != lhs,
+                    CompareOp::In => rhs.is_some_and(|v| {
+                        v.iter_items()
+                            .iter()
+                            .any(|(_, item)| item.to_string() == lhs)
+                    }),
+                }
+            }
+        }
+    }
+
+    fn collect_variables<'a>(&'a self, names: &mut Vec<&'a str>) {
+        match self {
+            Self::Operand(operand) => operand.collect_variable(names),
+            Self::Not(inner) => inner.collect_variables(names),
+            Self::And(lhs, rhs) | Self::Or(lhs, rhs) => {
+                lhs.collect_variables(names);
+                rhs.collect_variables(names);
+            }
+            Self::Compare(lhs, _, rhs) => {
+                lhs.collect_variable(names);
+                rhs.collect_variable(names);
+            }
+        }
+    }
+}
+
+impl Operand {
+    fn resolve(&self, context: &RenderContext) -> Option<RenderValue> {
+        match self {
+            Self::Literal(text) => Some(RenderValue::String(text.clone())),
+            Self::Variable(path) => {
+                let mut segments = path.split('.');
+                let mut value = context.value(segments.next()?)?;
+                for segment in segments {
+                    match value {
+                        RenderValue::Map(entries) => value = entries.get(segment)?,
+                        _ => return None,
+                    }
+                }
+                Some(value.clone())
+            }
+        }
+    }
+
+    fn collect_variable<'a>(&'a self, names: &mut Vec<&'a str>) {
+        if let Self::Variable(path) = self {
+            let name = path.split('.').next().unwrap_or(path);
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+}
+
+// ── Parsing ───────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Ident(String),
+    Literal(String),
+    Eq,
+    Ne,
+    In,
+    Not,
+    And,
+    Or,
+    Open,
+    Close,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ident(name) => write!(f, "'{name}'"),
+            Self::Literal(text) => write!(f, "literal '{text}'"),
+            Self::Eq => f.write_str("'=='"),
+            Self::Ne => f.write_str("'!='"),
+            Self::In => f.write_str("'in'"),
+            Self::Not => f.write_str("'!'"),
+            Self::And => f.write_str("'&&'"),
+            Self::Or => f.write_str("'||'"),
+            Self::Open => f.write_str("'('"),
+            Self::Close => f.write_str("')'"),
+        }
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::Open,
+            ')' => Token::Close,
+            '=' | '!' | '&' | '|' => {
+                let next = chars.peek().map(|&(_, n)| n);
+                match (c, next) {
+                    ('=', Some('=')) => {
+                        chars.next();
+                        Token::Eq
+                    }
+                    ('!', Some('=')) => {
+                        chars.next();
+                        Token::Ne
+                    }
+                    ('!', _) => Token::Not,
+                    ('&', Some('&')) => {
+                        chars.next();
+                        Token::And
+                    }
+                    ('|', Some('|')) => {
+                        chars.next();
+                        Token::Or
+                    }
+                    _ => return Err(format!("unexpected '{c}' at offset {start}")),
+                }
+            }
+            '\'' | '"' => {
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, q)) if q == c => break,
+                        Some((_, ch)) => text.push(ch),
+                        None => return Err(format!("unterminated string at offset {start}")),
+                    }
+                }
+                Token::Literal(text)
+            }
+            c if is_word_char(c) => {
+                let mut word = c.to_string();
+                while let Some(&(_, ch)) = chars.peek() {
+                    if !is_word_char(ch) {
+                        break;
+                    }
+                    word.push(ch);
+                    chars.next();
+                }
+                match word.as_str() {
+                    "and" => Token::And,
+                    "or" => Token::Or,
+                    "not" => Token::Not,
+                    "in" => Token::In,
+                    "true" | "false" => Token::Literal(word),
+                    _ if word.starts_with(|ch: char| ch.is_ascii_digit() || ch == '-') => {
+                        Token::Literal(word)
+                    }
+                    _ => Token::Ident(word),
+                }
+            }
+            c => return Err(format!("unexpected '{c}' at offset {start}")),
+        };
+        tokens.push(token);
+    }
+
+    Ok(tokens)
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-')
+}
+
+/// Recursive descent; precedence from loosest: `or`, `and`, `not`, comparison.
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.peek() == Some(token) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn or(&mut self) -> Result<Expr, String> {
+        let mut expr = self.and()?;
+        while self.eat(&Token::Or) {
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr, String> {
+        let mut expr = self.unary()?;
+        while self.eat(&Token::And) {
+            expr = Expr::And(Box::new(expr), Box::new(self.unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        if self.eat(&Token::Not) {
+            return Ok(Expr::Not(Box::new(self.unary()?)));
+        }
+        if self.eat(&Token::Open) {
+            let expr = self.or()?;
+            if !self.eat(&Token::Close) {
+                return Err("missing ')'".into());
+            }
+            return Ok(expr);
+        }
+
+        let lhs = self.operand()?;
+        let op = match self.peek() {
+            Some(Token::Eq) => CompareOp::Eq,
+            Some(Token::Ne) => CompareOp::Ne,
+            Some(Token::In) => CompareOp::In,
+            _ => return Ok(Expr::Operand(lhs)),
+        };
+        self.pos += 1;
+        Ok(Expr::Compare(lhs, op, self.operand()?))
+    }
+
+    fn operand(&mut self) -> Result<Operand, String> {
+        match self.next() {
+            Some(Token::Ident(name)) => Ok(Operand::Variable(name)),
+            Some(Token::Literal(text)) => Ok(Operand::Literal(text)),
+            Some(other) => Err(format!("expected a name or value, found {other}")),
+            None => Err("unexpected end of expression".into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(source: &str, ctx: &RenderContext) -> bool {
+        Condition::parse(source).unwrap().evaluate(ctx)
+    }
+
+    #[test]
+    fn evaluates_comparisons_and_connectives() {
+        let ctx = RenderContext::new("app")
+            .with_variable("db", "postgres")
+            .with_variable("docker", "false")
+            .with_variable("features", vec!["auth", "metrics"]);
+
+        assert!(eval("db != 'none'", &ctx));
+        assert!(eval("db == \"postgres\" && !docker", &ctx));
+        assert!(eval("docker or 'auth' in features", &ctx));
+        assert!(eval(
+            "not (db == 'postgres' and 'billing' in features)",
+            &ctx
+        ));
+        assert!(!eval("undefined_var", &ctx));
+        assert!(eval("undefined_var == ''", &ctx));
+        assert!(eval("docker == false", &ctx));
+    }
+
+    #[test]
+    fn reports_syntax_errors() {
+        for source in ["", "db ==", "(db", "db = 'x'", "'open", "db db"] {
+            let err = Condition::parse(source).unwrap_err().to_string();
+            assert!(err.contains("invalid condition"), "{source}: {err}");
+        }
+    }
+
+    #[test]
+    fn lists_referenced_variables() {
+        let condition = Condition::parse("db != 'none' && (owner.name or db)").unwrap();
+        assert_eq!(condition.variables(), ["db", "owner"]);
+    }
+}
@@ -0,0 +1,284 @@
+//! `[[format]]` rules in `template.toml`: how rendered files are tidied up
+//! before they are written.
+//!
+//! Each rule pairs a [`Glob`] with [`FormatOptions`]. Every rule whose glob
+//! matches a file applies, later rules overriding the settings of earlier
+//! ones, so a catch-all `**` rule can come first and exceptions after it:
+//!
+//! ```toml
+//! [[format]]
+//! glob = "**"
+//! line_endings = "lf"
+//! trim_trailing_whitespace = true
+//! final_newline = true
+//!
+//! [[format]]
+//! glob = "Makefile"
+//! indent = "tabs"
+//! ```
+//!
+//! The settings are carried out by `PostProcessor` implementations.
+
+use std::{fmt, path::Path, str::FromStr};
+
+use regex::Regex;
+
+use crate::domain::error::DomainError;
+
+/// A path pattern.
+///
+/// | Syntax | Matches |
+/// |--------|---------|
+/// | `*` | Any characters except `/` |
+/// | `?` | One character except `/` |
+/// | `**` | Any number of whole path segments |
+/// | `{a,b}` | `a` or `b` |
+///
+/// A pattern without `/` matches file names at any depth (`*.rs` matches
+/// `src/main.rs`); one with `/` matches the whole relative path.
+#[derive(Debug, Clone)]
+pub struct Glob {
+    pattern: String,
+    regex: Regex,
+}
+
+impl Glob {
+    /// Compile `pattern`.
+    ///
+    /// # Errors
+    ///
+    /// [`DomainError::InvalidTemplate`] for an empty pattern or an unclosed
+    /// `{`.
+    pub fn new(pattern: &str) -> Result<Self, DomainError> {
+        let invalid = |reason: &str| {
+            DomainError::InvalidTemplate(format!("invalid glob '{pattern}': {reason}"))
+        };
+        if pattern.is_empty() {
+            return Err(invalid("pattern is empty"));
+        }
+
+        let mut regex = String::from("^");
+        if !pattern.contains('/') {
+            regex.push_str("(?:.*/)?");
+        }
+
+        let mut chars = pattern.chars().peekable();
+        let mut in_braces = false;
+        while let Some(c) = chars.next() {
+            match c {
+                '*' if chars.peek() == Some(&'*') => {
+                    chars.next();
+                    if chars.peek() == Some(&'/') {
+                        chars.next();
+                        regex.push_str("(?:.*/)?");
+                    } else {
+                        regex.push_str(".*");
+                    }
+                }
+                '*' => regex.push_str("[^/]*"),
+                '?' => regex.push_str("[^/]"),
+                '{' if !in_braces => {
+                    in_braces = true;
+                    regex.push_str("(?:");
+                }
+                ',' if in_braces => regex.push('|'),
+                '}' if in_braces => {
+                    in_braces = false;
+                    regex.push(')');
+                }
+                c => regex.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+            }
+        }
+        if in_braces {
+            return Err(invalid("unclosed '{'"));
+        }
+        regex.push('$');
+
+        let regex = Regex::new(&regex).map_err(|e| invalid(&e.to_string()))?;
+        Ok(Self {
+            pattern: pattern.to_string(),
+            regex,
+        })
+    }
+
+    /// Whether `path` (relative, `/`-separated) matches.
+    pub fn matches(&self, path: &Path) -> bool {
+        self.regex
+            .is_match(&path.to_string_lossy().replace('\\', "/"))
+    }
+
+    /// The pattern as written.
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+}
+
+impl PartialEq for Glob {
+    fn eq(&self, other: &Self) -> bool {
+        self.pattern == other.pattern
+    }
+}
+
+impl Eq for Glob {}
+
+impl fmt::Display for Glob {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.pattern)
+    }
+}
+
+/// Line terminator written to text files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineEnding {
+    Lf,
+    CrLf,
+}
+
+impl LineEnding {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Lf => "\n",
+            Self::CrLf => "\r\n",
+        }
+    }
+}
+
+impl FromStr for LineEnding {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "lf" => Ok(Self::Lf),
+            "crlf" => Ok(Self::CrLf),
+            _ => Err(DomainError::InvalidTemplate(format!(
+                "unknown line ending '{s}'; expected lf or crlf"
+            ))),
+        }
+    }
+}
+
+/// Leading indentation style, with the number of columns one tab stands for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Indent {
+    /// Leading tabs become `width` spaces each.
+    Spaces(usize),
+    /// Leading runs of `width` spaces become tabs.
+    Tabs(usize),
+}
+
+/// Settings for one file; `None` leaves that aspect untouched.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FormatOptions {
+    /// Convert every line break to this terminator.
+    pub line_ending: Option<LineEnding>,
+
+    /// Strip spaces and tabs at the end of each line.
+    pub trim_trailing_whitespace: Option<bool>,
+
+    /// `true`: end with exactly one line break, dropping trailing blank
+    /// lines. `false`: end without one.
+    pub final_newline: Option<bool>,
+
+    /// Rewrite leading indentation.
+    pub indent: Option<Indent>,
+
+    /// `true`: start with a UTF-8 byte order mark. `false`: strip it.
+    pub bom: Option<bool>,
+}
+
+impl FormatOptions {
+    /// Whether no setting is made.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Take every setting `other` makes, keeping the rest.
+    pub fn overlay(&mut self, other: &Self) {
+        self.line_ending = other.line_ending.or(self.line_ending);
+        self.trim_trailing_whitespace = other
+            .trim_trailing_whitespace
+            .or(self.trim_trailing_whitespace);
+        self.final_newline = other.final_newline.or(self.final_newline);
+        self.indent = other.indent.or(self.indent);
+        self.bom = other.bom.or(self.bom);
+    }
+}
+
+/// One `[[format]]` entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatRule {
+    pub glob: Glob,
+    pub options: FormatOptions,
+}
+
+impl FormatRule {
+    pub fn new(glob: Glob, options: FormatOptions) -> Self {
+        Self { glob, options }
+    }
+}
+
+/// The combined settings of every rule in `rules` matching `path`.
+pub fn format_options_for(rules: &[FormatRule], path: &Path) -> FormatOptions {
+    let mut options = FormatOptions::default();
+    for rule in rules.iter().filter(|rule| rule.glob.matches(path)) {
+        options.overlay(&rule.options);
+    }
+    options
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(pattern: &str, path: &str) -> bool {
+        Glob::new(pattern).unwrap().matches(Path::new(path))
+    }
+
+    #[test]
+    fn globs_match_names_anywhere_and_paths_from_the_root() {
+        assert!(matches("*.rs", "src/bin/main.rs"));
+        assert!(matches("Makefile", "Makefile"));
+        assert!(!matches("*.rs", "src/main.rs.bak"));
+        assert!(matches("src/*.rs", "src/lib.rs"));
+        assert!(!matches("src/*.rs", "src/bin/main.rs"));
+        assert!(matches("src/**/*.rs", "src/lib.rs"));
+        assert!(matches("src/**/*.rs", "src/a/b/lib.rs"));
+        assert!(matches("docs/**", "docs/a/b.md"));
+        assert!(matches("**", ".github/workflows/ci.yml"));
+        assert!(matches("*.{yml,yaml}", "ci.yaml"));
+        assert!(matches("?.txt", "a.txt"));
+        assert!(!matches("?.txt", "ab.txt"));
+
+        assert!(Glob::new("").is_err());
+        assert!(Glob::new("*.{a,b").is_err());
+    }
+
+    #[test]
+    fn later_rules_override_earlier_ones() {
+        let rules = [
+            FormatRule::new(
+                Glob::new("**").unwrap(),
+                FormatOptions {
+                    line_ending: Some(LineEnding::Lf),
+                    indent: Some(Indent::Spaces(4)),
+                    ..FormatOptions::default()
+                },
+            ),
+            FormatRule::new(
+                Glob::new("Makefile").unwrap(),
+                FormatOptions {
+                    indent: Some(Indent::Tabs(4)),
+                    ..FormatOptions::default()
+                },
+            ),
+        ];
+
+        let make = format_options_for(&rules, Path::new("Makefile"));
+        assert_eq!(make.indent, Some(Indent::Tabs(4)));
+        assert_eq!(make.line_ending, Some(LineEnding::Lf));
+
+        let main = format_options_for(&rules, Path::new("src/main.rs"));
+        assert_eq!(main.indent, Some(Indent::Spaces(4)));
+        assert!(format_options_for(&[], Path::new("x")).is_empty());
+    }
+}
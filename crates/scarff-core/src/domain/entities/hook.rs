@@ -0,0 +1,164 @@
+//! Post-generation hooks: commands run in the project once it is written.
+//!
+//! ```toml
+//! [[hooks.post]]
+//! command = "git"
+//! args    = ["init", "--quiet"]
+//!
+//! [[hooks.post]]
+//! command     = "npm"
+//! args        = ["install"]
+//! working_dir = "web"              # relative to the project root
+//! when        = "with_frontend"
+//! ```
+//!
+//! The command, arguments and working directory may use placeholders; the
+//! `when` condition sees the same variables as file conditions. Hooks are
+//! run, in order, by a `HookRunner`, never through a shell.
+
+use std::fmt;
+
+use super::{common::RelativePath, condition::Condition, template::RenderContext};
+use crate::domain::error::DomainError;
+
+/// One `[[hooks.post]]` entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hook {
+    /// Executable, looked up on `PATH` unless it contains a path separator.
+    pub command: String,
+
+    /// Arguments, passed as-is (no shell quoting or expansion).
+    pub args: Vec<String>,
+
+    /// Directory to run in, relative to the project root; the root itself
+    /// when `None`.
+    pub working_dir: Option<RelativePath>,
+
+    /// Run only when this holds.
+    pub when: Option<Condition>,
+}
+
+impl Hook {
+    pub fn new(command: impl Into<String>) -> Self {
+        Self {
+            command: command.into(),
+            args: Vec::new(),
+            working_dir: None,
+            when: None,
+        }
+    }
+
+    /// Add an argument (accumulates).
+    pub fn arg(mut self, arg: impl Into<String>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    /// Add several arguments.
+    pub fn args(mut self, args: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    /// Run in `dir` instead of the project root.
+    pub fn working_dir(mut self, dir: RelativePath) -> Self {
+        self.working_dir = Some(dir);
+        self
+    }
+
+    /// Run only when `condition` holds.
+    pub fn when(mut self, condition: Condition) -> Self {
+        self.when = Some(condition);
+        self
+    }
+
+    /// Whether the hook runs for `context`.
+    pub fn applies(&self, context: &RenderContext) -> bool {
+        self.when.as_ref().is_none_or(|c| c.evaluate(context))
+    }
+
+    /// The hook with placeholders in its command, arguments and working
+    /// directory filled in from `context`.
+    ///
+    /// # Errors
+    ///
+    /// Those of [`RenderContext::try_render`] and
+    /// [`RenderContext::render_path`], and [`DomainError::InvalidTemplate`]
+    /// if the command renders empty.
+    pub fn render(&self, context: &RenderContext) -> Result<Self, DomainError> {
+        let command = context.try_render(&self.command)?;
+        if command.trim().is_empty() {
+            return Err(DomainError::InvalidTemplate(format!(
+                "hook '{}' has an empty command",
+                self.command
+            )));
+        }
+        Ok(Self {
+            command,
+            args: self
+                .args
+                .iter()
+                .map(|arg| context.try_render(arg))
+                .collect::<Result<_, _>>()?,
+            working_dir: self
+                .working_dir
+                .as_ref()
+                .map(|dir| context.render_path(dir))
+                .transpose()?,
+            when: self.when.clone(),
+        })
+    }
+}
+
+/// The command line, quoted for a POSIX shell where needed, e.g.
+/// `git commit -m 'Initial commit'`.
+impl fmt::Display for Hook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&quote(&self.command))?;
+        for arg in &self.args {
+            write!(f, " {}", quote(arg))?;
+        }
+        Ok(())
+    }
+}
+
+fn quote(word: &str) -> String {
+    let plain = !word.is_empty()
+        && word
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./=:@,+%".contains(c));
+    if plain {
+        word.to_string()
+    } else {
+        format!("'{}'", word.replace('\'', r"'\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hooks_render_placeholders_and_display_quoted() {
+        let context = RenderContext::new("my-app").with_variable("frontend", "true");
+        let hook = Hook::new("git")
+            .args(["commit", "-m", "Initial commit of {{PROJECT_NAME}}"])
+            .working_dir(RelativePath::parse("{{PROJECT_NAME_SNAKE}}").unwrap())
+            .when(Condition::parse("frontend").unwrap());
+
+        assert!(hook.applies(&context));
+        assert!(!hook.applies(&RenderContext::new("my-app")));
+
+        let rendered = hook.render(&context).unwrap();
+        assert_eq!(
+            rendered.to_string(),
+            "git commit -m 'Initial commit of my-app'"
+        );
+        assert_eq!(
+            rendered.working_dir.as_ref().map(RelativePath::as_str),
+            Some("my_app")
+        );
+        assert_eq!(Hook::new("echo").arg("it's").to_string(), r"echo 'it'\''s'");
+        assert!(Hook::new(" ").render(&context).is_err());
+    }
+}
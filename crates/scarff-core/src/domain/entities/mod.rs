@@ -1,7 +1,13 @@
 pub mod common;
+pub mod condition;
+pub mod format;
+pub mod hook;
+pub mod overlay;
+pub mod package_names;
 pub mod project_structure;
 pub mod target;
 pub mod template;
+pub mod variable;
 
 pub use crate::domain::DomainError;
 pub use project_structure::ProjectStructure;
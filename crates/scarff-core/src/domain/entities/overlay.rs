@@ -0,0 +1,251 @@
+//! Overlays: templates layered on top of the base template.
+//!
+//! A run resolves exactly one base template for its target. Overlays add a
+//! feature to whatever base was chosen (`scarff new app --with docker,ci`):
+//! a `Dockerfile`, a CI workflow, OpenTelemetry wiring, pre-commit hooks.
+//! They are ordinary templates, matched through the same `TargetMatcher`
+//! (so a `docker` overlay can exist once per language), that also carry an
+//! [`Overlay`] naming the feature.
+//!
+//! Each overlay is rendered on its own and its output layered onto the
+//! project with [`ProjectStructure::layer`]. A file the project already has
+//! is a conflict unless the overlay declares a [`MergeStrategy`] for it:
+//!
+//! ```toml
+//! [overlay]
+//! name = "docker"
+//!
+//! [[overlay.merge]]
+//! glob     = ".gitignore"
+//! strategy = "append"      # replace | append | keep
+//!
+//! [[overlay.merge]]
+//! glob     = "{Cargo.toml,package.json,.env.example}"
+//! strategy = "merge"       # structured, by file type; or toml | json | lines | dotenv
+//! ```
+//!
+//! Structured merges are carried out by `FileMerger` implementations, one
+//! per [`MergeFormat`].
+//!
+//! [`ProjectStructure::layer`]: super::project_structure::ProjectStructure::layer
+
+use std::{fmt, path::Path, str::FromStr};
+
+use super::format::Glob;
+use crate::domain::error::DomainError;
+
+/// What happens when an overlay writes a file the project already has.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeStrategy {
+    /// The overlay's file takes the place of the existing one.
+    Replace,
+    /// The overlay's text is added after the existing text, on a new line.
+    Append,
+    /// The existing file stays; the overlay's is dropped.
+    Keep,
+    /// The two files are combined structurally; `None` picks the format
+    /// from the file name with [`MergeFormat::detect`].
+    Merge(Option<MergeFormat>),
+}
+
+impl MergeStrategy {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Replace => "replace",
+            Self::Append => "append",
+            Self::Keep => "keep",
+            Self::Merge(None) => "merge",
+            Self::Merge(Some(format)) => format.as_str(),
+        }
+    }
+}
+
+impl fmt::Display for MergeStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for MergeStrategy {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "replace" => Ok(Self::Replace),
+            "append" => Ok(Self::Append),
+            "keep" => Ok(Self::Keep),
+            "merge" => Ok(Self::Merge(None)),
+            other => match other.parse::<MergeFormat>() {
+                Ok(format) => Ok(Self::Merge(Some(format))),
+                Err(_) => Err(DomainError::InvalidTemplate(format!(
+                    "unknown merge strategy '{s}'; expected replace, append, keep, merge, \
+                     toml, json, lines or dotenv"
+                ))),
+            },
+        }
+    }
+}
+
+/// File formats that can be merged structurally.
+///
+/// | Format | Merge |
+/// |--------|-------|
+/// | `Toml` | Deep merge of tables; arrays gain missing items; other values are replaced |
+/// | `Json` | The same, for objects and arrays |
+/// | `Lines` | Union of lines, as for `.gitignore` |
+/// | `Dotenv` | Union of `KEY=value` entries; a repeated key takes the new value |
+///
+/// In every case the existing file keeps its order and formatting, and
+/// what the overlay adds comes after it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MergeFormat {
+    Toml,
+    Json,
+    Lines,
+    Dotenv,
+}
+
+impl MergeFormat {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Toml => "toml",
+            Self::Json => "json",
+            Self::Lines => "lines",
+            Self::Dotenv => "dotenv",
+        }
+    }
+
+    /// The format of `path` judged by its name: `*.toml`, `*.json`,
+    /// `*ignore` (`.gitignore`, `.dockerignore`, …) and `.env` / `.env.*` /
+    /// `*.env`.
+    pub fn detect(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?.to_ascii_lowercase();
+        if name.ends_with(".toml") {
+            Some(Self::Toml)
+        } else if name.ends_with(".json") {
+            Some(Self::Json)
+        } else if name.ends_with("ignore") {
+            Some(Self::Lines)
+        } else if name == ".env" || name.starts_with(".env.") || name.ends_with(".env") {
+            Some(Self::Dotenv)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for MergeFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for MergeFormat {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "toml" => Ok(Self::Toml),
+            "json" => Ok(Self::Json),
+            "lines" => Ok(Self::Lines),
+            "dotenv" => Ok(Self::Dotenv),
+            _ => Err(DomainError::InvalidTemplate(format!(
+                "unknown merge format '{s}'; expected toml, json, lines or dotenv"
+            ))),
+        }
+    }
+}
+
+/// One `[[overlay.merge]]` entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeRule {
+    pub glob: Glob,
+    pub strategy: MergeStrategy,
+}
+
+impl MergeRule {
+    pub fn new(glob: Glob, strategy: MergeStrategy) -> Self {
+        Self { glob, strategy }
+    }
+}
+
+/// Marks a template as an overlay (`[overlay]` in `template.toml`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Overlay {
+    /// Feature name selected with `--with`, e.g. `docker`.
+    pub name: String,
+
+    /// How files the project already has are merged, by glob.
+    pub merge: Vec<MergeRule>,
+}
+
+impl Overlay {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            merge: Vec::new(),
+        }
+    }
+
+    /// Add a merge rule (accumulates; later rules win).
+    pub fn with_merge_rule(mut self, rule: MergeRule) -> Self {
+        self.merge.push(rule);
+        self
+    }
+
+    /// The strategy of the last rule matching `path`, if any.
+    pub fn strategy_for(&self, path: &Path) -> Option<MergeStrategy> {
+        self.merge
+            .iter()
+            .rev()
+            .find(|rule| rule.glob.matches(path))
+            .map(|rule| rule.strategy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn later_merge_rules_win() {
+        let overlay = Overlay::new("ci")
+            .with_merge_rule(MergeRule::new(
+                Glob::new("**").unwrap(),
+                MergeStrategy::Keep,
+            ))
+            .with_merge_rule(MergeRule::new(
+                Glob::new(".gitignore").unwrap(),
+                MergeStrategy::Append,
+            ));
+
+        assert_eq!(
+            overlay.strategy_for(Path::new(".gitignore")),
+            Some(MergeStrategy::Append)
+        );
+        assert_eq!(
+            overlay.strategy_for(Path::new("README.md")),
+            Some(MergeStrategy::Keep)
+        );
+        assert_eq!(Overlay::new("ci").strategy_for(Path::new("x")), None);
+        assert_eq!("merge".parse(), Ok(MergeStrategy::Merge(None)));
+        assert_eq!(
+            "JSON".parse(),
+            Ok(MergeStrategy::Merge(Some(MergeFormat::Json)))
+        );
+        assert!("union".parse::<MergeStrategy>().is_err());
+    }
+
+    #[test]
+    fn merge_format_is_detected_from_the_file_name() {
+        let detect = |path: &str| MergeFormat::detect(Path::new(path));
+
+        assert_eq!(detect("Cargo.toml"), Some(MergeFormat::Toml));
+        assert_eq!(detect("web/package.json"), Some(MergeFormat::Json));
+        assert_eq!(detect(".gitignore"), Some(MergeFormat::Lines));
+        assert_eq!(detect(".dockerignore"), Some(MergeFormat::Lines));
+        assert_eq!(detect(".env"), Some(MergeFormat::Dotenv));
+        assert_eq!(detect(".env.example"), Some(MergeFormat::Dotenv));
+        assert_eq!(detect("README.md"), None);
+    }
+}
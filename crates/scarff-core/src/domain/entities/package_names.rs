@@ -0,0 +1,347 @@
+//! Ecosystem-correct names derived from the project name.
+//!
+//! `PROJECT_NAME_KEBAB` and friends are plain case conversions; they say
+//! nothing about what a package registry or compiler accepts. A project
+//! called `2048 Game` or `type` needs adjusting before it is a valid crate,
+//! distribution or module. [`PackageNames`] does that once per language, and
+//! [`RenderContext::with_target`](super::template::RenderContext::with_target)
+//! exposes the result as `PACKAGE_NAME` and `MODULE_NAME`:
+//!
+//! | Language | `PACKAGE_NAME` | `MODULE_NAME` |
+//! |----------|----------------|---------------|
+//! | Rust | crate name: `my-app` | lib identifier: `my_app` |
+//! | Python | PEP 503 distribution name: `my-app` | import package: `my_app` |
+//! | TypeScript | npm name: `my-app`, `@acme/my-app` | identifier: `myApp` |
+//! | Go | module path: `my-app`, `github.com/acme/my-app` | package name: `myapp` |
+//!
+//! Only ASCII letters and digits survive from the project name. A name that
+//! would start with a digit gets a `_` in front; one that is a keyword or a
+//! reserved name of the ecosystem gets a suffix (`type-rs` / `type_rs` for
+//! Rust, a trailing `_` elsewhere).
+
+use crate::domain::value_objects::Language;
+
+use super::template::{capitalize, split_words};
+
+/// Used when nothing of the project name is usable (`"日本"`, `"---"`).
+const FALLBACK_WORD: &str = "app";
+
+/// Rust keywords, plus the names Cargo refuses because they clash with the
+/// standard crates.
+const RUST_RESERVED: &[&str] = &[
+    "abstract",
+    "alloc",
+    "as",
+    "async",
+    "await",
+    "become",
+    "box",
+    "break",
+    "const",
+    "continue",
+    "core",
+    "crate",
+    "do",
+    "dyn",
+    "else",
+    "enum",
+    "extern",
+    "false",
+    "final",
+    "fn",
+    "for",
+    "gen",
+    "if",
+    "impl",
+    "in",
+    "let",
+    "loop",
+    "macro",
+    "match",
+    "mod",
+    "move",
+    "mut",
+    "override",
+    "priv",
+    "proc_macro",
+    "pub",
+    "ref",
+    "return",
+    "self",
+    "static",
+    "std",
+    "struct",
+    "super",
+    "test",
+    "trait",
+    "true",
+    "try",
+    "type",
+    "typeof",
+    "unsafe",
+    "unsized",
+    "use",
+    "virtual",
+    "where",
+    "while",
+    "yield",
+];
+
+/// Python keywords (`keyword.kwlist`, lowercased: identifiers here are).
+const PYTHON_RESERVED: &[&str] = &[
+    "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del", "elif",
+    "else", "except", "false", "finally", "for", "from", "global", "if", "import", "in", "is",
+    "lambda", "none", "nonlocal", "not", "or", "pass", "raise", "return", "true", "try", "while",
+    "with", "yield",
+];
+
+/// ECMAScript reserved words.
+const TYPESCRIPT_RESERVED: &[&str] = &[
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "enum",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "implements",
+    "import",
+    "in",
+    "instanceof",
+    "interface",
+    "let",
+    "new",
+    "null",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "static",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "var",
+    "void",
+    "while",
+    "with",
+    "yield",
+];
+
+/// Go keywords.
+const GO_RESERVED: &[&str] = &[
+    "break",
+    "case",
+    "chan",
+    "const",
+    "continue",
+    "default",
+    "defer",
+    "else",
+    "fallthrough",
+    "for",
+    "func",
+    "go",
+    "goto",
+    "if",
+    "import",
+    "interface",
+    "map",
+    "package",
+    "range",
+    "return",
+    "select",
+    "struct",
+    "switch",
+    "type",
+    "var",
+];
+
+/// The `PACKAGE_NAME` / `MODULE_NAME` pair for one project and language.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageNames {
+    /// Name the package is published or imported under.
+    pub package: String,
+
+    /// Identifier used for the project's own code.
+    pub module: String,
+}
+
+impl PackageNames {
+    /// Derive the names for `project_name` in `language`.
+    ///
+    /// `scope` qualifies the package where the ecosystem has such a thing:
+    /// an npm scope (`acme` or `@acme`) for TypeScript, a module path prefix
+    /// (`github.com/acme`) for Go. Rust and Python ignore it.
+    pub fn derive(project_name: &str, language: Language, scope: Option<&str>) -> Self {
+        let words = identifier_words(project_name);
+        let scope = scope.map(str::trim).filter(|s| !s.is_empty());
+
+        match language {
+            Language::Rust => {
+                let mut package = leading_digit_guard(words.join("-"));
+                if RUST_RESERVED.contains(&package.replace('-', "_").as_str()) {
+                    package.push_str("-rs");
+                }
+                Self {
+                    module: package.replace('-', "_"),
+                    package,
+                }
+            }
+            Language::Python => Self {
+                package: words.join("-"),
+                module: identifier(words.join("_"), PYTHON_RESERVED),
+            },
+            Language::TypeScript => {
+                let name = words.join("-");
+                let package = match scope {
+                    Some(scope) => {
+                        let scope = identifier_words(scope.trim_start_matches('@')).join("-");
+                        format!("@{scope}/{name}")
+                    }
+                    None => name,
+                };
+                let camel: String = words
+                    .iter()
+                    .enumerate()
+                    .map(|(i, w)| if i == 0 { w.clone() } else { capitalize(w) })
+                    .collect();
+                Self {
+                    package,
+                    module: identifier(camel, TYPESCRIPT_RESERVED),
+                }
+            }
+            Language::Go => {
+                let name = words.join("-");
+                let package = match scope {
+                    Some(prefix) => format!("{}/{name}", prefix.trim_end_matches('/')),
+                    None => name,
+                };
+                Self {
+                    package,
+                    module: identifier(words.concat(), GO_RESERVED),
+                }
+            }
+        }
+    }
+}
+
+/// Lowercase ASCII-alphanumeric words of `name`; never empty. Punctuation
+/// separates words, as `.` does in PEP 503.
+fn identifier_words(name: &str) -> Vec<String> {
+    let spaced: String = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect();
+    let words: Vec<String> = split_words(&spaced)
+        .iter()
+        .map(|w| {
+            w.chars()
+                .filter(char::is_ascii_alphanumeric)
+                .collect::<String>()
+                .to_ascii_lowercase()
+        })
+        .filter(|w| !w.is_empty())
+        .collect();
+    if words.is_empty() {
+        vec![FALLBACK_WORD.to_string()]
+    } else {
+        words
+    }
+}
+
+fn leading_digit_guard(name: String) -> String {
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{name}")
+    } else {
+        name
+    }
+}
+
+/// A valid identifier: no leading digit, not a reserved word.
+fn identifier(name: String, reserved: &[&str]) -> String {
+    let name = leading_digit_guard(name);
+    if reserved.contains(&name.as_str()) {
+        format!("{name}_")
+    } else {
+        name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(project: &str, language: Language, scope: Option<&str>) -> (String, String) {
+        let names = PackageNames::derive(project, language, scope);
+        (names.package, names.module)
+    }
+
+    #[test]
+    fn each_language_gets_its_own_conventions() {
+        let pair = |p: &str, m: &str| (p.to_string(), m.to_string());
+
+        assert_eq!(
+            names("My Awesome.App", Language::Rust, None),
+            pair("my-awesome-app", "my_awesome_app")
+        );
+        assert_eq!(
+            names("HTTPServer", Language::Python, None),
+            pair("http-server", "http_server")
+        );
+        assert_eq!(
+            names("web_app", Language::TypeScript, Some("@Acme Corp")),
+            pair("@acme-corp/web-app", "webApp")
+        );
+        assert_eq!(
+            names("web-app", Language::Go, Some("github.com/acme/")),
+            pair("github.com/acme/web-app", "webapp")
+        );
+        assert_eq!(
+            names("web-app", Language::Rust, Some("ignored")),
+            pair("web-app", "web_app")
+        );
+    }
+
+    #[test]
+    fn invalid_names_are_adjusted() {
+        let pair = |p: &str, m: &str| (p.to_string(), m.to_string());
+
+        assert_eq!(names("2048", Language::Rust, None), pair("_2048", "_2048"));
+        assert_eq!(
+            names("type", Language::Rust, None),
+            pair("type-rs", "type_rs")
+        );
+        assert_eq!(
+            names("test", Language::Rust, None),
+            pair("test-rs", "test_rs")
+        );
+        assert_eq!(
+            names("class", Language::Python, None),
+            pair("class", "class_")
+        );
+        assert_eq!(
+            names("3d-viewer", Language::TypeScript, None),
+            pair("3d-viewer", "_3dViewer")
+        );
+        assert_eq!(names("func", Language::Go, None), pair("func", "func_"));
+        assert_eq!(names("日本", Language::Python, None), pair("app", "app"));
+    }
+}
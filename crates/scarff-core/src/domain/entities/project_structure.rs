@@ -1,8 +1,16 @@
 use std::collections::HashSet;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+use sha2::{Digest, Sha256};
+
+use super::overlay::{MergeFormat, MergeStrategy, Overlay};
 use crate::domain::{entities::common::Permissions, error::DomainError};
 
+/// Structured merge used by [`ProjectStructure::layer`]: format, path,
+/// existing text and overlay text in; merged text or the reason it failed
+/// out.
+pub type MergeFn<'a> = dyn Fn(MergeFormat, &Path, &str, &str) -> Result<String, String> + 'a;
+
 /// Final project structure ready for materialization.
 ///
 /// This is the output of the template rendering process.
@@ -21,10 +29,20 @@ impl ProjectStructure {
         }
     }
 
-    pub fn add_file(&mut self, path: impl Into<PathBuf>, content: String, perms: Permissions) {
+    /// Directory the project is written to.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn add_file(
+        &mut self,
+        path: impl Into<PathBuf>,
+        content: impl Into<FileContent>,
+        perms: Permissions,
+    ) {
         self.entries.push(FsEntry::File(FileToWrite {
             path: path.into(),
-            content,
+            content: content.into(),
             permissions: perms,
         }));
     }
@@ -39,7 +57,7 @@ impl ProjectStructure {
     pub fn with_file(
         mut self,
         path: impl Into<PathBuf>,
-        content: String,
+        content: impl Into<FileContent>,
         perms: Permissions,
     ) -> Self {
         self.add_file(path, content, perms);
@@ -77,9 +95,125 @@ impl ProjectStructure {
             }
         }
 
+        // Rendered paths can also collide by nesting: a file `src` and a
+        // directory or file under `src/` cannot both be written.
+        let files: HashSet<&Path> = self.files().map(|f| f.path.as_path()).collect();
+        for entry in &self.entries {
+            let path = match entry {
+                FsEntry::File(f) => &f.path,
+                FsEntry::Directory(d) => &d.path,
+            };
+            if let Some(file) = path.ancestors().skip(1).find(|a| files.contains(a)) {
+                return Err(DomainError::InvalidPath {
+                    path: path.display().to_string(),
+                    reason: format!("'{}' is a file, not a directory", file.display()),
+                });
+            }
+        }
+
         Ok(())
     }
 
+    /// Add the rendered output of `overlay` on top of this structure.
+    ///
+    /// Directories already present are shared. A file already present is
+    /// merged with the overlay's [`MergeStrategy`] for its path; without one
+    /// the run fails rather than silently dropping either file. Structured
+    /// merges are handed to `merge` with the format, the path, the existing
+    /// text and the overlay's text; it returns the merged text or why it
+    /// could not merge.
+    ///
+    /// # Errors
+    ///
+    /// [`DomainError::OverlayConflict`] for a path both write without a
+    /// strategy, a file on one side and a directory on the other (at the
+    /// same path, or a file where the other side needs a parent directory),
+    /// an `append` or `merge` involving binary content, a `merge` of a file
+    /// whose format cannot be told from its name, or a failed merge.
+    pub fn layer(
+        &mut self,
+        layer: ProjectStructure,
+        overlay: &Overlay,
+        merge: &MergeFn<'_>,
+    ) -> Result<(), DomainError> {
+        for entry in layer.entries {
+            let path = match &entry {
+                FsEntry::File(f) => f.path.clone(),
+                FsEntry::Directory(d) => d.path.clone(),
+            };
+            let conflict = |reason: String| DomainError::OverlayConflict {
+                overlay: overlay.name.clone(),
+                path: path.display().to_string(),
+                reason,
+            };
+
+            let existing = self.entries.iter_mut().find(|e| match e {
+                FsEntry::File(f) => f.path == path,
+                FsEntry::Directory(d) => d.path == path,
+            });
+            let Some(existing) = existing else {
+                self.entries.push(entry);
+                continue;
+            };
+
+            match (existing, entry) {
+                (FsEntry::Directory(_), FsEntry::Directory(_)) => {}
+                (FsEntry::File(base), FsEntry::File(file)) => match overlay.strategy_for(&path) {
+                    None => {
+                        return Err(conflict(
+                                "the project already has this file and the overlay declares no merge strategy for it"
+                                    .into(),
+                            ));
+                    }
+                    Some(MergeStrategy::Keep) => {}
+                    Some(MergeStrategy::Replace) => *base = file,
+                    Some(MergeStrategy::Append) => {
+                        let (FileContent::Text(text), FileContent::Text(extra)) =
+                            (&mut base.content, file.content)
+                        else {
+                            return Err(conflict("binary files cannot be appended".into()));
+                        };
+                        if !text.is_empty() && !text.ends_with('\n') {
+                            text.push('\n');
+                        }
+                        text.push_str(&extra);
+                    }
+                    Some(MergeStrategy::Merge(format)) => {
+                        let (FileContent::Text(text), FileContent::Text(extra)) =
+                            (&mut base.content, file.content)
+                        else {
+                            return Err(conflict("binary files cannot be merged".into()));
+                        };
+                        let format = format.or_else(|| MergeFormat::detect(&path)).ok_or_else(|| {
+                            conflict(
+                                "cannot tell the file's format from its name; use toml, json, lines or dotenv as the strategy".into(),
+                            )
+                        })?;
+                        *text = merge(format, &path, text, &extra).map_err(|reason| {
+                            conflict(format!("{format} merge failed: {reason}"))
+                        })?;
+                    }
+                },
+                _ => {
+                    return Err(conflict(
+                        "one side writes a file and the other a directory".into(),
+                    ));
+                }
+            }
+        }
+
+        // Paths can also collide by nesting (an overlay file `src` over a
+        // base `src/main.rs`), which only the whole structure shows.
+        self.validate().map_err(|e| match e {
+            DomainError::InvalidPath { path, reason } => DomainError::OverlayConflict {
+                overlay: overlay.name.clone(),
+                path,
+                reason,
+            },
+            other => other,
+        })
+    }
+
     pub fn files(&self) -> impl Iterator<Item = &FileToWrite> {
         self.entries.iter().filter_map(|e| match e {
             FsEntry::File(f) => Some(f),
@@ -97,6 +231,58 @@ impl ProjectStructure {
     pub fn entry_count(&self) -> usize {
         self.entries.len()
     }
+
+    /// Stable SHA-256 of the structure's paths, contents and executable bits,
+    /// formatted as `sha256:<hex>`.
+    ///
+    /// Independent of the output root, entry order and platform path
+    /// separator, so the same template and inputs give the same digest on
+    /// every machine. CI can assert it to detect template drift.
+    pub fn digest(&self) -> String {
+        let mut entries: Vec<(String, &FsEntry)> = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let path = match entry {
+                    FsEntry::File(f) => &f.path,
+                    FsEntry::Directory(d) => &d.path,
+                };
+                (portable_path(path), entry)
+            })
+            .collect();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+        // Length-prefixed fields keep the encoding unambiguous.
+        let mut hasher = Sha256::new();
+        for (path, entry) in entries {
+            match entry {
+                FsEntry::Directory(_) => hasher.update(b"D"),
+                FsEntry::File(f) if f.permissions.executable_flag() => hasher.update(b"X"),
+                FsEntry::File(_) => hasher.update(b"F"),
+            }
+            hasher.update((path.len() as u64).to_le_bytes());
+            hasher.update(path.as_bytes());
+            if let FsEntry::File(f) = entry {
+                hasher.update((f.content.len() as u64).to_le_bytes());
+                hasher.update(f.content.as_bytes());
+            }
+        }
+
+        let hex: String = hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        format!("sha256:{hex}")
+    }
+}
+
+/// `/`-separated form of a relative path.
+fn portable_path(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
 }
 
 #[derive(Debug, Clone)]
@@ -108,10 +294,78 @@ pub enum FsEntry {
 #[derive(Debug, Clone)]
 pub struct FileToWrite {
     pub path: PathBuf,
-    pub content: String,
+    pub content: FileContent,
     pub permissions: Permissions,
 }
 
+/// Content of a file to write: rendered text, or bytes copied from a binary
+/// template file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileContent {
+    Text(String),
+    Binary(Vec<u8>),
+}
+
+impl FileContent {
+    /// The bytes to write.
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            Self::Text(text) => text.as_bytes(),
+            Self::Binary(bytes) => bytes,
+        }
+    }
+
+    /// The text, or `None` for binary content.
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            Self::Text(text) => Some(text),
+            Self::Binary(_) => None,
+        }
+    }
+
+    pub fn is_binary(&self) -> bool {
+        matches!(self, Self::Binary(_))
+    }
+
+    pub fn len(&self) -> usize {
+        self.as_bytes().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.as_bytes().is_empty()
+    }
+}
+
+impl From<String> for FileContent {
+    fn from(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+impl From<&str> for FileContent {
+    fn from(text: &str) -> Self {
+        Self::Text(text.to_string())
+    }
+}
+
+impl From<Vec<u8>> for FileContent {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::Binary(bytes)
+    }
+}
+
+impl PartialEq<str> for FileContent {
+    fn eq(&self, other: &str) -> bool {
+        self.as_text() == Some(other)
+    }
+}
+
+impl PartialEq<&str> for FileContent {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_text() == Some(*other)
+    }
+}
+
 impl FileToWrite {
     pub fn is_empty(&self) -> bool {
         self.content.is_empty()
@@ -127,3 +381,186 @@ pub struct DirectoryToCreate {
     pub path: PathBuf,
     pub permissions: Permissions,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn structure(root: &str) -> ProjectStructure {
+        ProjectStructure::new(root)
+            .with_directory("src", Permissions::read_write())
+            .with_file("src/main.rs", "fn main() {}\n", Permissions::read_write())
+            .with_file("run.sh", "#!/bin/sh\n", Permissions::executable())
+    }
+
+    #[test]
+    fn digest_ignores_root_and_entry_order() {
+        let mut reordered = ProjectStructure::new("/elsewhere")
+            .with_file("run.sh", "#!/bin/sh\n", Permissions::executable())
+            .with_file("src/main.rs", "fn main() {}\n", Permissions::read_write());
+        reordered.add_directory("src", Permissions::read_write());
+
+        assert_eq!(structure("/a").digest(), reordered.digest());
+        assert!(structure("/a").digest().starts_with("sha256:"));
+    }
+
+    #[test]
+    fn digest_changes_with_content_and_permissions() {
+        let base = structure("/a").digest();
+
+        let content = ProjectStructure::new("/a")
+            .with_directory("src", Permissions::read_write())
+            .with_file("src/main.rs", "fn main() { }\n", Permissions::read_write())
+            .with_file("run.sh", "#!/bin/sh\n", Permissions::executable());
+        let perms = ProjectStructure::new("/a")
+            .with_directory("src", Permissions::read_write())
+            .with_file("src/main.rs", "fn main() {}\n", Permissions::read_write())
+            .with_file("run.sh", "#!/bin/sh\n", Permissions::read_write());
+
+        assert_ne!(base, content.digest());
+        assert_ne!(base, perms.digest());
+    }
+
+    fn no_merge(_: MergeFormat, _: &Path, _: &str, _: &str) -> Result<String, String> {
+        Err("not merging".into())
+    }
+
+    #[test]
+    fn layer_hands_structured_merges_to_the_merger() {
+        use super::super::{format::Glob, overlay::MergeRule};
+
+        let overlay = Overlay::new("otel").with_merge_rule(MergeRule::new(
+            Glob::new("**").unwrap(),
+            MergeStrategy::Merge(None),
+        ));
+        let layer = || {
+            ProjectStructure::new("/a")
+                .with_file("Cargo.toml", "b", Permissions::read_write())
+                .with_file("run.sh", "b", Permissions::read_write())
+        };
+        let concat = |format: MergeFormat, path: &Path, base: &str, extra: &str| {
+            Ok(format!("{format}:{}:{base}+{extra}", path.display()))
+        };
+
+        let mut project =
+            ProjectStructure::new("/a").with_file("Cargo.toml", "a", Permissions::read_write());
+        project.layer(layer(), &overlay, &concat).unwrap();
+        assert_eq!(
+            project.files().next().unwrap().content,
+            "toml:Cargo.toml:a+b"
+        );
+
+        let mut project = structure("/a");
+        let err = project.layer(layer(), &overlay, &concat).unwrap_err();
+        assert!(
+            err.to_string().contains("cannot tell the file's format"),
+            "{err}"
+        );
+
+        let mut project =
+            ProjectStructure::new("/a").with_file("Cargo.toml", "a", Permissions::read_write());
+        let err = project.layer(layer(), &overlay, &no_merge).unwrap_err();
+        assert!(
+            err.to_string().contains("toml merge failed: not merging"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn layer_merges_declared_paths_and_rejects_the_rest() {
+        use super::super::{format::Glob, overlay::MergeRule};
+
+        let overlay = Overlay::new("docker")
+            .with_merge_rule(MergeRule::new(
+                Glob::new(".gitignore").unwrap(),
+                MergeStrategy::Append,
+            ))
+            .with_merge_rule(MergeRule::new(
+                Glob::new("run.sh").unwrap(),
+                MergeStrategy::Replace,
+            ));
+        let docker = || {
+            ProjectStructure::new("/a")
+                .with_directory("src", Permissions::read_write())
+                .with_file(".gitignore", "*.tar\n", Permissions::read_write())
+                .with_file("run.sh", "docker run\n", Permissions::executable())
+                .with_file("Dockerfile", "FROM scratch\n", Permissions::read_write())
+        };
+
+        let mut project =
+            structure("/a").with_file(".gitignore", "target", Permissions::read_write());
+        project.layer(docker(), &overlay, &no_merge).unwrap();
+
+        let content = |path: &str| {
+            project
+                .files()
+                .find(|f| f.path == Path::new(path))
+                .map(|f| f.content.clone())
+                .unwrap()
+        };
+        assert_eq!(content(".gitignore"), "target\n*.tar\n");
+        assert_eq!(content("run.sh"), "docker run\n");
+        assert_eq!(content("Dockerfile"), "FROM scratch\n");
+        assert_eq!(project.directories().count(), 1);
+        assert!(project.validate().is_ok());
+
+        let mut project = structure("/a");
+        let err = project
+            .layer(docker(), &Overlay::new("docker"), &no_merge)
+            .unwrap_err();
+        assert!(
+            matches!(&err, DomainError::OverlayConflict { overlay, path, .. } if overlay == "docker" && path == "run.sh"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn layer_rejects_files_nested_under_files_of_the_other_side() {
+        let overlay = Overlay::new("docs");
+
+        let mut project = structure("/a");
+        let err = project
+            .layer(
+                ProjectStructure::new("/a").with_file(
+                    "run.sh/README",
+                    "",
+                    Permissions::read_write(),
+                ),
+                &overlay,
+                &no_merge,
+            )
+            .unwrap_err();
+        assert!(
+            matches!(&err, DomainError::OverlayConflict { overlay, path, reason }
+                if overlay == "docs" && path == "run.sh/README" && reason.contains("'run.sh' is a file")),
+            "{err}"
+        );
+
+        let mut project =
+            ProjectStructure::new("/a").with_file("docs/index.md", "", Permissions::read_write());
+        let err = project
+            .layer(
+                ProjectStructure::new("/a").with_file("docs", "", Permissions::read_write()),
+                &overlay,
+                &no_merge,
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("'docs' is a file"), "{err}");
+    }
+
+    #[test]
+    fn validate_rejects_duplicate_and_nested_file_paths() {
+        assert!(structure("/a").validate().is_ok());
+
+        let duplicate =
+            structure("/a").with_file("run.sh", String::new(), Permissions::read_write());
+        assert!(matches!(
+            duplicate.validate(),
+            Err(DomainError::DuplicatePath { path }) if path == "run.sh"
+        ));
+
+        let nested = structure("/a").with_directory("run.sh/bin", Permissions::read_write());
+        let err = nested.validate().unwrap_err().to_string();
+        assert!(err.contains("'run.sh' is a file"), "{err}");
+    }
+}
@@ -48,16 +48,18 @@
 //! **Clarity:** Explicit intent. A `Cargo.toml` with `{{PROJECT_NAME}}` is obviously
 //! parameterized; a README without placeholders is obviously literal.
 //!
-//! ### 3. Why `TemplateSource` with `Static` vs `Owned`?
+//! ### 3. Why `TemplateSource` with static vs owned text?
 //!
-//! **Zero-copy for hardcoded:** `TemplateSource::Static` references compile-time strings
-//! without allocation or cloning.
+//! **Zero-copy for hardcoded:** `TemplateSource::from(&'static str)` references
+//! compile-time strings without allocation or cloning.
 //!
-//! **Flexibility for loaded:** `TemplateSource::Owned` allows filesystem-loaded or
+//! **Flexibility for loaded:** `TemplateSource::from(String)` allows filesystem-loaded or
 //! remotely-fetched templates to own their content.
 //!
-//! **Future-proof:** When we add `TemplateSource::Cached(Arc<str>)` for the template hub,
-//! the enum variant approach makes this a non-breaking addition.
+//! **Parsed once:** the source also keeps the AST the rendering engine parses from it,
+//! so a file rendered for several projects is tokenized a single time, with no global
+//! cache to key or bound. The text's storage stays private, so adding shared storage
+//! (`Arc<str>`) for the template hub is a non-breaking change.
 //!
 //! ### 4. Why `TargetMatcher` with `Option<T>` fields?
 //!
@@ -87,20 +89,13 @@
 //! ```
 //!
 //! ### Conditional Rendering
-//! ```rust, ignore
-//! // Add to TemplateContent:
-//! Conditional {
-//!     condition: String, // "{{FEATURE_X}}"
-//!     then_branch: Box<TemplateContent>,
-//!     else_branch: Option<Box<TemplateContent>>,
-//! }
-//! ```
+//! Implemented inside file content rather than as a `TemplateContent` variant:
+//! `{{#if FEATURE_X}}…{{else}}…{{/if}}` (see [`RenderContext::try_render`]).
 //!
 //! ### Template Composition (Inheritance)
-//! ```rust, ignore
-//! // Add to TemplateTree:
-//! Extend { base: TemplateId, overrides: Vec<TemplateNode> }
-//! ```
+//! Implemented by flattening rather than as a `TemplateTree` variant: a
+//! template naming a base in [`Template::extends`] is merged onto it with
+//! [`Template::extend`] when loaded, so renderers only ever see full trees.
 //!
 //! ### Live Reloading
 //! ```rust, ignore
@@ -108,15 +103,29 @@
 //! Watched { path: PathBuf, last_modified: SystemTime }
 //! ```
 
-use std::collections::HashSet;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt;
+use std::path::Path;
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
 use uuid::Uuid;
 
-use super::{project_structure::ProjectStructure, target::Target};
+use super::{
+    condition::Condition,
+    format::{FormatOptions, FormatRule, format_options_for},
+    hook::Hook,
+    overlay::Overlay,
+    package_names::PackageNames,
+    project_structure::ProjectStructure,
+    target::Target,
+    variable::VariableSpec,
+};
 
 use crate::domain::{
     entities::common::{Permissions, RelativePath},
     error::DomainError,
+    render::Parsed,
     value_objects::{Architecture, Framework, Language, ProjectKind},
 };
 
@@ -189,19 +198,34 @@ pub trait TemplateEngine: Send + Sync {
 /// | `PROJECT_NAME_SNAKE` | "my_awesome_app" | Computed |
 /// | `PROJECT_NAME_KEBAB` | "my-awesome-app" | Computed |
 /// | `PROJECT_NAME_PASCAL` | "MyAwesomeApp" | Computed |
-/// | `YEAR` | "2026" | System time |
-///
-/// ## Future Extensions
-///
-/// - `SCARFF_VERSION`: Version of tool generating the project
-/// - `GENERATED_AT`: ISO 8601 timestamp
-/// - `GIT_USER_NAME`, `GIT_USER_EMAIL`: From git config
-/// - `RANDOM_ID`: UUID fragment for unique identifiers
+/// | `YEAR` | "2026" | [`RunInfo::generated_at`] |
+/// | `GENERATED_AT` | "2026-03-01T09:30:00Z" | [`RunInfo::generated_at`] (UTC, ISO 8601) |
+/// | `SCARFF_VERSION` | "0.1.0" | [`RunInfo::scarff_version`] |
+/// | `GIT_USER_NAME` | "Ada Lovelace" | git config `user.name`, or empty |
+/// | `GIT_USER_EMAIL` | "ada@example.com" | git config `user.email`, or empty |
+/// | `RANDOM_ID` | "3f2a9c1e" | [`RunInfo::random_id`] |
+/// | `LANGUAGE` | "rust" | [`Target::language`] |
+/// | `KIND` | "web-backend" | [`Target::kind`] |
+/// | `FRAMEWORK` | "axum" | [`Target::framework`], or empty |
+/// | `ARCHITECTURE` | "layered" | [`Target::architecture`] |
+/// | `PACKAGE_NAME` | "my-awesome-app" | [`PackageNames`] for the target language |
+/// | `MODULE_NAME` | "my_awesome_app" | [`PackageNames`] for the target language |
+///
+/// The run-dependent values come from a [`RunInfo`], supplied by the
+/// application layer's `RunInfoProvider` port through
+/// [`with_run_info`](RenderContext::with_run_info); [`new`](RenderContext::new)
+/// leaves them unset, so the domain never reads a clock or an RNG. The target
+/// built-ins are set by [`with_target`](RenderContext::with_target).
+///
+/// ## Variable Types
+///
+/// Values are [`RenderValue`]s: plain strings, lists, or maps. Lists and maps
+/// are iterated with `{{#each}}`; a comma-separated string can be iterated as
+/// a list too, so `--var modules=users,orders` style input needs no parsing.
 #[derive(Debug, Clone)]
 pub struct RenderContext {
     /// Original project name as provided by user.
     /// Kept separate from variables for debugging and display purposes.
-    #[allow(unused)]
     project_name: String,
 
     /// Variable map for substitution.
@@ -209,10 +233,36 @@ pub struct RenderContext {
     /// - Order doesn't matter for simple replacement
     /// - O(1) lookup for variable resolution
     /// - No need for sorted iteration in this use case
-    variables: std::collections::HashMap<String, String>,
+    ///
+    /// Maps nested *inside* values are `BTreeMap`s, since `{{#each}}` over a
+    /// map must iterate in a stable order.
+    variables: HashMap<String, RenderValue>,
+
+    /// Fail on unresolved placeholders instead of leaving them in the output.
+    strict: bool,
 }
 
 impl RenderContext {
+    /// Names of the built-in variables every render context has.
+    pub const BUILTIN_VARIABLES: [&'static str; 16] = [
+        "PROJECT_NAME",
+        "PROJECT_NAME_SNAKE",
+        "PROJECT_NAME_KEBAB",
+        "PROJECT_NAME_PASCAL",
+        "YEAR",
+        "GENERATED_AT",
+        "SCARFF_VERSION",
+        "GIT_USER_NAME",
+        "GIT_USER_EMAIL",
+        "RANDOM_ID",
+        "LANGUAGE",
+        "KIND",
+        "FRAMEWORK",
+        "ARCHITECTURE",
+        "PACKAGE_NAME",
+        "MODULE_NAME",
+    ];
+
     /// Create a new render context with automatic variable derivation.
     ///
     /// # Automatic Derivations
@@ -227,29 +277,99 @@ impl RenderContext {
     ///
     /// # Performance Note
     ///
-    /// All transformations happen once at construction. Rendering is then
-    /// O(n*m) where n=template length, m=variable count (typically < 20).
+    /// All transformations happen once at construction. Rendering is a single
+    /// pass over the parsed template: O(n) in template length, independent of
+    /// the variable count.
     pub fn new(project_name: impl Into<String>) -> Self {
         let name = project_name.into();
-        let mut vars = std::collections::HashMap::new();
+        let mut vars: HashMap<String, RenderValue> = HashMap::new();
 
         // Standard variables - these are the "contract" between Scarff and templates.
         // Any template using {{PROJECT_NAME}} can expect this to exist.
-        vars.insert("PROJECT_NAME".to_string(), name.clone());
-        vars.insert("PROJECT_NAME_SNAKE".to_string(), to_snake_case(&name));
-        vars.insert("PROJECT_NAME_KEBAB".to_string(), to_kebab_case(&name));
-        vars.insert("PROJECT_NAME_PASCAL".to_string(), to_pascal_case(&name));
-
-        // Static for now; should use `chrono` for actual current year in production.
-        // Using literal "2026" avoids chrono dependency in MVP.
-        vars.insert("YEAR".to_string(), "2026".to_string());
+        vars.insert("PROJECT_NAME".to_string(), name.clone().into());
+        vars.insert(
+            "PROJECT_NAME_SNAKE".to_string(),
+            to_snake_case(&name).into(),
+        );
+        vars.insert(
+            "PROJECT_NAME_KEBAB".to_string(),
+            to_kebab_case(&name).into(),
+        );
+        vars.insert(
+            "PROJECT_NAME_PASCAL".to_string(),
+            to_pascal_case(&name).into(),
+        );
 
         Self {
             project_name: name,
             variables: vars,
+            strict: false,
         }
     }
 
+    /// Set the run-dependent built-ins (`YEAR`, `GENERATED_AT`,
+    /// `SCARFF_VERSION`, `GIT_USER_*`, `RANDOM_ID`) from `info`.
+    ///
+    /// A missing git identity becomes an empty string, so templates can use
+    /// `{{#if GIT_USER_NAME}}` or `{{GIT_USER_NAME | default("…")}}` even in
+    /// strict mode.
+    pub fn with_run_info(self, info: &RunInfo) -> Self {
+        let timestamp = UtcTimestamp::from(info.generated_at);
+        self.with_variable("YEAR", timestamp.year.to_string())
+            .with_variable("GENERATED_AT", timestamp.to_string())
+            .with_variable("SCARFF_VERSION", info.scarff_version.as_str())
+            .with_variable(
+                "GIT_USER_NAME",
+                info.git_user_name.as_deref().unwrap_or_default(),
+            )
+            .with_variable(
+                "GIT_USER_EMAIL",
+                info.git_user_email.as_deref().unwrap_or_default(),
+            )
+            .with_variable("RANDOM_ID", info.random_id.as_str())
+    }
+
+    /// Set the target built-ins (`LANGUAGE`, `KIND`, `FRAMEWORK`,
+    /// `ARCHITECTURE`) so templates and `when` conditions can branch on
+    /// them, e.g. `when = "FRAMEWORK == 'axum'"`. Without a framework,
+    /// `FRAMEWORK` is empty.
+    ///
+    /// Also derives `PACKAGE_NAME` and `MODULE_NAME` for the target
+    /// language, unscoped; see [`with_package_names`](Self::with_package_names).
+    pub fn with_target(self, target: &Target) -> Self {
+        let names = PackageNames::derive(&self.project_name, target.language(), None);
+        self.with_package_names(&names)
+            .with_variable("LANGUAGE", target.language().as_str())
+            .with_variable("KIND", target.kind().as_str())
+            .with_variable(
+                "FRAMEWORK",
+                target.framework().map(|f| f.as_str()).unwrap_or_default(),
+            )
+            .with_variable("ARCHITECTURE", target.architecture().as_str())
+    }
+
+    /// Set `PACKAGE_NAME` and `MODULE_NAME`, e.g. to names derived with an
+    /// npm scope or Go module prefix.
+    pub fn with_package_names(self, names: &PackageNames) -> Self {
+        self.with_variable("PACKAGE_NAME", names.package.as_str())
+            .with_variable("MODULE_NAME", names.module.as_str())
+    }
+
+    /// Enable or disable strict rendering.
+    ///
+    /// In strict mode [`try_render`](Self::try_render) fails with
+    /// [`DomainError::UnresolvedPlaceholders`] instead of leaving `{{UNKNOWN}}`
+    /// in the output. Off by default; `scarff new` turns it on.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Whether unresolved placeholders are errors.
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
     /// Add a custom variable, consuming self and returning a new context.
     ///
     /// # Builder Pattern
@@ -258,55 +378,352 @@ impl RenderContext {
     /// ```rust,ignore
     /// let ctx = RenderContext::new("MyApp")
     ///     .with_variable("FEATURE_X", "enabled")
-    ///     .with_variable("DATABASE", "postgres");
+    ///     .with_variable("DATABASE", "postgres")
+    ///     .with_variable("MODULES", vec!["users", "orders"]);
     /// ```
     ///
     /// # Variable Precedence
     ///
     /// User-defined variables can **override** built-ins if needed (though this
     /// is generally discouraged to avoid confusion).
-    pub fn with_variable(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+    pub fn with_variable(mut self, key: impl Into<String>, value: impl Into<RenderValue>) -> Self {
         self.variables.insert(key.into(), value.into());
         self
     }
 
-    /// Get a variable value if it exists.
+    /// Get a string variable value if it exists.
     ///
-    /// Returns `None` for undefined variables (rendering will leave placeholder as-is
-    /// or fail based on strict mode - TBD).
+    /// Returns `None` for undefined variables and for list/map values; use
+    /// [`value`](Self::value) to access those.
     pub fn get(&self, key: &str) -> Option<&str> {
-        self.variables.get(key).map(|s| s.as_str())
+        self.variables.get(key).and_then(RenderValue::as_str)
     }
 
-    /// Render a template string by replacing `{{VARIABLE}}` placeholders.
-    ///
-    /// # Algorithm
-    ///
-    /// Simple linear scan and replace. Not the most efficient for large templates
-    /// with many variables (would benefit from Aho-Corasick or similar), but
-    /// adequate for MVP file sizes (< 10KB typical).
+    /// Get a variable value of any type if it exists.
+    pub fn value(&self, key: &str) -> Option<&RenderValue> {
+        self.variables.get(key)
+    }
+
+    /// Iterate over all variables (in no particular order).
     ///
-    /// # Future: Proper Template Engine
+    /// Used by renderers that hand the whole context to another engine.
+    pub fn variables(&self) -> impl Iterator<Item = (&str, &RenderValue)> {
+        self.variables.iter().map(|(k, v)| (k.as_str(), v))
+    }
+
+    /// Render a template string, substituting `{{VARIABLE}}` placeholders and
+    /// evaluating `{{#if}}` / `{{#unless}}` blocks.
     ///
-    /// This is intentionally simple. When we need conditionals (`{{#if}}`), loops,
-    /// or filters, we'll replace this with a real engine (Handlebars, Tera, or Minijinja)
-    /// without changing the `RenderContext` API.
+    /// Lenient wrapper around [`try_render`](Self::try_render): on any error
+    /// (including strict-mode failures) the input is returned unchanged.
+    /// Prefer `try_render` wherever the error can be surfaced to the template
+    /// author.
     ///
     /// # Edge Cases
     ///
-    /// - `{{UNKNOWN}}` → remains as literal `{{UNKNOWN}}` (no error)
+    /// - `{{UNKNOWN}}` → remains as literal `{{UNKNOWN}}` (no error, unless strict)
     /// - `{{PROJECT_NAME}}{{PROJECT_NAME}}` → both replaced correctly
     /// - Nested braces `{{{PROJECT_NAME}}}` → outer braces preserved, inner replaced
     pub fn render(&self, template: &str) -> String {
-        let mut result = template.to_string();
+        self.try_render(template)
+            .unwrap_or_else(|_| template.to_string())
+    }
+
+    /// Render a template string, reporting malformed block syntax.
+    ///
+    /// # Conditionals
+    ///
+    /// ```text
+    /// {{#if DATABASE}}
+    /// sqlx = "0.8"
+    /// {{else}}
+    /// # no database
+    /// {{/if}}
+    /// ```
+    ///
+    /// A variable is falsy when undefined, empty, or `false`/`0`/`no`/`off`
+    /// (case-insensitive). `{{#unless}}` inverts the test and `{{else if X}}`
+    /// chains further conditions. Block tags alone on a line drop that line.
+    ///
+    /// # Loops
+    ///
+    /// ```text
+    /// {{#each MODULES as module}}
+    /// pub mod {{module}};
+    /// {{else}}
+    /// // no modules
+    /// {{/each}}
+    /// ```
+    ///
+    /// Inside a loop the current item is `{{this}}` (or the `as` name), map
+    /// fields are reached with dots (`{{this.name}}`), and `{{@index}}`,
+    /// `{{@first}}`, `{{@last}}` and `{{@key}}` describe the iteration.
+    /// `{{else}}` renders when the collection is empty.
+    ///
+    /// # Filters
+    ///
+    /// Any placeholder can be piped through filters, applied left to right:
+    /// `{{ ENTITY | plural | snake }}`, `{{ PORT | default("8080") }}`.
+    /// Available: `snake`, `kebab`, `pascal`, `camel`, `screaming_snake`,
+    /// `title`, `upper`, `lower`, `plural`, `singular`, `default("x")`.
+    ///
+    /// # Escaping
+    ///
+    /// `\{{` renders as a literal `{{`, and everything between `{{{{raw}}}}`
+    /// and `{{{{/raw}}}}` is copied verbatim, for files that also contain
+    /// another tool's brace syntax.
+    ///
+    /// # Errors
+    ///
+    /// - [`DomainError::TemplateSyntax`] with the line and column of the
+    ///   offending tag for unbalanced or malformed blocks
+    /// - [`DomainError::UnresolvedPlaceholders`] in [strict](Self::strict) mode,
+    ///   listing every placeholder that could not be resolved
+    pub fn try_render(&self, template: &str) -> Result<String, DomainError> {
+        crate::domain::render::render(template, &self.variables, self.strict)
+    }
+
+    /// Render a file or directory path from `template.toml`, e.g.
+    /// `src/{{PROJECT_NAME_SNAKE}}/__init__.py`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`try_render`](Self::try_render), plus the errors of
+    /// [`RelativePath::parse`] when the rendered path is absolute, contains
+    /// `..` or has an empty segment.
+    pub fn render_path(&self, path: &RelativePath) -> Result<RelativePath, DomainError> {
+        RelativePath::parse(&self.try_render(path.as_str())?)
+    }
+
+    /// Render template file content, reusing its parsed form.
+    ///
+    /// Same semantics as [`try_render`](Self::try_render), but the AST built
+    /// for `source` is cached, so a source rendered repeatedly (several
+    /// projects, overlays sharing a base) is only tokenized once.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`try_render`](Self::try_render).
+    pub fn render_source(&self, source: &TemplateSource) -> Result<String, DomainError> {
+        crate::domain::render::render_source(source, &self.variables, self.strict)
+    }
+}
+
+// ============================================================================
+// Run Information
+// ============================================================================
+
+/// Values that differ from one `scarff new` run to the next.
+///
+/// Gathered by the application layer (clock, git config, RNG) and injected
+/// into the [`RenderContext`] with [`RenderContext::with_run_info`]. Pinning
+/// a `RunInfo` makes rendering fully reproducible.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunInfo {
+    /// Version of scarff generating the project.
+    pub scarff_version: String,
+    /// Generation time; drives `YEAR` and `GENERATED_AT`.
+    pub generated_at: SystemTime,
+    /// git `user.name`, if configured.
+    pub git_user_name: Option<String>,
+    /// git `user.email`, if configured.
+    pub git_user_email: Option<String>,
+    /// Short random identifier (8 hex digits).
+    pub random_id: String,
+}
 
-        // Single-pass replacement. Order doesn't matter for independent variables.
-        for (key, value) in &self.variables {
-            let placeholder = format!("{{{{{key}}}}}");
-            result = result.replace(&placeholder, value);
+impl RunInfo {
+    /// Current time, this crate's version, a fresh random id and no git
+    /// identity (reading git config is I/O, left to adapters).
+    pub fn now() -> Self {
+        Self {
+            scarff_version: env!("CARGO_PKG_VERSION").to_string(),
+            generated_at: SystemTime::now(),
+            git_user_name: None,
+            git_user_email: None,
+            random_id: Uuid::new_v4().simple().to_string()[..8].to_string(),
         }
+    }
+}
+
+/// A [`SystemTime`] broken down into UTC calendar fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct UtcTimestamp {
+    year: i64,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+}
 
-        result
+impl From<SystemTime> for UtcTimestamp {
+    fn from(time: SystemTime) -> Self {
+        // Times before the epoch clamp to it; no template cares about 1969.
+        let secs = time
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs() as i64);
+        let (days, rem) = (secs.div_euclid(86_400), secs.rem_euclid(86_400));
+
+        // Days since 1970-01-01 to civil date (Howard Hinnant's algorithm).
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = yoe + era * 400 + i64::from(month <= 2);
+
+        Self {
+            year,
+            month,
+            day,
+            hour: (rem / 3_600) as u32,
+            minute: (rem % 3_600 / 60) as u32,
+            second: (rem % 60) as u32,
+        }
+    }
+}
+
+impl fmt::Display for UtcTimestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+}
+
+// ============================================================================
+// Render Values
+// ============================================================================
+
+/// A typed render variable.
+///
+/// ## Conversions
+///
+/// `&str`/`String` become [`RenderValue::String`], `Vec<T>` becomes
+/// [`RenderValue::List`] and `BTreeMap<String, T>` becomes [`RenderValue::Map`],
+/// so most call sites never name the enum:
+///
+/// ```rust,ignore
+/// ctx.with_variable("MODULES", vec!["users", "orders"]);
+/// ```
+///
+/// ## Display
+///
+/// Substituting a list with `{{NAME}}` yields its items joined by `, `;
+/// a map yields `key=value` pairs joined the same way.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RenderValue {
+    String(String),
+    List(Vec<RenderValue>),
+    Map(BTreeMap<String, RenderValue>),
+}
+
+impl RenderValue {
+    /// Borrow the string if this is a [`RenderValue::String`].
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Items visited by `{{#each}}`, as `(key, value)` pairs.
+    ///
+    /// - Lists yield their items with no key.
+    /// - Maps yield their entries in key order.
+    /// - Strings are split on commas (trimmed, empty items skipped), which
+    ///   lets `--var modules=users,orders` be iterated directly.
+    pub fn iter_items(&self) -> Vec<(Option<&str>, RenderValue)> {
+        match self {
+            Self::List(items) => items.iter().map(|v| (None, v.clone())).collect(),
+            Self::Map(entries) => entries
+                .iter()
+                .map(|(k, v)| (Some(k.as_str()), v.clone()))
+                .collect(),
+            Self::String(s) => s
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(|item| (None, Self::from(item)))
+                .collect(),
+        }
+    }
+
+    /// Empty strings, lists and maps are empty.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Self::String(s) => s.is_empty(),
+            Self::List(items) => items.is_empty(),
+            Self::Map(entries) => entries.is_empty(),
+        }
+    }
+}
+
+impl fmt::Display for RenderValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::String(s) => f.write_str(s),
+            Self::List(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                Ok(())
+            }
+            Self::Map(entries) => {
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{key}={value}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl From<String> for RenderValue {
+    fn from(s: String) -> Self {
+        Self::String(s)
+    }
+}
+
+impl From<&str> for RenderValue {
+    fn from(s: &str) -> Self {
+        Self::String(s.to_string())
+    }
+}
+
+impl From<&String> for RenderValue {
+    fn from(s: &String) -> Self {
+        Self::String(s.clone())
+    }
+}
+
+impl From<bool> for RenderValue {
+    fn from(b: bool) -> Self {
+        Self::String(b.to_string())
+    }
+}
+
+impl<T: Into<RenderValue>> From<Vec<T>> for RenderValue {
+    fn from(items: Vec<T>) -> Self {
+        Self::List(items.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T: Into<RenderValue>> From<BTreeMap<String, T>> for RenderValue {
+    fn from(entries: BTreeMap<String, T>) -> Self {
+        Self::Map(entries.into_iter().map(|(k, v)| (k, v.into())).collect())
     }
 }
 
@@ -330,7 +747,7 @@ impl RenderContext {
 /// | "my-app" | "my_app" |
 /// | "HTTPRequest" | "http_request" |
 /// | "XMLHttpRequest" | "xml_http_request" |
-fn to_snake_case(s: &str) -> String {
+pub(crate) fn to_snake_case(s: &str) -> String {
     split_words(s).join("_")
 }
 
@@ -340,7 +757,7 @@ fn to_snake_case(s: &str) -> String {
 ///
 /// Same as `to_snake_case` but joins with `-` instead of `_`.
 /// Used for package names, directory names, and CLI tools.
-fn to_kebab_case(s: &str) -> String {
+pub(crate) fn to_kebab_case(s: &str) -> String {
     split_words(s).join("-")
 }
 
@@ -358,25 +775,55 @@ fn to_kebab_case(s: &str) -> String {
 /// |-------|--------|
 /// | "my-app" | "MyApp" |
 /// | "HTTPRequest" | "HttpRequest" |
-fn to_pascal_case(s: &str) -> String {
+pub(crate) fn to_pascal_case(s: &str) -> String {
+    split_words(s).iter().map(|w| capitalize(w)).collect()
+}
+
+/// Convert a string to camelCase.
+///
+/// Same as `to_pascal_case` except the first word stays lowercase:
+/// "my-app" → "myApp", "HTTPRequest" → "httpRequest".
+pub(crate) fn to_camel_case(s: &str) -> String {
     split_words(s)
-        .into_iter()
-        .map(|w| {
-            let mut chars = w.chars();
-            match chars.next() {
-                Some(first) => {
-                    let mut out = String::new();
-                    // to_uppercase handles Unicode correctly (e.g., "ß" -> "SS")
-                    out.extend(first.to_uppercase());
-                    out.push_str(chars.as_str());
-                    out
-                }
-                None => String::new(),
-            }
-        })
+        .iter()
+        .enumerate()
+        .map(|(i, w)| if i == 0 { w.clone() } else { capitalize(w) })
         .collect()
 }
 
+/// Convert a string to SCREAMING_SNAKE_CASE (constants, env vars).
+///
+/// "my-app" → "MY_APP", "HTTPRequest" → "HTTP_REQUEST".
+pub(crate) fn to_screaming_snake_case(s: &str) -> String {
+    to_snake_case(s).to_uppercase()
+}
+
+/// Convert a string to Title Case (headings, display names).
+///
+/// "my_awesome-app" → "My Awesome App".
+pub(crate) fn to_title_case(s: &str) -> String {
+    split_words(s)
+        .iter()
+        .map(|w| capitalize(w))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Uppercase the first character of a (lowercase) word.
+pub(crate) fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => {
+            let mut out = String::new();
+            // to_uppercase handles Unicode correctly (e.g., "ß" -> "SS")
+            out.extend(first.to_uppercase());
+            out.push_str(chars.as_str());
+            out
+        }
+        None => String::new(),
+    }
+}
+
 /// Split a string into words based on casing and separators.
 ///
 /// ## Word Boundary Detection
@@ -395,7 +842,7 @@ fn to_pascal_case(s: &str) -> String {
 /// - `MyAwesomeApp` (PascalCase)
 /// - `XMLHttpRequest` (acronyms)
 /// - `my HTTP request` (natural language)
-fn split_words(input: &str) -> Vec<String> {
+pub(crate) fn split_words(input: &str) -> Vec<String> {
     let mut words = Vec::new();
     let mut current = String::new();
 
@@ -593,6 +1040,12 @@ impl TemplateRecord {
 /// 2. `metadata.name` is non-empty (human-readable display name)
 /// 3. `tree` is non-empty (templates must create at least one file/dir)
 /// 4. All paths in `tree` are unique (no duplicate files or dirs)
+/// 5. Declared variables are well-formed and uniquely named, and `when`
+///    conditions only reference variables declared before them
+/// 6. An overlay has a name
+/// 7. Every hook names a command
+/// 8. File, directory and hook `when` conditions only reference declared
+///    variables and built-ins (for overlays, see [`check_conditions`](Self::check_conditions))
 ///
 /// ## Lifecycle
 ///
@@ -614,6 +1067,82 @@ pub struct Template {
 
     /// The actual content: files and directories to create
     pub tree: TemplateTree,
+
+    /// Whether this template may be rendered strictly (the default).
+    ///
+    /// Authors whose files intentionally contain `{{…}}` text that looks like
+    /// a placeholder can opt out with `strict = false` in `template.toml`;
+    /// unresolved placeholders are then left in the output as-is.
+    pub strict: bool,
+
+    /// Template language used for parameterized files (`engine` in
+    /// `template.toml`).
+    pub engine: RenderEngine,
+
+    /// Inputs the template declares (`[[variables]]` in `template.toml`),
+    /// bound with [`bind_variables`](Self::bind_variables) before rendering.
+    pub variables: Vec<VariableSpec>,
+
+    /// Tidy-up rules for rendered files (`[[format]]` in `template.toml`),
+    /// applied by the service's post-processors.
+    pub format: Vec<FormatRule>,
+
+    /// Template this one was built on (`extends` in `template.toml`).
+    ///
+    /// Still set after [`extend`](Self::extend) has merged the base in, to
+    /// record where the files came from.
+    pub extends: Option<TemplateId>,
+
+    /// Set for overlays (`[overlay]` in `template.toml`): templates layered
+    /// onto the base template with `--with`, never resolved as the base.
+    pub overlay: Option<Overlay>,
+
+    /// Commands run in the project after it is written (`[[hooks.post]]`
+    /// in `template.toml`), in order.
+    pub post_hooks: Vec<Hook>,
+}
+
+/// Template language a template's parameterized files are written in.
+///
+/// The domain only records the choice; renderers in the adapters layer
+/// implement the languages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum RenderEngine {
+    /// Scarff's built-in syntax: `{{NAME}}`, `{{#if}}`, `{{#each}}`, filters.
+    #[default]
+    Simple,
+
+    /// Jinja2 syntax (macros, includes, whitespace control), via MiniJinja.
+    Jinja,
+}
+
+impl RenderEngine {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Simple => "simple",
+            Self::Jinja => "jinja",
+        }
+    }
+}
+
+impl fmt::Display for RenderEngine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for RenderEngine {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "simple" => Ok(Self::Simple),
+            "jinja" | "minijinja" | "jinja2" => Ok(Self::Jinja),
+            other => Err(DomainError::InvalidTemplate(format!(
+                "unknown template engine '{other}'; expected 'simple' or 'jinja'"
+            ))),
+        }
+    }
 }
 
 impl Template {
@@ -632,6 +1161,67 @@ impl Template {
         TemplateBuilder::default()
     }
 
+    /// Merge this template onto `base`, which it [`extends`](Self::extends).
+    ///
+    /// The result has this template's identity, matcher, metadata and
+    /// settings (including whether it is an overlay), and:
+    ///
+    /// - **files and directories:** the base's, minus those under a path in
+    ///   `remove`; a node with the same path as a base node replaces it in
+    ///   place, new paths are added after the base's
+    /// - **variables:** the base's, then this template's; redeclaring a base
+    ///   variable replaces it in place
+    /// - **`[[format]]` rules:** the base's, then this template's
+    /// - **hooks:** the base's, then this template's
+    ///
+    /// Validate the result; neither side need be valid on its own.
+    ///
+    /// # Errors
+    ///
+    /// [`DomainError::InvalidTemplate`] if the engines differ or a `remove`
+    /// path is not in the base.
+    pub fn extend(mut self, base: &Template, remove: &[RelativePath]) -> Result<Self, DomainError> {
+        if self.engine != base.engine {
+            return Err(DomainError::InvalidTemplate(format!(
+                "'{}' uses engine '{}' but its base '{}' uses '{}'",
+                self.id, self.engine, base.id, base.engine
+            )));
+        }
+        if let Some(missing) = remove.iter().find(|path| {
+            !base
+                .tree
+                .nodes
+                .iter()
+                .any(|node| node.path().as_path().starts_with(path.as_path()))
+        }) {
+            return Err(DomainError::InvalidTemplate(format!(
+                "'{}' removes '{missing}', which its base '{}' does not have",
+                self.id, base.id
+            )));
+        }
+
+        self.tree = base.tree.extended(&self.tree, remove);
+
+        let mut variables = base.variables.clone();
+        for variable in self.variables {
+            match variables.iter_mut().find(|v| v.name == variable.name) {
+                Some(slot) => *slot = variable,
+                None => variables.push(variable),
+            }
+        }
+        self.variables = variables;
+
+        self.format = base.format.iter().cloned().chain(self.format).collect();
+        self.post_hooks = base
+            .post_hooks
+            .iter()
+            .cloned()
+            .chain(self.post_hooks)
+            .collect();
+        self.extends = Some(base.id.clone());
+        Ok(self)
+    }
+
     /// Validate all invariants.
     ///
     /// Should be called before persisting or using a template. The `TemplateEngine`
@@ -673,9 +1263,207 @@ impl Template {
             }
         }
 
+        // Invariant 5: Variable declarations are usable
+        let declared: HashSet<&str> = self.variables.iter().map(|v| v.name.as_str()).collect();
+        let mut names = HashSet::new();
+        for variable in &self.variables {
+            // Prompts ask in declaration order, so a condition can only
+            // depend on answers already given.
+            if let Some(when) = &variable.when
+                && let Some(later) = when
+                    .variables()
+                    .into_iter()
+                    .find(|v| declared.contains(v) && !names.contains(v))
+            {
+                return Err(DomainError::InvalidTemplate(format!(
+                    "variable '{}': condition '{when}' references '{later}', which is not declared before it",
+                    variable.name
+                )));
+            }
+            if !names.insert(variable.name.as_str()) {
+                return Err(DomainError::InvalidTemplate(format!(
+                    "variable '{}' is declared more than once",
+                    variable.name
+                )));
+            }
+            variable.validate().map_err(DomainError::InvalidTemplate)?;
+        }
+
+        // Invariant 6: Overlays are selected by name
+        if self.overlay.as_ref().is_some_and(|o| o.name.is_empty()) {
+            return Err(DomainError::InvalidTemplate(
+                "Overlay name cannot be empty".into(),
+            ));
+        }
+
+        // Invariant 7: Hooks have something to run
+        if self.post_hooks.iter().any(|h| h.command.trim().is_empty()) {
+            return Err(DomainError::InvalidTemplate(
+                "Hook command cannot be empty".into(),
+            ));
+        }
+
+        // Invariant 8: Conditions can be evaluated. An overlay's may refer
+        // to the variables of the template it is layered on, so those are
+        // checked once the layers are known.
+        if self.overlay.is_none() {
+            self.check_conditions(|name| declared.contains(name))?;
+        }
+
         Ok(())
     }
 
+    /// Check that every file, directory and hook `when` condition only
+    /// references variables for which `declared` holds, or built-ins.
+    ///
+    /// A typo in a condition would otherwise silently read as false.
+    pub fn check_conditions(&self, declared: impl Fn(&str) -> bool) -> Result<(), DomainError> {
+        let nodes = self.tree.nodes.iter().filter_map(|node| {
+            let when = match node {
+                TemplateNode::File(spec) => spec.when.as_ref()?,
+                TemplateNode::Directory(spec) => spec.when.as_ref()?,
+            };
+            Some((format!("'{}'", node.path().as_str()), when))
+        });
+        let hooks = self
+            .post_hooks
+            .iter()
+            .filter_map(|hook| Some((format!("hook '{}'", hook.command), hook.when.as_ref()?)));
+
+        for (owner, when) in nodes.chain(hooks) {
+            if let Some(unknown) = when
+                .variables()
+                .into_iter()
+                .find(|v| !declared(v) && !RenderContext::BUILTIN_VARIABLES.contains(v))
+            {
+                return Err(DomainError::InvalidTemplate(format!(
+                    "{owner}: condition '{when}' references '{unknown}', which is neither a declared variable nor a built-in"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Validate `answers` against the declared variables and add them, plus
+    /// the defaults for everything unanswered, to `context`.
+    ///
+    /// Answers for names the template does not declare are ignored here.
+    ///
+    /// # Errors
+    ///
+    /// [`DomainError::InvalidVariables`] listing every missing or invalid
+    /// value, so nothing is rendered from a partially valid context.
+    pub fn bind_variables(
+        &self,
+        context: RenderContext,
+        answers: &HashMap<String, RenderValue>,
+    ) -> Result<RenderContext, DomainError> {
+        super::variable::bind(&self.variables, answers, context)
+    }
+
+    /// The combined [`FormatOptions`] of every `[[format]]` rule matching
+    /// `path`.
+    pub fn format_options(&self, path: &Path) -> FormatOptions {
+        format_options_for(&self.format, path)
+    }
+
+    /// Whether this template is an overlay rather than a base template.
+    pub fn is_overlay(&self) -> bool {
+        self.overlay.is_some()
+    }
+
+    /// Stable SHA-256 of what the template writes and runs, formatted as
+    /// `sha256:<hex>`: its id, files and directories (paths, contents,
+    /// conditions, executable bits), variable defaults and hooks.
+    ///
+    /// External content is fetched through `external` and hashed as it is
+    /// now, not just by id, since `file:` and `workspace:` content lives
+    /// outside the template. The digest therefore changes whenever the
+    /// template's output or commands can change, so a decision tied to it
+    /// (trusting its hooks) can be revisited.
+    ///
+    /// # Errors
+    ///
+    /// Those of `external`.
+    pub fn digest<E>(
+        &self,
+        mut external: impl FnMut(&ContentTemplateId) -> Result<TemplateSource, E>,
+    ) -> Result<String, E> {
+        // Length-prefixed fields keep the encoding unambiguous.
+        let mut hasher = Sha256::new();
+        let mut field = |bytes: &[u8]| {
+            hasher.update((bytes.len() as u64).to_le_bytes());
+            hasher.update(bytes);
+        };
+        let condition =
+            |when: &Option<Condition>| when.as_ref().map_or("", Condition::as_str).to_string();
+
+        field(self.id.to_string().as_bytes());
+        field(self.engine.as_str().as_bytes());
+        let mut nodes: Vec<&TemplateNode> = self.tree.nodes.iter().collect();
+        nodes.sort_by(|a, b| a.path().as_str().cmp(b.path().as_str()));
+        for node in nodes {
+            match node {
+                TemplateNode::File(f) => {
+                    let exec = if f.permissions.executable_flag() {
+                        "X"
+                    } else {
+                        "F"
+                    };
+                    field(exec.as_bytes());
+                    field(f.path.as_str().as_bytes());
+                    field(condition(&f.when).as_bytes());
+                    match &f.content {
+                        TemplateContent::Literal(source) => {
+                            field(b"literal");
+                            field(source.as_str().as_bytes());
+                        }
+                        TemplateContent::Parameterized(source) => {
+                            field(b"parameterized");
+                            field(source.as_str().as_bytes());
+                        }
+                        TemplateContent::External(id) => {
+                            field(b"external");
+                            field(id.to_string().as_bytes());
+                            field(external(id)?.as_str().as_bytes());
+                        }
+                        TemplateContent::Binary(bytes) => {
+                            field(b"binary");
+                            field(bytes.as_bytes());
+                        }
+                    }
+                }
+                TemplateNode::Directory(d) => {
+                    field(b"D");
+                    field(d.path.as_str().as_bytes());
+                    field(condition(&d.when).as_bytes());
+                }
+            }
+        }
+        for variable in &self.variables {
+            field(variable.name.as_bytes());
+            let default = variable.default.as_ref().map(ToString::to_string);
+            field(default.unwrap_or_default().as_bytes());
+        }
+        for hook in &self.post_hooks {
+            field(hook.command.as_bytes());
+            field(&(hook.args.len() as u64).to_le_bytes());
+            for arg in &hook.args {
+                field(arg.as_bytes());
+            }
+            let dir = hook.working_dir.as_ref().map_or("", RelativePath::as_str);
+            field(dir.as_bytes());
+            field(condition(&hook.when).as_bytes());
+        }
+
+        let hex: String = hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        Ok(format!("sha256:{hex}"))
+    }
+
     /// Check if this template matches a target.
     ///
     /// Delegates to `TargetMatcher::matches`. Convenience method for
@@ -715,6 +1503,13 @@ pub struct TemplateBuilder {
     matcher: Option<TargetMatcher>,
     metadata: Option<TemplateMetadata>,
     tree: TemplateTree,
+    strict: Option<bool>,
+    engine: RenderEngine,
+    variables: Vec<VariableSpec>,
+    format: Vec<FormatRule>,
+    extends: Option<TemplateId>,
+    overlay: Option<Overlay>,
+    post_hooks: Vec<Hook>,
 }
 
 impl TemplateBuilder {
@@ -733,6 +1528,49 @@ impl TemplateBuilder {
         self
     }
 
+    /// Allow or forbid strict rendering (default: allowed).
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = Some(strict);
+        self
+    }
+
+    /// Select the template language (default: [`RenderEngine::Simple`]).
+    pub fn engine(mut self, engine: RenderEngine) -> Self {
+        self.engine = engine;
+        self
+    }
+
+    /// Declare a variable (accumulates).
+    pub fn variable(mut self, variable: VariableSpec) -> Self {
+        self.variables.push(variable);
+        self
+    }
+
+    /// Declare the base template; the tree may then be empty until the base
+    /// is merged in with [`Template::extend`].
+    pub fn extends(mut self, base: TemplateId) -> Self {
+        self.extends = Some(base);
+        self
+    }
+
+    /// Make the template an overlay.
+    pub fn overlay(mut self, overlay: Overlay) -> Self {
+        self.overlay = Some(overlay);
+        self
+    }
+
+    /// Add a `[[format]]` rule (accumulates; later rules win).
+    pub fn format_rule(mut self, rule: FormatRule) -> Self {
+        self.format.push(rule);
+        self
+    }
+
+    /// Add a post-generation hook (accumulates; run in order).
+    pub fn post_hook(mut self, hook: Hook) -> Self {
+        self.post_hooks.push(hook);
+        self
+    }
+
     /// Set the entire tree at once (replaces any previous nodes).
     pub fn tree(mut self, tree: TemplateTree) -> Self {
         self.tree = tree;
@@ -752,8 +1590,9 @@ impl TemplateBuilder {
     /// - `MissingRequiredField` if id/matcher/metadata not set
     /// - `InvalidTemplate` if tree is empty
     pub fn build(self) -> Result<Template, DomainError> {
-        // Early validation: empty tree is always wrong
-        if self.tree.is_empty() {
+        // Early validation: empty tree is always wrong, unless the files all
+        // come from a base template.
+        if self.tree.is_empty() && self.extends.is_none() {
             return Err(DomainError::InvalidTemplate(
                 "Template tree cannot be empty".into(),
             ));
@@ -770,6 +1609,13 @@ impl TemplateBuilder {
                 .metadata
                 .ok_or(DomainError::MissingRequiredField { field: "metadata" })?,
             tree: self.tree,
+            strict: self.strict.unwrap_or(true),
+            engine: self.engine,
+            variables: self.variables,
+            format: self.format,
+            extends: self.extends,
+            overlay: self.overlay,
+            post_hooks: self.post_hooks,
         })
     }
 }
@@ -1074,6 +1920,59 @@ impl TemplateTree {
     pub fn len(&self) -> usize {
         self.nodes.len()
     }
+
+    /// `self` as a base with `overrides` layered on top: nodes under a path
+    /// in `remove` are dropped, an override with the same path as a base
+    /// node takes its place, and the remaining overrides are appended.
+    pub fn extended(&self, overrides: &TemplateTree, remove: &[RelativePath]) -> TemplateTree {
+        let mut nodes: Vec<TemplateNode> = self
+            .nodes
+            .iter()
+            .filter(|node| {
+                !remove
+                    .iter()
+                    .any(|path| node.path().as_path().starts_with(path.as_path()))
+            })
+            .cloned()
+            .collect();
+
+        for node in &overrides.nodes {
+            match nodes.iter_mut().find(|n| n.path() == node.path()) {
+                Some(slot) => *slot = node.clone(),
+                None => nodes.push(node.clone()),
+            }
+        }
+        TemplateTree { nodes }
+    }
+
+    /// The nodes to emit for `context`: those whose `when` condition holds
+    /// and that are not inside a directory whose condition is false.
+    pub fn nodes_for(&self, context: &RenderContext) -> Vec<&TemplateNode> {
+        let excluded: Vec<&Path> = self
+            .nodes
+            .iter()
+            .filter_map(|node| match node {
+                TemplateNode::Directory(spec)
+                    if spec.when.as_ref().is_some_and(|c| !c.evaluate(context)) =>
+                {
+                    Some(spec.path.as_path())
+                }
+                _ => None,
+            })
+            .collect();
+
+        self.nodes
+            .iter()
+            .filter(|node| {
+                let (path, when) = match node {
+                    TemplateNode::File(spec) => (spec.path.as_path(), &spec.when),
+                    TemplateNode::Directory(spec) => (spec.path.as_path(), &spec.when),
+                };
+                when.as_ref().is_none_or(|c| c.evaluate(context))
+                    && !excluded.iter().any(|dir| path.starts_with(dir))
+            })
+            .collect()
+    }
 }
 
 /// A single node in the template tree: either a file or directory.
@@ -1082,13 +1981,22 @@ impl TemplateTree {
 ///
 /// - `Symlink(RelativePath, RelativePath)` - for shared configs
 /// - `Command(String)` - run shell command during generation
-/// - `Conditional { condition: String, node: Box<TemplateNode> }` - feature-gated
 #[derive(Debug, Clone)]
 pub enum TemplateNode {
     File(FileSpec),
     Directory(DirectorySpec),
 }
 
+impl TemplateNode {
+    /// Where the node is created, relative to the project root.
+    pub fn path(&self) -> &RelativePath {
+        match self {
+            Self::File(spec) => &spec.path,
+            Self::Directory(spec) => &spec.path,
+        }
+    }
+}
+
 /// Specification for a file to create.
 ///
 /// ## Content Types
@@ -1115,6 +2023,9 @@ pub struct FileSpec {
 
     /// Unix-style permissions (e.g., 0o644 for files, 0o755 for executables)
     pub permissions: Permissions,
+
+    /// Emit the file only when this holds (see [`TemplateTree::nodes_for`]).
+    pub when: Option<Condition>,
 }
 
 impl FileSpec {
@@ -1124,6 +2035,7 @@ impl FileSpec {
             path: path.into(),
             content,
             permissions: Permissions::read_write(),
+            when: None,
         }
     }
 
@@ -1132,6 +2044,12 @@ impl FileSpec {
         self.permissions = Permissions::executable();
         self
     }
+
+    /// Emit this file only when `condition` holds.
+    pub fn when(mut self, condition: Condition) -> Self {
+        self.when = Some(condition);
+        self
+    }
 }
 
 /// Specification for a directory to create.
@@ -1144,6 +2062,8 @@ impl FileSpec {
 pub struct DirectorySpec {
     pub path: RelativePath,
     pub permissions: Permissions,
+    /// Emit the directory, and everything under it, only when this holds.
+    pub when: Option<Condition>,
 }
 
 impl DirectorySpec {
@@ -1151,8 +2071,15 @@ impl DirectorySpec {
         Self {
             path: path.into(),
             permissions: Permissions::read_write(),
+            when: None,
         }
     }
+
+    /// Emit this directory and its contents only when `condition` holds.
+    pub fn when(mut self, condition: Condition) -> Self {
+        self.when = Some(condition);
+        self
+    }
 }
 
 // ============================================================================
@@ -1172,6 +2099,9 @@ impl DirectorySpec {
 /// - Remote content (fetch latest CoC from GitHub)
 /// - Large content without bloating the template definition
 ///
+/// **Binary:** Bytes that are not UTF-8 text (images, fonts, `.jar`
+/// wrappers). Always copied verbatim.
+///
 /// ## Future: Caching for External
 ///
 /// External content should be cached with TTL to avoid network calls on every
@@ -1186,9 +2116,12 @@ pub enum TemplateContent {
 
     /// Reference to external content fetched at render time.
     ///
-    /// The [`ContentTemplateId`] is resolved by the [`TemplateEngine`] implementation
-    /// (e.g., looking up in a registry or cache).
+    /// The [`ContentTemplateId`] is resolved by the renderer's content
+    /// resolvers, then rendered like parameterized content.
     External(ContentTemplateId),
+
+    /// Non-text content copied byte for byte.
+    Binary(BinarySource),
 }
 
 /// Source of template content: either compile-time or runtime.
@@ -1198,12 +2131,23 @@ pub enum TemplateContent {
 /// `Static` references binary data (zero-cost). `Owned` allocates for dynamic
 /// content (filesystem-loaded, network-fetched, user-provided).
 ///
+/// ## Parsed Once
+///
+/// The AST the rendering engine parses from the text is kept on the source
+/// (and shared by its clones), so rendering it again skips the tokenizer.
+///
 /// ## Future: Shared Ownership
 ///
-/// May add `Cached(Arc<str>)` for content shared across multiple templates
+/// May add `Arc<str>` storage for content shared across multiple templates
 /// (e.g., a standard README used by 50 templates, stored once in memory).
+#[derive(Clone)]
+pub struct TemplateSource {
+    text: SourceText,
+    parsed: OnceLock<Parsed>,
+}
+
 #[derive(Debug, Clone)]
-pub enum TemplateSource {
+enum SourceText {
     /// Compile-time string literal (e.g., `include_str!("template.rs")`)
     Static(&'static str),
 
@@ -1213,31 +2157,85 @@ pub enum TemplateSource {
 
 impl From<&'static str> for TemplateSource {
     fn from(s: &'static str) -> Self {
-        Self::Static(s)
+        Self::new(SourceText::Static(s))
     }
 }
 
 impl From<String> for TemplateSource {
     fn from(s: String) -> Self {
-        Self::Owned(s)
+        Self::new(SourceText::Owned(s))
+    }
+}
+
+impl fmt::Debug for TemplateSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("TemplateSource").field(&self.text).finish()
     }
 }
 
 impl TemplateSource {
+    fn new(text: SourceText) -> Self {
+        Self {
+            text,
+            parsed: OnceLock::new(),
+        }
+    }
+
     /// Get string slice regardless of storage type.
     pub fn as_str(&self) -> &str {
-        match self {
-            Self::Static(s) => s,
-            Self::Owned(s) => s,
+        match &self.text {
+            SourceText::Static(s) => s,
+            SourceText::Owned(s) => s,
         }
     }
 
-    /// Check if content contains placeholder syntax (quick heuristic).
+    /// The AST parsed from this source, once.
+    pub(crate) fn parsed(&self, parse: impl FnOnce(&str) -> Parsed) -> Parsed {
+        self.parsed.get_or_init(|| parse(self.as_str())).clone()
+    }
+
+    /// Check if content contains syntax the renderer acts on.
     ///
     /// Used by loaders to auto-detect if a file should be `Parameterized`
     /// vs `Literal` when the manifest doesn't specify.
+    ///
+    /// Uses the renderer's own tokenizer, so foreign `{{ … }}` syntax (Helm's
+    /// `{{ .Values.x }}`) does not count, while escapes (`\{{`) and
+    /// `{{{{raw}}}}` blocks do: they must be rendered to be unescaped.
     pub fn contains_placeholder(&self) -> bool {
-        self.as_str().contains("{{") && self.as_str().contains("}}")
+        crate::domain::render::contains_syntax(self.as_str())
+    }
+}
+
+/// Source of binary file content; the byte counterpart of [`TemplateSource`].
+#[derive(Debug, Clone)]
+pub enum BinarySource {
+    /// Compile-time bytes (e.g., `include_bytes!("favicon.ico")`)
+    Static(&'static [u8]),
+
+    /// Runtime-owned bytes (heap-allocated)
+    Owned(Vec<u8>),
+}
+
+impl From<&'static [u8]> for BinarySource {
+    fn from(bytes: &'static [u8]) -> Self {
+        Self::Static(bytes)
+    }
+}
+
+impl From<Vec<u8>> for BinarySource {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::Owned(bytes)
+    }
+}
+
+impl BinarySource {
+    /// Get the bytes regardless of storage type.
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            Self::Static(bytes) => bytes,
+            Self::Owned(bytes) => bytes,
+        }
     }
 }
 
@@ -1245,18 +2243,33 @@ impl TemplateSource {
 ///
 /// ## Format
 ///
-/// Currently a simple string. Future versions may use URIs:
-/// - `builtin:license-mit` - built into scarff binary
-/// - `remote:github.com/scarff/templates/license-mit` - fetch from GitHub
-/// - `workspace:shared-readme` - from workspace shared templates
+/// `scheme:key`, where the scheme selects the resolver:
+/// - `builtin:mit` - snippet embedded in the scarff binary
+/// - `file:licenses/acme.txt` - file relative to the templates root
+/// - `workspace:readme.md` - shared content in the templates root's `_shared/`
 ///
 /// ## Resolution
 ///
-/// The [`TemplateEngine`] implementation maintains a registry of resolvers:
-/// ```rust,ignore
-/// trait ContentResolver {
-///     fn resolve(&self, id: &ContentTemplateId) -> Result<String, Error>;
-/// }
-/// ```
+/// Renderers hand the id to a registry of
+/// [`ContentResolver`](crate::application::ports::ContentResolver)s and
+/// dispatch on [`scheme`](Self::scheme).
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ContentTemplateId(pub &'static str);
+
+impl ContentTemplateId {
+    /// The part before the first `:`, or `None` for ids without a scheme.
+    pub fn scheme(&self) -> Option<&'static str> {
+        self.0.split_once(':').map(|(scheme, _)| scheme)
+    }
+
+    /// The part after the first `:` (the whole id if there is no scheme).
+    pub fn key(&self) -> &'static str {
+        self.0.split_once(':').map_or(self.0, |(_, key)| key)
+    }
+}
+
+impl fmt::Display for ContentTemplateId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
@@ -0,0 +1,656 @@
+//! Template-declared variables (`[[variables]]` in `template.toml`).
+//!
+//! A template lists the inputs it needs beyond the built-ins:
+//!
+//! ```toml
+//! [[variables]]
+//! name    = "db"
+//! type    = "choice"
+//! choices = ["postgres", "sqlite", "none"]
+//! default = "postgres"
+//!
+//! [[variables]]
+//! name    = "port"
+//! type    = "int"
+//! default = 3000
+//! min     = 1
+//! max     = 65535
+//!
+//! [[variables]]
+//! name    = "db_name"
+//! default = "{{PROJECT_NAME_SNAKE}}_{{db}}"   # defaults may reference other variables
+//!
+//! [[variables]]
+//! name    = "author_email"
+//! pattern = '^[^@\s]+@[^@\s]+$'
+//!
+//! [[variables]]
+//! name = "db_pool_size"
+//! type = "int"
+//! default = 10
+//! when = "db != 'none'"                       # only asked for / bound when true
+//! ```
+//!
+//! [`Template::bind_variables`](super::template::Template::bind_variables)
+//! validates supplied answers, fills in defaults and adds every value to the
+//! [`RenderContext`], reporting *all* missing or invalid values in one
+//! [`DomainError::InvalidVariables`] before anything is rendered. A variable
+//! whose [`when`](VariableSpec::when) condition is false is skipped: it takes
+//! its empty value and any supplied answer is ignored.
+//!
+//! ## Value Representation
+//!
+//! Values are [`RenderValue`]s. `bool` normalizes to `"true"`/`"false"` (so
+//! `{{#if flag}}` works), `int` to its decimal form, and `list` to a
+//! [`RenderValue::List`]; a comma-separated string is accepted for lists.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use regex::Regex;
+
+use super::{
+    condition::Condition,
+    template::{RenderContext, RenderValue},
+};
+use crate::domain::error::DomainError;
+
+/// A variable a template declares.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariableSpec {
+    /// Name used in templates: `{{db}}`.
+    pub name: String,
+
+    /// Value type and type-specific constraints.
+    pub kind: VariableKind,
+
+    /// Help text shown to users.
+    pub description: Option<String>,
+
+    /// Used when no value is supplied. String items may contain
+    /// placeholders referencing built-ins or other variables.
+    pub default: Option<RenderValue>,
+
+    /// Whether a value (or default) is required. Optional variables without
+    /// one resolve to an empty value.
+    pub required: bool,
+
+    /// Regex every string value (every list item, for lists) must match in
+    /// full.
+    pub pattern: Option<VariablePattern>,
+
+    /// Only ask for and bind the variable when this holds. May reference
+    /// variables declared earlier.
+    pub when: Option<Condition>,
+}
+
+/// A [`VariableSpec::pattern`], compiled once when it is set.
+///
+/// A pattern that does not compile is kept with its error, for
+/// [`VariableSpec::validate`] to report.
+#[derive(Debug, Clone)]
+pub struct VariablePattern {
+    pattern: String,
+    regex: Result<Regex, String>,
+}
+
+impl VariablePattern {
+    /// Compile `pattern`, anchored so that it must match a whole value.
+    pub fn new(pattern: impl Into<String>) -> Self {
+        let pattern = pattern.into();
+        let regex = Regex::new(&format!("^(?:{pattern})$")).map_err(|e| e.to_string());
+        Self { pattern, regex }
+    }
+
+    /// The pattern as written.
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+}
+
+impl PartialEq for VariablePattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.pattern == other.pattern
+    }
+}
+
+impl Eq for VariablePattern {}
+
+impl fmt::Display for VariablePattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.pattern)
+    }
+}
+
+/// Type of a declared variable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VariableKind {
+    /// Free text.
+    String,
+    /// `true`/`false` (also accepts yes/no, on/off, 1/0).
+    Bool,
+    /// Whole number with optional inclusive bounds.
+    Int { min: Option<i64>, max: Option<i64> },
+    /// Exactly one of the listed values.
+    Choice(Vec<String>),
+    /// Zero or more items; restricted to `choices` if non-empty.
+    List { choices: Vec<String> },
+}
+
+impl VariableKind {
+    /// Name used in `template.toml` (`type = "..."`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::String => "string",
+            Self::Bool => "bool",
+            Self::Int { .. } => "int",
+            Self::Choice(_) => "choice",
+            Self::List { .. } => "list",
+        }
+    }
+}
+
+impl fmt::Display for VariableKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl VariableSpec {
+    /// A required string variable without default or constraints.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            kind: VariableKind::String,
+            description: None,
+            default: None,
+            required: true,
+            pattern: None,
+            when: None,
+        }
+    }
+
+    pub fn kind(mut self, kind: VariableKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn default_value(mut self, default: impl Into<RenderValue>) -> Self {
+        self.default = Some(default.into());
+        self
+    }
+
+    pub fn required(mut self, required: bool) -> Self {
+        self.required = required;
+        self
+    }
+
+    pub fn pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.pattern = Some(VariablePattern::new(pattern));
+        self
+    }
+
+    pub fn when(mut self, condition: Condition) -> Self {
+        self.when = Some(condition);
+        self
+    }
+
+    /// Whether the variable is in play given the values in `context`.
+    pub fn applies(&self, context: &RenderContext) -> bool {
+        self.when.as_ref().is_none_or(|c| c.evaluate(context))
+    }
+
+    /// The default with its placeholders rendered from `context`, as offered
+    /// to the user by prompts. `None` without a default, or while it still
+    /// references a variable `context` lacks.
+    pub fn default_in(&self, context: &RenderContext) -> Option<RenderValue> {
+        let rendered = render_default(self.default.as_ref()?, context).ok()?;
+        Some(self.parse(&rendered).unwrap_or(rendered))
+    }
+
+    /// Check the declaration itself: identifier name, compilable pattern,
+    /// non-empty choices, and a default that satisfies the constraints
+    /// (when it has no placeholders to resolve first).
+    pub fn validate(&self) -> Result<(), String> {
+        if !is_identifier(&self.name) {
+            return Err(format!(
+                "variable name '{}' must be letters, digits and underscores, not starting with a digit",
+                self.name
+            ));
+        }
+        if let Some(Err(e)) = self.pattern.as_ref().map(|p| &p.regex) {
+            return Err(format!("variable '{}': invalid pattern: {e}", self.name));
+        }
+        match &self.kind {
+            VariableKind::Choice(choices) if choices.is_empty() => {
+                return Err(format!(
+                    "variable '{}': type 'choice' needs at least one entry in `choices`",
+                    self.name
+                ));
+            }
+            VariableKind::Int {
+                min: Some(min),
+                max: Some(max),
+            } if min > max => {
+                return Err(format!(
+                    "variable '{}': min ({min}) is greater than max ({max})",
+                    self.name
+                ));
+            }
+            _ => {}
+        }
+        if let Some(default) = &self.default
+            && !has_placeholders(default)
+        {
+            self.parse(default)
+                .map_err(|e| format!("variable '{}': invalid default: {e}", self.name))?;
+        }
+        Ok(())
+    }
+
+    /// Validate and normalize a supplied value.
+    pub fn parse(&self, value: &RenderValue) -> Result<RenderValue, String> {
+        let value = match (&self.kind, value) {
+            (VariableKind::List { choices }, value) => {
+                let items: Vec<String> = value
+                    .iter_items()
+                    .into_iter()
+                    .map(|(_, item)| item.to_string().trim().to_string())
+                    .filter(|item| !item.is_empty())
+                    .collect();
+                for item in &items {
+                    if !choices.is_empty() && !choices.contains(item) {
+                        return Err(not_a_choice(item, choices));
+                    }
+                    self.check_pattern(item)?;
+                }
+                return Ok(RenderValue::List(
+                    items.into_iter().map(RenderValue::String).collect(),
+                ));
+            }
+            (_, RenderValue::String(s)) => s.trim(),
+            (kind, other) => {
+                return Err(format!("expected a single {kind} value, got '{other}'"));
+            }
+        };
+
+        let normalized = match &self.kind {
+            VariableKind::String => value.to_string(),
+            VariableKind::Bool => match value.to_ascii_lowercase().as_str() {
+                "true" | "yes" | "y" | "on" | "1" => "true".to_string(),
+                "false" | "no" | "n" | "off" | "0" => "false".to_string(),
+                _ => return Err(format!("'{value}' is not a boolean (use true or false)")),
+            },
+            VariableKind::Int { min, max } => {
+                let n: i64 = value
+                    .parse()
+                    .map_err(|_| format!("'{value}' is not a whole number"))?;
+                if let Some(min) = min
+                    && n < *min
+                {
+                    return Err(format!("{n} is less than the minimum {min}"));
+                }
+                if let Some(max) = max
+                    && n > *max
+                {
+                    return Err(format!("{n} is greater than the maximum {max}"));
+                }
+                n.to_string()
+            }
+            VariableKind::Choice(choices) => {
+                if !choices.iter().any(|c| c == value) {
+                    return Err(not_a_choice(value, choices));
+                }
+                value.to_string()
+            }
+            VariableKind::List { .. } => unreachable!("lists are handled above"),
+        };
+
+        if matches!(self.kind, VariableKind::String) {
+            self.check_pattern(&normalized)?;
+        }
+        Ok(RenderValue::String(normalized))
+    }
+
+    /// The value an optional variable without default takes.
+    fn empty_value(&self) -> RenderValue {
+        match self.kind {
+            VariableKind::List { .. } => RenderValue::List(Vec::new()),
+            _ => RenderValue::String(String::new()),
+        }
+    }
+
+    fn check_pattern(&self, value: &str) -> Result<(), String> {
+        let Some(pattern) = &self.pattern else {
+            return Ok(());
+        };
+        // `validate` has already rejected patterns that do not compile.
+        match &pattern.regex {
+            Ok(re) if re.is_match(value) => Ok(()),
+            Ok(_) => Err(format!("'{value}' does not match the pattern {pattern}")),
+            Err(e) => Err(format!("invalid pattern {pattern}: {e}")),
+        }
+    }
+}
+
+/// Resolve `specs` against `answers`, returning `context` extended with every
+/// declared variable.
+///
+/// Defaults and `when` conditions are evaluated after the variables they
+/// reference, whatever the declaration order. Every problem is collected
+/// before failing.
+pub(crate) fn bind(
+    specs: &[VariableSpec],
+    answers: &HashMap<String, RenderValue>,
+    mut context: RenderContext,
+) -> Result<RenderContext, DomainError> {
+    let mut problems = Vec::new();
+    // Variables already reported; defaults depending on them are skipped
+    // rather than reported a second time.
+    let mut failed: HashSet<&str> = HashSet::new();
+    let mut pending: Vec<&VariableSpec> = specs.iter().collect();
+
+    // Bind until no more progress is made; anything left waits on a variable
+    // that is itself unresolved (a cycle).
+    let declared: HashSet<&str> = specs.iter().map(|s| s.name.as_str()).collect();
+    loop {
+        let mut waiting = Vec::new();
+        let before = pending.len();
+
+        for spec in pending {
+            if let Some(when) = &spec.when {
+                let unbound: Vec<&str> = when
+                    .variables()
+                    .into_iter()
+                    .filter(|v| declared.contains(v) && context.value(v).is_none())
+                    .collect();
+                if unbound.iter().any(|v| failed.contains(v)) {
+                    failed.insert(&spec.name);
+                    continue;
+                }
+                if !unbound.is_empty() {
+                    waiting.push(spec);
+                    continue;
+                }
+                if !when.evaluate(&context) {
+                    context = context.with_variable(spec.name.clone(), spec.empty_value());
+                    continue;
+                }
+            }
+
+            if let Some(answer) = answers.get(&spec.name) {
+                match spec.parse(answer) {
+                    Ok(value) => context = context.with_variable(spec.name.clone(), value),
+                    Err(e) => {
+                        problems.push(format!("{}: {e}", spec.name));
+                        failed.insert(&spec.name);
+                    }
+                }
+                continue;
+            }
+
+            let Some(default) = &spec.default else {
+                if spec.required {
+                    problems.push(format!("{}: a value is required", spec.name));
+                    failed.insert(&spec.name);
+                } else {
+                    context = context.with_variable(spec.name.clone(), spec.empty_value());
+                }
+                continue;
+            };
+
+            match render_default(default, &context) {
+                Ok(rendered) => match spec.parse(&rendered) {
+                    Ok(value) => context = context.with_variable(spec.name.clone(), value),
+                    Err(e) => {
+                        problems.push(format!("{}: invalid default: {e}", spec.name));
+                        failed.insert(&spec.name);
+                    }
+                },
+                Err(missing) if missing.iter().any(|m| failed.contains(m.as_str())) => {
+                    failed.insert(&spec.name);
+                }
+                Err(missing) if missing.iter().all(|m| declared.contains(m.as_str())) => {
+                    waiting.push(spec);
+                }
+                Err(missing) => {
+                    problems.push(format!(
+                        "{}: default references undefined variable(s): {}",
+                        spec.name,
+                        missing.join(", ")
+                    ));
+                    failed.insert(&spec.name);
+                }
+            }
+        }
+
+        if waiting.is_empty() {
+            break;
+        }
+        if waiting.len() == before {
+            let names: Vec<&str> = waiting.iter().map(|s| s.name.as_str()).collect();
+            problems.push(format!(
+                "defaults of {} reference each other or a variable without a value",
+                names.join(", ")
+            ));
+            break;
+        }
+        pending = waiting;
+    }
+
+    if problems.is_empty() {
+        Ok(context)
+    } else {
+        Err(DomainError::InvalidVariables { problems })
+    }
+}
+
+/// Render every string in `default`; on unresolved placeholders, return their
+/// names.
+fn render_default(
+    default: &RenderValue,
+    context: &RenderContext,
+) -> Result<RenderValue, Vec<String>> {
+    let strict = context.clone().strict(true);
+    let render = |text: &str| match strict.try_render(text) {
+        Ok(rendered) => Ok(rendered),
+        Err(DomainError::UnresolvedPlaceholders { placeholders }) => {
+            Err(placeholders.into_iter().map(|p| p.name).collect())
+        }
+        // Syntax errors surface through `parse` of the raw text instead.
+        Err(_) => Ok(text.to_string()),
+    };
+
+    match default {
+        RenderValue::String(s) => render(s).map(RenderValue::String),
+        RenderValue::List(items) => items
+            .iter()
+            .map(|item| match item {
+                RenderValue::String(s) => render(s).map(RenderValue::String),
+                other => Ok(other.clone()),
+            })
+            .collect::<Result<_, _>>()
+            .map(RenderValue::List),
+        RenderValue::Map(_) => Ok(default.clone()),
+    }
+}
+
+fn has_placeholders(value: &RenderValue) -> bool {
+    match value {
+        RenderValue::String(s) => crate::domain::render::contains_syntax(s),
+        RenderValue::List(items) => items.iter().any(has_placeholders),
+        RenderValue::Map(_) => false,
+    }
+}
+
+fn not_a_choice(value: &str, choices: &[String]) -> String {
+    format!("'{value}' is not one of: {}", choices.join(", "))
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answers(pairs: &[(&str, &str)]) -> HashMap<String, RenderValue> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), RenderValue::from(*v)))
+            .collect()
+    }
+
+    fn specs() -> Vec<VariableSpec> {
+        vec![
+            // Declared before the variable its default references.
+            VariableSpec::new("db_name").default_value("{{PROJECT_NAME_SNAKE}}_{{db}}"),
+            VariableSpec::new("db")
+                .kind(VariableKind::Choice(vec![
+                    "postgres".into(),
+                    "sqlite".into(),
+                ]))
+                .default_value("postgres"),
+            VariableSpec::new("port")
+                .kind(VariableKind::Int {
+                    min: Some(1),
+                    max: Some(65535),
+                })
+                .default_value("3000"),
+            VariableSpec::new("docker")
+                .kind(VariableKind::Bool)
+                .default_value("no"),
+            VariableSpec::new("features").kind(VariableKind::List {
+                choices: Vec::new(),
+            }),
+        ]
+    }
+
+    #[test]
+    fn defaults_are_typed_and_may_reference_other_variables() {
+        let ctx = bind(
+            &specs(),
+            &answers(&[("db", "sqlite"), ("features", "auth, billing")]),
+            RenderContext::new("My App"),
+        )
+        .unwrap();
+
+        assert_eq!(ctx.get("db_name"), Some("my_app_sqlite"));
+        assert_eq!(ctx.get("port"), Some("3000"));
+        assert_eq!(ctx.get("docker"), Some("false"));
+        assert_eq!(
+            ctx.value("features"),
+            Some(&RenderValue::from(vec!["auth", "billing"]))
+        );
+    }
+
+    #[test]
+    fn every_missing_or_invalid_value_is_reported() {
+        let mut specs = specs();
+        specs.push(VariableSpec::new("author_email").pattern(r"[^@\s]+@[^@\s]+"));
+
+        let err = bind(
+            &specs,
+            &answers(&[("db", "mysql"), ("port", "99999"), ("features", "x")]),
+            RenderContext::new("app"),
+        )
+        .unwrap_err();
+
+        let DomainError::InvalidVariables { problems } = err else {
+            panic!("expected InvalidVariables, got {err:?}");
+        };
+        assert_eq!(problems.len(), 3, "{problems:?}");
+        assert!(
+            problems[0].contains("'mysql' is not one of"),
+            "{problems:?}"
+        );
+        assert!(
+            problems[1].contains("greater than the maximum"),
+            "{problems:?}"
+        );
+        assert!(problems[2].starts_with("author_email: a value is required"));
+    }
+
+    #[test]
+    fn false_condition_skips_the_variable() {
+        let specs = [
+            VariableSpec::new("db")
+                .kind(VariableKind::Choice(vec!["none".into(), "postgres".into()]))
+                .default_value("none"),
+            VariableSpec::new("pool_size")
+                .kind(VariableKind::Int {
+                    min: None,
+                    max: None,
+                })
+                .when(Condition::parse("db != 'none'").unwrap()),
+        ];
+
+        // Required, but skipped: no error, and the invalid answer is ignored.
+        let ctx = bind(
+            &specs,
+            &answers(&[("pool_size", "many")]),
+            RenderContext::new("app"),
+        )
+        .unwrap();
+        assert_eq!(ctx.get("pool_size"), Some(""));
+        assert!(!specs[1].applies(&ctx));
+
+        let err = bind(
+            &specs,
+            &answers(&[("db", "postgres")]),
+            RenderContext::new("app"),
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string().contains("pool_size: a value is required"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn pattern_must_match_in_full() {
+        let spec = VariableSpec::new("email").pattern(r"[^@\s]+@[^@\s]+");
+        assert!(spec.parse(&"ada@example.com".into()).is_ok());
+        assert!(spec.parse(&"ada@example.com extra".into()).is_err());
+    }
+
+    #[test]
+    fn cyclic_defaults_are_reported() {
+        let specs = [
+            VariableSpec::new("a").default_value("{{b}}"),
+            VariableSpec::new("b").default_value("{{a}}"),
+        ];
+        let err = bind(&specs, &HashMap::new(), RenderContext::new("x")).unwrap_err();
+        assert!(err.to_string().contains("a, b"), "{err}");
+    }
+
+    #[test]
+    fn validate_checks_the_declaration() {
+        assert!(VariableSpec::new("ok_name").validate().is_ok());
+        assert!(VariableSpec::new("9lives").validate().is_err());
+        assert!(VariableSpec::new("x").pattern("(").validate().is_err());
+        assert!(
+            VariableSpec::new("x")
+                .kind(VariableKind::Choice(Vec::new()))
+                .validate()
+                .is_err()
+        );
+        assert!(
+            VariableSpec::new("x")
+                .kind(VariableKind::Bool)
+                .default_value("maybe")
+                .validate()
+                .is_err()
+        );
+    }
+}
@@ -30,6 +30,24 @@ pub enum DomainError {
     #[error("Absolute paths not allowed: {path}")]
     AbsolutePathNotAllowed { path: String },
 
+    #[error("Invalid path '{path}': {reason}")]
+    InvalidPath { path: String, reason: String },
+
+    #[error("Template syntax error at {line}:{column}: {message}")]
+    TemplateSyntax {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+
+    #[error("{} unresolved placeholder(s): {}", placeholders.len(), join_placeholders(placeholders))]
+    UnresolvedPlaceholders {
+        placeholders: Vec<UnresolvedPlaceholder>,
+    },
+
+    #[error("{} invalid template variable(s):\n  {}", problems.len(), problems.join("\n  "))]
+    InvalidVariables { problems: Vec<String> },
+
     // ========================================================================
     // Compatibility Errors (409-level equivalent)
     // ========================================================================
@@ -53,6 +71,13 @@ pub enum DomainError {
         reason: String,
     },
 
+    #[error("overlay '{overlay}' conflicts with the project at '{path}': {reason}")]
+    OverlayConflict {
+        overlay: String,
+        path: String,
+        reason: String,
+    },
+
     // ========================================================================
     // Not Found Errors (404-level equivalent)
     // ========================================================================
@@ -94,6 +119,20 @@ impl DomainError {
                 format!("Target: {}", target),
                 "Try: scarff list-templates".into(),
             ],
+            Self::UnresolvedPlaceholders { .. } => vec![
+                "Check the variable names in the template for typos".into(),
+                "Define the missing variables, or add a `| default(\"...\")` filter".into(),
+            ],
+            Self::InvalidVariables { .. } => vec![
+                "Provide a valid value for each variable listed above".into(),
+                "See the template's [[variables]] section for types and allowed values".into(),
+            ],
+            Self::OverlayConflict { overlay, path, .. } => vec![
+                format!(
+                    "Declare how '{path}' is merged with an [[overlay.merge]] entry in the '{overlay}' overlay"
+                ),
+                "Or leave out one of the overlays that write it".into(),
+            ],
             Self::EmptyTemplate { template_id } => vec![
                 format!("Template '{}' is corrupted", template_id),
                 "Please report this issue or use a different template".into(),
@@ -105,10 +144,15 @@ impl DomainError {
     /// Error category for CLI display styling.
     pub fn category(&self) -> ErrorCategory {
         match self {
-            Self::InvalidTarget(_) | Self::InvalidTemplate(_) => ErrorCategory::Validation,
-            Self::IncompatibleLanguageKind { .. } | Self::IncompatibleFramework { .. } => {
-                ErrorCategory::Compatibility
-            }
+            Self::InvalidTarget(_)
+            | Self::InvalidTemplate(_)
+            | Self::InvalidPath { .. }
+            | Self::TemplateSyntax { .. }
+            | Self::UnresolvedPlaceholders { .. }
+            | Self::InvalidVariables { .. } => ErrorCategory::Validation,
+            Self::IncompatibleLanguageKind { .. }
+            | Self::IncompatibleFramework { .. }
+            | Self::OverlayConflict { .. } => ErrorCategory::Compatibility,
             Self::NoMatchingTemplate(_) | Self::AmbiguousTemplateMatch(_) => {
                 ErrorCategory::NotFound
             }
@@ -117,6 +161,28 @@ impl DomainError {
     }
 }
 
+/// A `{{NAME}}` placeholder left unresolved by a strict render.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnresolvedPlaceholder {
+    pub name: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl std::fmt::Display for UnresolvedPlaceholder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{{{{{}}}}} at {}:{}", self.name, self.line, self.column)
+    }
+}
+
+fn join_placeholders(placeholders: &[UnresolvedPlaceholder]) -> String {
+    placeholders
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ErrorCategory {
     Validation,
@@ -4,7 +4,7 @@
 
 //! Core domain layer for Scarff.
 //!
-//! This module contains pure business logic with ZERO external dependencies.
+//! This module contains pure business logic with no I/O dependencies.
 //! All I/O, templating, and rendering concerns are handled via ports (traits)
 //! defined in the application layer.
 //!
@@ -12,7 +12,9 @@
 //!
 //! - **No async**: Domain logic is synchronous
 //! - **No I/O**: No filesystem, network, or external calls
-//! - **No external crates**: Only std library + thiserror
+//! - **Pure dependencies only**: Besides std and thiserror, only crates that
+//!   compute without touching the outside world: `regex` (variable patterns,
+//!   format globs), `sha2` (digests) and `uuid` (seeded ids)
 //! - **Immutable entities**: All domain objects are Clone + PartialEq
 //! - **Rich domain model**: Behavior lives in entities, not services
 //!
@@ -23,22 +25,32 @@ pub mod error;
 pub mod value_objects;
 
 // Private implementation details - not visible outside domain
+mod render;
 mod validation;
 
 // mod validator;
 
 // Re-exports for convenience
 pub use entities::{
-    project_structure::{DirectoryToCreate, FileToWrite, FsEntry, ProjectStructure},
+    condition::Condition,
+    format::{FormatOptions, FormatRule, Glob, Indent, LineEnding},
+    hook::Hook,
+    overlay::{MergeFormat, MergeRule, MergeStrategy, Overlay},
+    package_names::PackageNames,
+    project_structure::{DirectoryToCreate, FileContent, FileToWrite, FsEntry, ProjectStructure},
     target::{Target, TargetBuilder},
     template::{
-        ContentTemplateId, DirectorySpec, FileSpec, RenderContext, TargetMatcher,
-        TargetMatcherBuilder, Template, TemplateBuilder, TemplateContent, TemplateId,
-        TemplateMetadata, TemplateNode, TemplateRecord, TemplateSource, TemplateTree,
+        BinarySource, ContentTemplateId, DirectorySpec, FileSpec, RenderContext, RenderEngine,
+        RenderValue, RunInfo, TargetMatcher, TargetMatcherBuilder, Template, TemplateBuilder,
+        TemplateContent, TemplateId, TemplateMetadata, TemplateNode, TemplateRecord,
+        TemplateSource, TemplateTree,
     },
+    variable::{VariableKind, VariablePattern, VariableSpec},
 };
 
-pub use error::{DomainError, ErrorCategory};
+pub use error::{DomainError, ErrorCategory, UnresolvedPlaceholder};
+
+pub use render::{apply_filter, expand_partials};
 
 pub use value_objects::{
     Architecture, Framework, Language, ProjectKind, PythonFramework, RustFramework,
@@ -220,6 +232,94 @@ mod tests {
         assert!(template.validate().is_err());
     }
 
+    #[test]
+    fn template_extend_checks_engine_and_removals() {
+        let base = Template::builder()
+            .id(TemplateId::new("base", "1.0.0"))
+            .matcher(TargetMatcher::default())
+            .metadata(TemplateMetadata::new("Base"))
+            .add_node(TemplateNode::Directory(DirectorySpec::new("src")))
+            .build()
+            .unwrap();
+        let child = || {
+            Template::builder()
+                .id(TemplateId::new("child", "1.0.0"))
+                .matcher(TargetMatcher::default())
+                .metadata(TemplateMetadata::new("Child"))
+                .extends(base.id.clone())
+        };
+
+        let flat = child().build().unwrap().extend(&base, &[]).unwrap();
+        assert_eq!(flat.tree.len(), 1);
+        assert!(flat.validate().is_ok());
+
+        let emptied = child()
+            .build()
+            .unwrap()
+            .extend(&base, &[RelativePath::new("src")])
+            .unwrap();
+        assert!(emptied.validate().is_err());
+
+        let err = child()
+            .build()
+            .unwrap()
+            .extend(&base, &[RelativePath::new("docs")])
+            .unwrap_err();
+        assert!(err.to_string().contains("removes 'docs'"), "{err}");
+
+        let err = child()
+            .engine(RenderEngine::Jinja)
+            .build()
+            .unwrap()
+            .extend(&base, &[])
+            .unwrap_err();
+        assert!(err.to_string().contains("uses engine"), "{err}");
+    }
+
+    #[test]
+    fn template_digest_changes_with_hooks_and_files() {
+        let template = |hook_arg: &str, dir: &str| {
+            Template::builder()
+                .id(TemplateId::new("test", "1.0.0"))
+                .matcher(TargetMatcher::default())
+                .metadata(TemplateMetadata::new("Test"))
+                .add_node(TemplateNode::Directory(DirectorySpec::new(dir)))
+                .post_hook(Hook::new("git").arg(hook_arg))
+                .build()
+                .unwrap()
+        };
+
+        let no_external = |_: &ContentTemplateId| -> Result<TemplateSource, ()> { Err(()) };
+        let digest = |t: Template| t.digest(no_external).unwrap();
+
+        let base = digest(template("init", "src"));
+        assert!(base.starts_with("sha256:"));
+        assert_eq!(base, digest(template("init", "src")));
+        assert_ne!(base, digest(template("push", "src")));
+        assert_ne!(base, digest(template("init", "lib")));
+
+        // External content counts as it resolves now, not just by id.
+        let with_script = || {
+            Template::builder()
+                .id(TemplateId::new("test", "1.0.0"))
+                .matcher(TargetMatcher::default())
+                .metadata(TemplateMetadata::new("Test"))
+                .add_node(TemplateNode::File(FileSpec::new(
+                    "setup.sh",
+                    TemplateContent::External(ContentTemplateId("file:setup.sh")),
+                )))
+                .build()
+                .unwrap()
+        };
+        let resolving = |text: &'static str| {
+            move |_: &ContentTemplateId| Ok::<_, ()>(TemplateSource::from(text))
+        };
+        let before = with_script().digest(resolving("echo a")).unwrap();
+        assert_eq!(before, with_script().digest(resolving("echo a")).unwrap());
+        assert_ne!(before, with_script().digest(resolving("echo b")).unwrap());
+        assert!(with_script().digest(no_external).is_err());
+    }
+
     #[test]
     fn template_specificity_calculation() {
         let template = Template::builder()
@@ -310,11 +410,7 @@ mod tests {
     fn project_structure_builds_correctly() {
         let structure = ProjectStructure::new("/tmp/test")
             .with_directory("src", Permissions::read_write())
-            .with_file(
-                "src/main.rs",
-                "fn main() {}".into(),
-                Permissions::read_write(),
-            );
+            .with_file("src/main.rs", "fn main() {}", Permissions::read_write());
 
         assert_eq!(structure.entry_count(), 2);
         assert_eq!(structure.files().count(), 1);
@@ -324,8 +420,8 @@ mod tests {
     #[test]
     fn project_structure_validates_duplicates() {
         let structure = ProjectStructure::new("/tmp/test")
-            .with_file("main.rs", "".into(), Permissions::read_write())
-            .with_file("main.rs", "".into(), Permissions::read_write());
+            .with_file("main.rs", "", Permissions::read_write())
+            .with_file("main.rs", "", Permissions::read_write());
 
         assert!(structure.validate().is_err());
     }
@@ -359,9 +455,99 @@ mod tests {
 
     #[test]
     fn render_context_renders_template() {
-        let ctx = RenderContext::new("my-project");
+        let info = RunInfo {
+            generated_at: std::time::UNIX_EPOCH + std::time::Duration::from_secs(1_772_357_400),
+            ..RunInfo::now()
+        };
+        let ctx = RenderContext::new("my-project").with_run_info(&info);
         let template = "Project: {{PROJECT_NAME}}, Year: {{YEAR}}";
 
         assert_eq!(ctx.render(template), "Project: my-project, Year: 2026");
     }
+
+    #[test]
+    fn render_context_exposes_run_info() {
+        let info = RunInfo {
+            scarff_version: "9.9.9".into(),
+            generated_at: std::time::UNIX_EPOCH + std::time::Duration::from_secs(951_827_696),
+            git_user_name: Some("Ada Lovelace".into()),
+            git_user_email: None,
+            random_id: "deadbeef".into(),
+        };
+        // Nothing run-dependent is read unless it is injected.
+        for name in ["YEAR", "GENERATED_AT", "SCARFF_VERSION", "RANDOM_ID"] {
+            assert_eq!(RenderContext::new("x").get(name), None, "{name}");
+        }
+        let ctx = RenderContext::new("x").with_run_info(&info).strict(true);
+
+        assert_eq!(
+            ctx.try_render(
+                "{{SCARFF_VERSION}} {{GENERATED_AT}} {{YEAR}} {{RANDOM_ID}} \
+                 {{GIT_USER_NAME}} <{{GIT_USER_EMAIL | default(\"n/a\")}}>"
+            )
+            .unwrap(),
+            "9.9.9 2000-02-29T12:34:56Z 2000 deadbeef Ada Lovelace <n/a>"
+        );
+    }
+
+    #[test]
+    fn target_built_ins_drive_when_conditions() {
+        let target = Target::builder()
+            .language(Language::Rust)
+            .kind(ProjectKind::WebBackend)
+            .unwrap()
+            .framework(Framework::Rust(RustFramework::Axum))
+            .unwrap()
+            .build()
+            .unwrap();
+        let ctx = RenderContext::new("api").with_target(&target);
+        let when = |source: &str| Condition::parse(source).unwrap();
+
+        let tree = TemplateTree::new()
+            .with_node(TemplateNode::File(
+                FileSpec::new(
+                    "src/routes.rs",
+                    TemplateContent::Literal(TemplateSource::from("")),
+                )
+                .when(when("FRAMEWORK == 'axum' && KIND == 'web-backend'")),
+            ))
+            .with_node(TemplateNode::Directory(
+                DirectorySpec::new("frontend").when(when("LANGUAGE == 'typescript'")),
+            ))
+            .with_node(TemplateNode::File(FileSpec::new(
+                "frontend/index.html",
+                TemplateContent::Literal(TemplateSource::from("")),
+            )));
+
+        let emitted: Vec<_> = tree
+            .nodes_for(&ctx)
+            .into_iter()
+            .map(|node| match node {
+                TemplateNode::File(f) => f.path.as_str(),
+                TemplateNode::Directory(d) => d.path.as_str(),
+            })
+            .collect();
+        assert_eq!(emitted, ["src/routes.rs"]);
+        assert_eq!(ctx.get("ARCHITECTURE"), Some("layered"));
+    }
+
+    #[test]
+    fn target_sets_language_aware_package_names() {
+        let target = |language| Target::builder().language(language).build().unwrap();
+
+        let rust = RenderContext::new("My App").with_target(&target(Language::Rust));
+        assert_eq!(rust.get("PACKAGE_NAME"), Some("my-app"));
+        assert_eq!(rust.get("MODULE_NAME"), Some("my_app"));
+
+        let go = target(Language::Go);
+        let scoped = RenderContext::new("My App")
+            .with_target(&go)
+            .with_package_names(&PackageNames::derive(
+                "My App",
+                go.language(),
+                Some("github.com/acme"),
+            ));
+        assert_eq!(scoped.get("PACKAGE_NAME"), Some("github.com/acme/my-app"));
+        assert_eq!(scoped.get("MODULE_NAME"), Some("myapp"));
+    }
 }
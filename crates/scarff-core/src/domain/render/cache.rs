@@ -0,0 +1,52 @@
+//! Parsed templates, kept on the [`TemplateSource`] they came from.
+//!
+//! Parsing is the only step whose cost depends on template size alone, so a
+//! file rendered more than once (several projects in one process, overlays
+//! sharing a base, repeated test runs) is tokenized a single time. The AST
+//! lives and dies with its source, and clones of a source share it.
+//!
+//! Syntax errors are kept as well; a broken template stays broken.
+
+use std::sync::Arc;
+
+use crate::domain::{entities::template::TemplateSource, error::DomainError};
+
+use super::parser::{self, Node};
+
+pub(crate) type Parsed = Result<Arc<[Node]>, DomainError>;
+
+/// Parse `source`, or return the AST parsed for it earlier.
+pub(crate) fn parse(source: &TemplateSource) -> Parsed {
+    source.parsed(|text| parser::parse(text).map(Arc::from))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_source_is_parsed_once() {
+        let source = TemplateSource::from("cache {{A}} {{#if B}}b{{/if}}".to_string());
+        let first = parse(&source).unwrap();
+        let second = parse(&source).unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+    }
+
+    #[test]
+    fn clones_share_the_parsed_ast() {
+        let source = TemplateSource::from("static {{A}}");
+        let first = parse(&source).unwrap();
+        let second = parse(&source.clone()).unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+
+        let fresh = parse(&TemplateSource::from("static {{A}}")).unwrap();
+        assert!(!Arc::ptr_eq(&first, &fresh));
+    }
+
+    #[test]
+    fn syntax_errors_are_kept() {
+        let source = TemplateSource::from("cache {{#if A}}".to_string());
+        assert!(parse(&source).is_err());
+        assert!(parse(&source).is_err());
+    }
+}
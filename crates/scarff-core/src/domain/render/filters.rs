@@ -0,0 +1,324 @@
+//! Placeholder filters: `{{ NAME | snake }}`, `{{ NAME | default("x") }}`.
+//!
+//! Filters are applied left to right, each receiving the previous output.
+//! Unknown filter names and wrong argument counts are rejected at parse time,
+//! so a typo in a template surfaces as a syntax error instead of silently
+//! rendering the wrong text.
+//!
+//! | Filter | Example input | Output |
+//! |--------|---------------|--------|
+//! | `snake` | `UserAccount` | `user_account` |
+//! | `kebab` | `UserAccount` | `user-account` |
+//! | `pascal` | `user_account` | `UserAccount` |
+//! | `camel` | `user_account` | `userAccount` |
+//! | `screaming_snake` | `user-account` | `USER_ACCOUNT` |
+//! | `title` | `user_account` | `User Account` |
+//! | `upper` / `lower` | `User` | `USER` / `user` |
+//! | `plural` | `category` | `categories` |
+//! | `singular` | `categories` | `category` |
+//! | `default("x")` | *(undefined or empty)* | `x` |
+
+use crate::domain::entities::template::{
+    to_camel_case, to_kebab_case, to_pascal_case, to_screaming_snake_case, to_snake_case,
+    to_title_case,
+};
+
+/// A parsed filter invocation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Filter {
+    pub name: String,
+    pub args: Vec<String>,
+}
+
+/// Names and arities of all supported filters.
+const FILTERS: &[(&str, usize)] = &[
+    ("snake", 0),
+    ("kebab", 0),
+    ("pascal", 0),
+    ("camel", 0),
+    ("screaming_snake", 0),
+    ("title", 0),
+    ("upper", 0),
+    ("lower", 0),
+    ("plural", 0),
+    ("singular", 0),
+    ("default", 1),
+];
+
+/// Check that `filter` names a known filter with the right number of arguments.
+pub(crate) fn check(filter: &Filter) -> Result<(), String> {
+    let Some((_, arity)) = FILTERS.iter().find(|(name, _)| *name == filter.name) else {
+        let known: Vec<&str> = FILTERS.iter().map(|(name, _)| *name).collect();
+        return Err(format!(
+            "unknown filter '{}'; available filters: {}",
+            filter.name,
+            known.join(", ")
+        ));
+    };
+
+    if filter.args.len() != *arity {
+        return Err(format!(
+            "filter '{}' expects {} argument(s), got {}",
+            filter.name,
+            arity,
+            filter.args.len()
+        ));
+    }
+    Ok(())
+}
+
+/// Run `value` through `filters`.
+///
+/// `value` is `None` for undefined variables; only `default` can turn that
+/// into a value; every other filter passes `None` through. `default` also
+/// replaces empty strings, so optional answers left blank fall back cleanly.
+pub(crate) fn apply(filters: &[Filter], mut value: Option<String>) -> Option<String> {
+    for filter in filters {
+        value = match (filter.name.as_str(), value) {
+            ("default", None) => Some(filter.args[0].clone()),
+            ("default", Some(v)) if v.is_empty() => Some(filter.args[0].clone()),
+            (_, None) => None,
+            (name, Some(v)) => Some(transform(name, &v)),
+        };
+    }
+    value
+}
+
+/// Apply a single argument-less filter by name; `None` if there is no such
+/// filter (or it needs arguments).
+pub(crate) fn apply_named(name: &str, value: &str) -> Option<String> {
+    FILTERS
+        .iter()
+        .any(|(known, arity)| *known == name && *arity == 0)
+        .then(|| transform(name, value))
+}
+
+fn transform(name: &str, value: &str) -> String {
+    match name {
+        "snake" => to_snake_case(value),
+        "kebab" => to_kebab_case(value),
+        "pascal" => to_pascal_case(value),
+        "camel" => to_camel_case(value),
+        "screaming_snake" => to_screaming_snake_case(value),
+        "title" => to_title_case(value),
+        "upper" => value.to_uppercase(),
+        "lower" => value.to_lowercase(),
+        "plural" => inflect(value, pluralize),
+        "singular" => inflect(value, singularize),
+        // `check` rejects everything else at parse time.
+        _ => value.to_string(),
+    }
+}
+
+// ── Inflection ────────────────────────────────────────────────────────────────
+
+/// Irregular singular/plural pairs (lowercase).
+const IRREGULAR: &[(&str, &str)] = &[
+    ("person", "people"),
+    ("child", "children"),
+    ("man", "men"),
+    ("woman", "women"),
+    ("mouse", "mice"),
+    ("goose", "geese"),
+    ("foot", "feet"),
+    ("tooth", "teeth"),
+    ("ox", "oxen"),
+    ("datum", "data"),
+    ("index", "indices"),
+    ("matrix", "matrices"),
+    ("vertex", "vertices"),
+    ("criterion", "criteria"),
+    ("analysis", "analyses"),
+    ("life", "lives"),
+    ("knife", "knives"),
+    ("leaf", "leaves"),
+    ("half", "halves"),
+    ("movie", "movies"),
+    ("status", "statuses"),
+];
+
+/// Words with identical singular and plural forms.
+const UNCOUNTABLE: &[&str] = &[
+    "equipment",
+    "information",
+    "metadata",
+    "news",
+    "series",
+    "species",
+    "sheep",
+    "fish",
+    "deer",
+    "auth",
+    "config",
+    "feedback",
+    "software",
+];
+
+/// Inflect only the last word of an identifier, keeping its casing:
+/// `user_category` → `user_categories`, `OrderItem` → `OrderItems`,
+/// `PERSON` → `PEOPLE`.
+fn inflect(value: &str, rule: fn(&str) -> String) -> String {
+    let start = last_word_start(value);
+    let (head, word) = value.split_at(start);
+    if word.is_empty() {
+        return value.to_string();
+    }
+
+    let inflected = rule(&word.to_lowercase());
+    let cased = if word.len() > 1 && word.chars().all(|c| !c.is_lowercase()) {
+        inflected.to_uppercase()
+    } else if word.starts_with(|c: char| c.is_uppercase()) {
+        let mut chars = inflected.chars();
+        chars
+            .next()
+            .map(|first| first.to_uppercase().chain(chars).collect())
+            .unwrap_or_default()
+    } else {
+        inflected
+    };
+
+    format!("{head}{cased}")
+}
+
+/// Byte offset where the trailing alphabetic word begins (camelCase aware).
+fn last_word_start(value: &str) -> usize {
+    let mut start = value.len();
+    let mut prev_lower = false;
+    for (i, c) in value.char_indices().rev() {
+        if !c.is_alphabetic() {
+            break;
+        }
+        start = i;
+        // `OrderItem`: stop at the capital that begins the last word.
+        if c.is_uppercase() && prev_lower {
+            break;
+        }
+        prev_lower = c.is_lowercase();
+    }
+    start
+}
+
+fn pluralize(word: &str) -> String {
+    if UNCOUNTABLE.contains(&word) {
+        return word.to_string();
+    }
+    if let Some((_, plural)) = IRREGULAR.iter().find(|(s, p)| *s == word || *p == word) {
+        return (*plural).to_string();
+    }
+
+    if let Some(stem) = word.strip_suffix('y')
+        && !stem.ends_with(['a', 'e', 'i', 'o', 'u'])
+        && !stem.is_empty()
+    {
+        return format!("{stem}ies");
+    }
+    if ["s", "x", "z", "ch", "sh"]
+        .iter()
+        .any(|suffix| word.ends_with(suffix))
+    {
+        return format!("{word}es");
+    }
+    format!("{word}s")
+}
+
+fn singularize(word: &str) -> String {
+    if UNCOUNTABLE.contains(&word) {
+        return word.to_string();
+    }
+    if let Some((singular, _)) = IRREGULAR.iter().find(|(s, p)| *p == word || *s == word) {
+        return (*singular).to_string();
+    }
+
+    if let Some(stem) = word.strip_suffix("ies")
+        && !stem.is_empty()
+    {
+        return format!("{stem}y");
+    }
+    for suffix in ["sses", "shes", "ches", "xes", "zes"] {
+        if word.ends_with(suffix) {
+            return word[..word.len() - 2].to_string();
+        }
+    }
+    if word.ends_with("ss") || word.ends_with("us") || word.ends_with("is") {
+        return word.to_string();
+    }
+    word.strip_suffix('s').unwrap_or(word).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(name: &str) -> Filter {
+        Filter {
+            name: name.into(),
+            args: Vec::new(),
+        }
+    }
+
+    fn run(name: &str, value: &str) -> String {
+        apply(&[filter(name)], Some(value.into())).unwrap()
+    }
+
+    #[test]
+    fn case_filters() {
+        assert_eq!(run("snake", "UserAccount"), "user_account");
+        assert_eq!(run("kebab", "UserAccount"), "user-account");
+        assert_eq!(run("pascal", "user_account"), "UserAccount");
+        assert_eq!(run("camel", "user_account"), "userAccount");
+        assert_eq!(run("screaming_snake", "user-account"), "USER_ACCOUNT");
+        assert_eq!(run("title", "user_account"), "User Account");
+        assert_eq!(run("upper", "User"), "USER");
+        assert_eq!(run("lower", "User"), "user");
+    }
+
+    #[test]
+    fn plural_and_singular() {
+        for (singular, plural) in [
+            ("user", "users"),
+            ("category", "categories"),
+            ("key", "keys"),
+            ("address", "addresses"),
+            ("box", "boxes"),
+            ("branch", "branches"),
+            ("person", "people"),
+            ("child", "children"),
+            ("series", "series"),
+        ] {
+            assert_eq!(run("plural", singular), plural, "plural of {singular}");
+            assert_eq!(run("singular", plural), singular, "singular of {plural}");
+        }
+    }
+
+    #[test]
+    fn inflection_keeps_identifier_shape() {
+        assert_eq!(run("plural", "user_category"), "user_categories");
+        assert_eq!(run("plural", "OrderItem"), "OrderItems");
+        assert_eq!(run("plural", "Person"), "People");
+        assert_eq!(run("plural", "PERSON"), "PEOPLE");
+        assert_eq!(run("singular", "order-items"), "order-item");
+    }
+
+    #[test]
+    fn default_fills_undefined_and_empty() {
+        let default = [Filter {
+            name: "default".into(),
+            args: vec!["x".into()],
+        }];
+        assert_eq!(apply(&default, None).as_deref(), Some("x"));
+        assert_eq!(apply(&default, Some(String::new())).as_deref(), Some("x"));
+        assert_eq!(apply(&default, Some("y".into())).as_deref(), Some("y"));
+        assert_eq!(apply(&[filter("upper")], None), None);
+    }
+
+    #[test]
+    fn check_rejects_unknown_filters_and_bad_arity() {
+        assert!(check(&filter("snake")).is_ok());
+        assert!(
+            check(&filter("shout"))
+                .unwrap_err()
+                .contains("available filters")
+        );
+        assert!(check(&filter("default")).is_err());
+    }
+}
@@ -0,0 +1,678 @@
+//! Template rendering engine behind [`RenderContext`].
+//!
+//! Supported syntax:
+//!
+//! | Syntax | Meaning |
+//! |--------|---------|
+//! | `{{NAME}}` | Substitute variable `NAME` |
+//! | `{{ NAME \| snake }}` | Substitute through filters (see [`filters`]) |
+//! | `{{#if NAME}}…{{/if}}` | Emit body when `NAME` is truthy |
+//! | `{{#unless NAME}}…{{/unless}}` | Emit body when `NAME` is falsy |
+//! | `{{else}}` / `{{else if NAME}}` | Alternative branches inside a block |
+//! | `{{#each NAME}}…{{/each}}` | Repeat body for every item of `NAME` |
+//! | `{{#each NAME as ITEM}}` | Same, binding each item to `ITEM` |
+//! | `{{> name}}` | Include a shared partial (inlined at load time, see [`expand_partials`]) |
+//! | `\{{` | Literal `{{` (the backslash is dropped) |
+//! | `{{{{raw}}}}…{{{{/raw}}}}` | Emit the body verbatim |
+//!
+//! The escapes keep mixed-syntax files safe: GitHub Actions (`$\{{ matrix.os }}`),
+//! Vue/Handlebars markup or Go `text/template` can live next to Scarff
+//! placeholders.
+//!
+//! A variable is **falsy** when it is undefined, empty (including empty lists
+//! and maps), or one of `false`, `0`, `no`, `off` (case-insensitive).
+//! Everything else is truthy.
+//!
+//! Block tags that sit alone on a line remove that whole line, so templates
+//! can be laid out naturally without leaving blank lines in the output.
+//!
+//! ## Scopes
+//!
+//! Every `{{#each}}` iteration pushes a scope holding `this` (or the alias),
+//! `@index`, `@first`, `@last` and, for maps, `@key`. Lookups walk from the
+//! innermost scope outwards to the context variables, and dotted names
+//! (`module.name`) descend into map values.
+//!
+//! ## Single Pass
+//!
+//! Each source is tokenized once into a small AST (cached per
+//! [`TemplateSource`], see [`cache`]) and evaluated in one walk. Substituted
+//! values are written straight to the output and never re-scanned, so a value
+//! containing `{{PROJECT_NAME}}` stays literal, and the result does not depend
+//! on variable iteration order.
+//!
+//! [`RenderContext`]: crate::domain::RenderContext
+
+mod cache;
+mod filters;
+mod parser;
+
+pub(crate) use cache::Parsed;
+pub(crate) use parser::contains_syntax;
+pub use parser::expand_partials;
+
+use std::collections::HashMap;
+
+use crate::domain::{
+    entities::template::{RenderValue, TemplateSource},
+    error::{DomainError, UnresolvedPlaceholder},
+};
+
+use parser::{Condition, Node, Position};
+
+/// Apply a built-in, argument-less filter (`snake`, `pascal`, `plural`, …)
+/// to `value`, for renderers that want to expose the same vocabulary.
+///
+/// Returns `None` for unknown filter names.
+pub fn apply_filter(name: &str, value: &str) -> Option<String> {
+    filters::apply_named(name, value)
+}
+
+/// Parse and evaluate `source` against `variables`.
+///
+/// In `strict` mode every placeholder that is reached but cannot be resolved
+/// is collected, and the render fails with
+/// [`DomainError::UnresolvedPlaceholders`] listing all of them. Placeholders in
+/// branches that are not taken are never reported.
+pub(crate) fn render(
+    source: &str,
+    variables: &HashMap<String, RenderValue>,
+    strict: bool,
+) -> Result<String, DomainError> {
+    let nodes = parser::parse(source)?;
+    render_nodes(&nodes, source.len(), variables, strict)
+}
+
+/// Like [`render`], reusing the cached AST for `source`.
+pub(crate) fn render_source(
+    source: &TemplateSource,
+    variables: &HashMap<String, RenderValue>,
+    strict: bool,
+) -> Result<String, DomainError> {
+    let nodes = cache::parse(source)?;
+    render_nodes(&nodes, source.as_str().len(), variables, strict)
+}
+
+fn render_nodes(
+    nodes: &[Node],
+    size_hint: usize,
+    variables: &HashMap<String, RenderValue>,
+    strict: bool,
+) -> Result<String, DomainError> {
+    let mut out = Output {
+        text: String::with_capacity(size_hint),
+        unresolved: Vec::new(),
+    };
+    let scope = Scope {
+        locals: HashMap::new(),
+        parent: None,
+        globals: variables,
+    };
+    evaluate(nodes, &scope, &mut out);
+
+    if strict && !out.unresolved.is_empty() {
+        return Err(DomainError::UnresolvedPlaceholders {
+            placeholders: out.unresolved,
+        });
+    }
+    Ok(out.text)
+}
+
+/// Render output plus the placeholders that could not be resolved.
+struct Output {
+    text: String,
+    unresolved: Vec<UnresolvedPlaceholder>,
+}
+
+impl Output {
+    fn unresolved(&mut self, name: &str, raw: &str, position: Position) {
+        self.text.push_str(raw);
+        self.unresolved.push(UnresolvedPlaceholder {
+            name: name.to_string(),
+            line: position.line,
+            column: position.column,
+        });
+    }
+}
+
+/// Variable lookup chain: loop-local bindings first, then the context.
+struct Scope<'a> {
+    locals: HashMap<String, RenderValue>,
+    parent: Option<&'a Scope<'a>>,
+    globals: &'a HashMap<String, RenderValue>,
+}
+
+impl Scope<'_> {
+    fn child(&self, locals: HashMap<String, RenderValue>) -> Scope<'_> {
+        Scope {
+            locals,
+            parent: Some(self),
+            globals: self.globals,
+        }
+    }
+
+    fn lookup_exact(&self, name: &str) -> Option<&RenderValue> {
+        match self.locals.get(name) {
+            Some(value) => Some(value),
+            None => match self.parent {
+                Some(parent) => parent.lookup_exact(name),
+                None => self.globals.get(name),
+            },
+        }
+    }
+
+    /// Resolve `name`, descending into maps for dotted paths. A variable whose
+    /// name itself contains dots takes precedence over path traversal.
+    fn resolve(&self, name: &str) -> Option<&RenderValue> {
+        if let Some(value) = self.lookup_exact(name) {
+            return Some(value);
+        }
+
+        let mut segments = name.split('.');
+        let mut current = self.lookup_exact(segments.next()?)?;
+        for segment in segments {
+            current = match current {
+                RenderValue::Map(entries) => entries.get(segment)?,
+                _ => return None,
+            };
+        }
+        Some(current)
+    }
+}
+
+fn evaluate(nodes: &[Node], scope: &Scope<'_>, out: &mut Output) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => out.text.push_str(text),
+            Node::Variable {
+                name,
+                filters,
+                raw,
+                position,
+            } if filters.is_empty() => match scope.resolve(name) {
+                Some(RenderValue::String(value)) => out.text.push_str(value),
+                Some(value) => out.text.push_str(&value.to_string()),
+                // Unknown placeholders pass through untouched.
+                None => out.unresolved(name, raw, *position),
+            },
+            Node::Variable {
+                name,
+                filters,
+                raw,
+                position,
+            } => {
+                let value = scope.resolve(name).map(ToString::to_string);
+                match filters::apply(filters, value) {
+                    Some(value) => out.text.push_str(&value),
+                    None => out.unresolved(name, raw, *position),
+                }
+            }
+            Node::Conditional {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                let branch = if is_satisfied(condition, scope) {
+                    then_branch
+                } else {
+                    else_branch
+                };
+                evaluate(branch, scope, out);
+            }
+            Node::Each {
+                collection,
+                alias,
+                body,
+                else_branch,
+            } => {
+                let items = scope
+                    .resolve(collection)
+                    .map(RenderValue::iter_items)
+                    .unwrap_or_default();
+                if items.is_empty() {
+                    evaluate(else_branch, scope, out);
+                    continue;
+                }
+
+                let last = items.len() - 1;
+                for (index, (key, item)) in items.into_iter().enumerate() {
+                    let mut locals = HashMap::new();
+                    locals.insert("@index".to_string(), index.to_string().into());
+                    locals.insert("@first".to_string(), (index == 0).into());
+                    locals.insert("@last".to_string(), (index == last).into());
+                    if let Some(key) = key {
+                        locals.insert("@key".to_string(), key.into());
+                    }
+                    if let Some(alias) = alias {
+                        locals.insert(alias.clone(), item.clone());
+                    }
+                    locals.insert("this".to_string(), item);
+
+                    evaluate(body, &scope.child(locals), out);
+                }
+            }
+        }
+    }
+}
+
+fn is_satisfied(condition: &Condition, scope: &Scope<'_>) -> bool {
+    let truthy = scope.resolve(&condition.variable).is_some_and(is_truthy);
+    truthy != condition.negated
+}
+
+pub(crate) fn is_truthy(value: &RenderValue) -> bool {
+    match value {
+        RenderValue::String(s) => {
+            let s = s.trim();
+            !(s.is_empty()
+                || ["false", "0", "no", "off"]
+                    .iter()
+                    .any(|falsy| s.eq_ignore_ascii_case(falsy)))
+        }
+        other => !other.is_empty(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> HashMap<String, RenderValue> {
+        pairs
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).into()))
+            .collect()
+    }
+
+    fn render_ok(source: &str, pairs: &[(&str, &str)]) -> String {
+        render(source, &vars(pairs), false).expect("template should render")
+    }
+
+    #[test]
+    fn substitutes_variables_and_keeps_unknown() {
+        let out = render_ok("{{A}}-{{ A }}-{{B}}", &[("A", "x")]);
+        assert_eq!(out, "x-x-{{B}}");
+    }
+
+    #[test]
+    fn keeps_foreign_syntax() {
+        let src = "image: {{ .Values.image }} {{#with items}}";
+        assert_eq!(render_ok(src, &[]), src);
+    }
+
+    #[test]
+    fn triple_braces_keep_outer_braces() {
+        assert_eq!(render_ok("{{{A}}}", &[("A", "x")]), "{x}");
+    }
+
+    #[test]
+    fn if_else_selects_branch() {
+        let src = "{{#if DB}}db={{DB}}{{else}}no db{{/if}}";
+        assert_eq!(render_ok(src, &[("DB", "postgres")]), "db=postgres");
+        assert_eq!(render_ok(src, &[]), "no db");
+        assert_eq!(render_ok(src, &[("DB", "false")]), "no db");
+        assert_eq!(render_ok(src, &[("DB", "")]), "no db");
+    }
+
+    #[test]
+    fn unless_negates() {
+        let src = "{{#unless QUIET}}loud{{else}}quiet{{/unless}}";
+        assert_eq!(render_ok(src, &[]), "loud");
+        assert_eq!(render_ok(src, &[("QUIET", "yes")]), "quiet");
+    }
+
+    #[test]
+    fn else_if_chains() {
+        let src = "{{#if A}}a{{else if B}}b{{else}}c{{/if}}";
+        assert_eq!(render_ok(src, &[("A", "1")]), "a");
+        assert_eq!(render_ok(src, &[("B", "1")]), "b");
+        assert_eq!(render_ok(src, &[]), "c");
+    }
+
+    #[test]
+    fn nested_blocks() {
+        let src = "{{#if A}}[{{#if B}}ab{{else}}a{{/if}}]{{/if}}";
+        assert_eq!(render_ok(src, &[("A", "1"), ("B", "1")]), "[ab]");
+        assert_eq!(render_ok(src, &[("A", "1")]), "[a]");
+        assert_eq!(render_ok(src, &[("B", "1")]), "");
+    }
+
+    #[test]
+    fn standalone_block_lines_are_removed() {
+        let src = "[dependencies]\n  {{#if DB}}\nsqlx = \"0.8\"\n  {{/if}}\nserde = \"1\"\n";
+        assert_eq!(
+            render_ok(src, &[("DB", "postgres")]),
+            "[dependencies]\nsqlx = \"0.8\"\nserde = \"1\"\n"
+        );
+        assert_eq!(render_ok(src, &[]), "[dependencies]\nserde = \"1\"\n");
+    }
+
+    #[test]
+    fn inline_blocks_keep_surrounding_text() {
+        assert_eq!(
+            render_ok("a {{#if X}}b{{/if}} c\n", &[("X", "1")]),
+            "a b c\n"
+        );
+    }
+
+    #[test]
+    fn unclosed_block_reports_opening_position() {
+        let err = render("line\n  {{#if A}}oops", &HashMap::new(), false).unwrap_err();
+        assert!(matches!(
+            err,
+            DomainError::TemplateSyntax {
+                line: 2,
+                column: 3,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn stray_closing_tag_is_an_error() {
+        let err = render("{{/if}}", &HashMap::new(), false).unwrap_err();
+        assert!(matches!(
+            err,
+            DomainError::TemplateSyntax {
+                line: 1,
+                column: 1,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn mismatched_closing_tag_is_an_error() {
+        let err = render("{{#if A}}x{{/unless}}", &HashMap::new(), false).unwrap_err();
+        assert!(err.to_string().contains("expected {{/if}}"));
+    }
+
+    #[test]
+    fn if_without_condition_is_an_error() {
+        assert!(render("{{#if}}x{{/if}}", &HashMap::new(), false).is_err());
+        assert!(render("{{#if a b}}x{{/if}}", &HashMap::new(), false).is_err());
+    }
+
+    #[test]
+    fn each_over_list_with_alias_and_metadata() {
+        let mut globals = HashMap::new();
+        globals.insert(
+            "MODULES".to_string(),
+            RenderValue::from(vec!["users", "orders"]),
+        );
+        let src = "{{#each MODULES as m}}{{@index}}:{{m}}{{#unless @last}},{{/unless}}{{/each}}";
+        assert_eq!(render(src, &globals, false).unwrap(), "0:users,1:orders");
+    }
+
+    #[test]
+    fn each_over_comma_separated_string() {
+        let src = "{{#each MODULES}}\npub mod {{this}};\n{{/each}}\n";
+        assert_eq!(
+            render_ok(src, &[("MODULES", "users, orders,")]),
+            "pub mod users;\npub mod orders;\n"
+        );
+    }
+
+    #[test]
+    fn each_over_map_is_key_ordered() {
+        let mut deps = std::collections::BTreeMap::new();
+        deps.insert("serde".to_string(), "1");
+        deps.insert("clap".to_string(), "4");
+        let mut globals = HashMap::new();
+        globals.insert("DEPS".to_string(), RenderValue::from(deps));
+
+        let src = "{{#each DEPS}}{{@key}} = \"{{this}}\"\n{{/each}}";
+        assert_eq!(
+            render(src, &globals, false).unwrap(),
+            "clap = \"4\"\nserde = \"1\"\n"
+        );
+    }
+
+    #[test]
+    fn each_reaches_map_fields_and_outer_scope() {
+        let mut route = std::collections::BTreeMap::new();
+        route.insert("path".to_string(), "/users");
+        let mut globals = HashMap::new();
+        globals.insert("ROUTES".to_string(), RenderValue::from(vec![route]));
+        globals.insert("PREFIX".to_string(), "/api".into());
+
+        let src = "{{#each ROUTES as r}}{{PREFIX}}{{r.path}}{{/each}}";
+        assert_eq!(render(src, &globals, false).unwrap(), "/api/users");
+    }
+
+    #[test]
+    fn each_else_renders_for_empty_or_missing_collection() {
+        let src = "{{#each XS}}x{{else}}none{{/each}}";
+        assert_eq!(render_ok(src, &[]), "none");
+        assert_eq!(render_ok(src, &[("XS", "")]), "none");
+    }
+
+    #[test]
+    fn empty_list_is_falsy() {
+        let mut globals = HashMap::new();
+        globals.insert("XS".to_string(), RenderValue::List(Vec::new()));
+        assert_eq!(
+            render("{{#if XS}}y{{else}}n{{/if}}", &globals, false).unwrap(),
+            "n"
+        );
+    }
+
+    #[test]
+    fn list_substitution_joins_items() {
+        let mut globals = HashMap::new();
+        globals.insert("XS".to_string(), RenderValue::from(vec!["a", "b"]));
+        assert_eq!(render("[{{XS}}]", &globals, false).unwrap(), "[a, b]");
+    }
+
+    #[test]
+    fn unclosed_each_is_an_error() {
+        let err = render("{{#each XS}}x", &HashMap::new(), false).unwrap_err();
+        assert!(err.to_string().contains("{{/each}}"));
+        assert!(render("{{#each}}x{{/each}}", &HashMap::new(), false).is_err());
+        assert!(render("{{#each XS in ys}}x{{/each}}", &HashMap::new(), false).is_err());
+    }
+
+    #[test]
+    fn filters_apply_to_any_variable() {
+        let src = "{{ ENTITY | pascal }}/{{ ENTITY|plural|kebab }}/{{ENTITY | screaming_snake}}";
+        assert_eq!(
+            render_ok(src, &[("ENTITY", "order_item")]),
+            "OrderItem/order-items/ORDER_ITEM"
+        );
+    }
+
+    #[test]
+    fn default_filter_covers_undefined_variables() {
+        let src = r#"port = {{ PORT | default("8080") }}"#;
+        assert_eq!(render_ok(src, &[]), "port = 8080");
+        assert_eq!(render_ok(src, &[("PORT", "3000")]), "port = 3000");
+        assert_eq!(render_ok("{{ X | upper }}", &[]), "{{ X | upper }}");
+    }
+
+    #[test]
+    fn filter_arguments_may_contain_pipes_and_quotes() {
+        let src = r#"{{ X | default("a|b \"c\"") }}"#;
+        assert_eq!(render_ok(src, &[]), r#"a|b "c""#);
+    }
+
+    #[test]
+    fn filters_work_on_loop_items() {
+        let src = "{{#each XS}}{{this | pascal}};{{/each}}";
+        assert_eq!(
+            render_ok(src, &[("XS", "user_role,order")]),
+            "UserRole;Order;"
+        );
+    }
+
+    #[test]
+    fn unknown_filter_is_an_error() {
+        let err = render("a\n{{ X | shout }}", &HashMap::new(), false).unwrap_err();
+        assert!(matches!(err, DomainError::TemplateSyntax { line: 2, .. }));
+        assert!(err.to_string().contains("unknown filter 'shout'"));
+        assert!(render("{{ X | default }}", &HashMap::new(), false).is_err());
+        assert!(render(r#"{{ X | default("x) }}"#, &HashMap::new(), false).is_err());
+    }
+
+    #[test]
+    fn strict_mode_reports_every_unresolved_placeholder() {
+        let src = "a={{A}}\nb={{ DB_URL }} c={{ C | snake }}\n{{ D | default(\"d\") }}";
+        let err = render(src, &vars(&[("A", "1")]), true).unwrap_err();
+        let DomainError::UnresolvedPlaceholders { placeholders } = err else {
+            panic!("expected unresolved placeholders");
+        };
+        let found: Vec<_> = placeholders
+            .iter()
+            .map(|p| (p.name.as_str(), p.line, p.column))
+            .collect();
+        assert_eq!(found, [("DB_URL", 2, 3), ("C", 2, 18)]);
+    }
+
+    #[test]
+    fn strict_mode_ignores_untaken_branches_and_foreign_syntax() {
+        let src = "{{#if DB}}{{DB_URL}}{{/if}}{{ .Values.x }}";
+        assert_eq!(
+            render(src, &HashMap::new(), true).unwrap(),
+            "{{ .Values.x }}"
+        );
+    }
+
+    #[test]
+    fn backslash_escapes_braces() {
+        let src = r"os: $\{{ matrix.os }} name: {{NAME}}";
+        assert_eq!(
+            render(src, &vars(&[("NAME", "x")]), true).unwrap(),
+            "os: ${{ matrix.os }} name: x"
+        );
+    }
+
+    #[test]
+    fn raw_block_is_emitted_verbatim() {
+        let src = "a: {{A}}\n{{{{raw}}}}\n{{#if x}}{{ vue }}{{/if}}\n{{{{/raw}}}}\nb\n";
+        assert_eq!(
+            render(src, &vars(&[("A", "1")]), true).unwrap(),
+            "a: 1\n{{#if x}}{{ vue }}{{/if}}\nb\n"
+        );
+        assert_eq!(
+            render_ok("[{{{{raw}}}}{{A}}{{{{/raw}}}}]", &[("A", "1")]),
+            "[{{A}}]"
+        );
+    }
+
+    #[test]
+    fn unclosed_raw_block_is_an_error() {
+        let err = render("x\n{{{{raw}}}}{{A}}", &HashMap::new(), false).unwrap_err();
+        assert!(matches!(err, DomainError::TemplateSyntax { line: 2, .. }));
+    }
+
+    #[test]
+    fn detects_renderer_syntax() {
+        assert!(contains_syntax("{{NAME}}"));
+        assert!(contains_syntax("{{#if X}}{{/if}}"));
+        assert!(contains_syntax(r"$\{{ matrix.os }}"));
+        assert!(contains_syntax("{{{{raw}}}}{{x}}{{{{/raw}}}}"));
+        assert!(contains_syntax("{{#if}}"));
+        assert!(!contains_syntax("{{ .Values.image }}"));
+        assert!(!contains_syntax("fn main() { let x = {}; }"));
+    }
+
+    #[test]
+    fn substituted_values_are_never_re_expanded() {
+        let globals = vars(&[
+            ("PROJECT_NAME", "app"),
+            ("DESCRIPTION", "uses {{PROJECT_NAME}} and {{#if X}}"),
+        ]);
+        assert_eq!(
+            render("{{PROJECT_NAME}}: {{DESCRIPTION}}", &globals, true).unwrap(),
+            "app: uses {{PROJECT_NAME}} and {{#if X}}"
+        );
+    }
+
+    #[test]
+    fn output_is_deterministic_across_variable_orders() {
+        // Values that reference each other used to expand differently
+        // depending on hash-map iteration order.
+        let pairs: Vec<(String, String)> = (0..32)
+            .map(|i| (format!("V{i}"), format!("{{{{V{}}}}}", (i + 1) % 32)))
+            .collect();
+        let src: String = (0..32).map(|i| format!("{{{{V{i}}}}}|")).collect();
+        let expected: String = (0..32)
+            .map(|i| format!("{{{{V{}}}}}|", (i + 1) % 32))
+            .collect();
+
+        for _ in 0..8 {
+            let globals: HashMap<String, RenderValue> = pairs
+                .iter()
+                .map(|(k, v)| (k.clone(), v.as_str().into()))
+                .collect();
+            assert_eq!(render(&src, &globals, false).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn cached_and_uncached_renders_agree() {
+        let text = "{{#each XS}}{{this | upper}}{{/each}} {{A}}";
+        let globals = vars(&[("XS", "a,b"), ("A", "1")]);
+        let source = TemplateSource::from(text.to_string());
+        assert_eq!(
+            render_source(&source, &globals, false).unwrap(),
+            render(text, &globals, false).unwrap()
+        );
+    }
+
+    fn partials(pairs: &[(&str, &str)]) -> impl FnMut(&str) -> Option<String> {
+        let map: HashMap<String, String> = pairs
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect();
+        move |name| map.get(name).cloned()
+    }
+
+    #[test]
+    fn partials_are_inlined_with_the_tag_indentation() {
+        let mut resolve = partials(&[
+            ("ci/steps.yml", "- run: cargo fmt\n{{> ci/test.yml}}\n"),
+            ("ci/test.yml", "- run: cargo test\n"),
+            ("name", "{{PROJECT_NAME}}\n"),
+        ]);
+        let source = "jobs:\n  steps:\n    {{> ci/steps.yml}}\n# {{> name}} \\{{> name}}\n";
+
+        let expanded = expand_partials(source, &mut resolve).unwrap();
+
+        assert_eq!(
+            expanded,
+            "jobs:\n  steps:\n    - run: cargo fmt\n    - run: cargo test\n\
+             # {{PROJECT_NAME}} \\{{> name}}\n"
+        );
+        assert_eq!(
+            render_ok(&expanded, &[("PROJECT_NAME", "app")])
+                .lines()
+                .last(),
+            Some("# app {{> name}}")
+        );
+    }
+
+    #[test]
+    fn unknown_and_cyclic_partials_are_errors() {
+        let mut resolve = partials(&[("a", "{{> b}}"), ("b", "{{> a}}")]);
+
+        let err = expand_partials("x\n {{> missing}}", &mut resolve).unwrap_err();
+        assert!(
+            err.to_string().contains("unknown partial 'missing' at 2:2"),
+            "{err}"
+        );
+
+        let err = expand_partials("{{> a}}", &mut resolve).unwrap_err();
+        assert!(err.to_string().contains("a -> b -> a"), "{err}");
+
+        let err = render("{{> a}}", &HashMap::new(), false).unwrap_err();
+        assert!(
+            err.to_string().contains("partial 'a' is not available"),
+            "{err}"
+        );
+        let err = expand_partials("{{> ../x}}", &mut resolve).unwrap_err();
+        assert!(
+            err.to_string().contains("invalid partial name '../x'"),
+            "{err}"
+        );
+    }
+}
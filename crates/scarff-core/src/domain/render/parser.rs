@@ -0,0 +1,853 @@
+//! Tokenizer and parser for the template syntax.
+//!
+//! Parsing happens in two steps:
+//!
+//! 1. **Lexing** splits the source into text runs and `{{ … }}` tags. Tags that
+//!    are not Scarff syntax (e.g. `{{ .Values.image }}`) stay part of the text,
+//!    as do escaped braces (`\{{`) and `{{{{raw}}}} … {{{{/raw}}}}` blocks.
+//! 2. **Parsing** folds the flat token stream into a [`Node`] tree, pairing
+//!    every `{{#if}}` / `{{#each}}` with its `{{else}}` and closing tag.
+//!
+//! Positions are 1-based and refer to the original source so errors can point
+//! authors at the exact tag.
+
+use std::ops::Range;
+
+use crate::domain::error::DomainError;
+
+use super::filters::{self, Filter};
+
+/// 1-based line/column of a tag in the template source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// A parsed template fragment.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Node {
+    /// Literal text, emitted as-is.
+    Text(String),
+
+    /// `{{NAME}}` / `{{ NAME | filter }}` placeholder. `raw` is the original
+    /// tag, emitted verbatim when the variable is undefined; `position` is
+    /// reported by strict renders.
+    Variable {
+        name: String,
+        filters: Vec<Filter>,
+        raw: String,
+        position: Position,
+    },
+
+    /// `{{#if}}` / `{{#unless}}` block.
+    Conditional {
+        condition: Condition,
+        then_branch: Vec<Node>,
+        else_branch: Vec<Node>,
+    },
+
+    /// `{{#each COLLECTION}}` / `{{#each COLLECTION as ITEM}}` loop.
+    /// `else_branch` is emitted when the collection is empty.
+    Each {
+        collection: String,
+        alias: Option<String>,
+        body: Vec<Node>,
+        else_branch: Vec<Node>,
+    },
+}
+
+/// Condition of a conditional block: a variable tested for truthiness.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Condition {
+    pub variable: String,
+    /// `true` for `{{#unless}}`.
+    pub negated: bool,
+}
+
+/// Parse template source into a node tree.
+///
+/// # Errors
+///
+/// Returns [`DomainError::TemplateSyntax`] for unbalanced or malformed block
+/// tags (`{{#if}}` without a condition, stray `{{/if}}`, unclosed blocks…).
+pub(crate) fn parse(source: &str) -> Result<Vec<Node>, DomainError> {
+    let tokens = lex(source)?;
+    let mut parser = Parser { tokens, cursor: 0 };
+
+    let (nodes, stop) = parser.parse_sequence()?;
+    match stop {
+        None => Ok(nodes),
+        Some(stop) => Err(syntax_error(
+            stop.position,
+            format!(
+                "unexpected {{{{{}}}}} without a matching opening block",
+                stop.tag
+            ),
+        )),
+    }
+}
+
+// ── Partials ──────────────────────────────────────────────────────────────────
+
+/// Inline every `{{> name}}` in `source` with the text `resolve` returns for
+/// `name`, recursively.
+///
+/// A partial tag alone on its line is replaced by the partial's lines, each
+/// indented like the tag, so a YAML snippet can be included at any depth.
+/// Inline tags insert the partial without its final newline. Escaped tags
+/// (`\{{> name}}`) and raw blocks are left alone.
+///
+/// # Errors
+///
+/// [`DomainError::InvalidTemplate`] for an unknown partial or a partial that
+/// (indirectly) includes itself; [`DomainError::TemplateSyntax`] for a
+/// malformed tag in `source` or a partial, including bad partial names.
+pub fn expand_partials(
+    source: &str,
+    resolve: &mut dyn FnMut(&str) -> Option<String>,
+) -> Result<String, DomainError> {
+    expand_tokens(source, &lex(source)?, resolve, &mut Vec::new())
+}
+
+fn expand_tokens(
+    source: &str,
+    tokens: &[Token<'_>],
+    resolve: &mut dyn FnMut(&str) -> Option<String>,
+    stack: &mut Vec<String>,
+) -> Result<String, DomainError> {
+    let mut out = String::with_capacity(source.len());
+    let mut copied = 0;
+
+    for token in tokens {
+        let Token::Tag {
+            kind: TagKind::Partial(name),
+            raw,
+            position,
+            span,
+        } = token
+        else {
+            continue;
+        };
+
+        if stack.contains(name) {
+            stack.push(name.clone());
+            return Err(DomainError::InvalidTemplate(format!(
+                "partials include each other: {}",
+                stack.join(" -> ")
+            )));
+        }
+        let partial = resolve(name).ok_or_else(|| {
+            DomainError::InvalidTemplate(format!(
+                "unknown partial '{name}' at {}:{}",
+                position.line, position.column
+            ))
+        })?;
+
+        stack.push(name.clone());
+        let partial = expand_tokens(&partial, &lex(&partial)?, resolve, stack)?;
+        stack.pop();
+
+        out.push_str(&source[copied..span.start]);
+        let tag_start = span.start + source[span.start..].find(*raw).unwrap_or_default();
+        if standalone_bounds(source, 0, tag_start, tag_start + raw.len()).is_none() {
+            // Inline: the tag is replaced in place.
+            out.push_str(partial.strip_suffix('\n').unwrap_or(&partial));
+        } else {
+            // Standalone: the tag's line is replaced by the indented partial.
+            let indent = &source[span.start..tag_start];
+            for line in partial.split_inclusive('\n') {
+                if line != "\n" {
+                    out.push_str(indent);
+                }
+                out.push_str(line);
+            }
+            if source[..span.end].ends_with('\n') && !partial.ends_with('\n') {
+                out.push('\n');
+            }
+        }
+        copied = span.end;
+    }
+
+    out.push_str(&source[copied..]);
+    Ok(out)
+}
+
+// ── Lexer ─────────────────────────────────────────────────────────────────────
+
+#[derive(Debug)]
+enum Token<'a> {
+    Text(&'a str),
+    /// Escaped text (`\{{` or a `{{{{raw}}}}` block body), emitted verbatim.
+    Raw(&'a str),
+    Tag {
+        kind: TagKind,
+        raw: &'a str,
+        position: Position,
+        /// Source range the tag replaces, including its whole line when it
+        /// stands alone.
+        span: Range<usize>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TagKind {
+    Variable {
+        name: String,
+        filters: Vec<Filter>,
+    },
+    If(String),
+    Unless(String),
+    Each {
+        collection: String,
+        alias: Option<String>,
+    },
+    ElseIf(String),
+    Else,
+    EndIf,
+    EndUnless,
+    EndEach,
+    /// `{{> name}}`, expanded by [`expand_partials`] before parsing.
+    Partial(String),
+}
+
+impl TagKind {
+    /// Block and partial tags are subject to the standalone-line rule;
+    /// variables are not.
+    fn is_block(&self) -> bool {
+        !matches!(self, Self::Variable { .. })
+    }
+}
+
+const RAW_OPEN: &str = "{{{{raw}}}}";
+const RAW_CLOSE: &str = "{{{{/raw}}}}";
+
+/// Whether `source` contains anything the renderer acts on: tags, escapes or
+/// raw blocks. Malformed Scarff syntax counts too, so that rendering reports it.
+pub(crate) fn contains_syntax(source: &str) -> bool {
+    match lex(source) {
+        Ok(tokens) => tokens.iter().any(|t| !matches!(t, Token::Text(_))),
+        Err(_) => true,
+    }
+}
+
+fn lex(source: &str) -> Result<Vec<Token<'_>>, DomainError> {
+    let bytes = source.as_bytes();
+    let mut tokens = Vec::new();
+    let mut lines = LineTracker::default();
+
+    // `text_start` marks the beginning of the pending text run; `cursor` is
+    // where the next `{{` search begins (they differ after skipping a tag
+    // that turned out not to be Scarff syntax).
+    let mut text_start = 0;
+    let mut cursor = 0;
+
+    while let Some(found) = source[cursor..].find("{{") {
+        let found = cursor + found;
+
+        // `\{{` → literal `{{`; the backslash is dropped.
+        if found > 0 && bytes[found - 1] == b'\\' {
+            if found - 1 > text_start {
+                tokens.push(Token::Text(&source[text_start..found - 1]));
+            }
+            tokens.push(Token::Raw(&source[found..found + 2]));
+            text_start = found + 2;
+            cursor = found + 2;
+            continue;
+        }
+
+        // `{{{{raw}}}} … {{{{/raw}}}}` → body emitted verbatim.
+        if source[found..].starts_with(RAW_OPEN) {
+            let open_end = found + RAW_OPEN.len();
+            let Some(close) = source[open_end..].find(RAW_CLOSE).map(|i| open_end + i) else {
+                return Err(syntax_error(
+                    lines.position(source, found),
+                    format!("{RAW_OPEN} block is never closed; add {RAW_CLOSE}"),
+                ));
+            };
+            let close_end = close + RAW_CLOSE.len();
+
+            let (text_end, body_start) =
+                standalone_bounds(source, text_start, found, open_end).unwrap_or((found, open_end));
+            let (body_end, resume) = standalone_bounds(source, body_start, close, close_end)
+                .unwrap_or((close, close_end));
+
+            if text_end > text_start {
+                tokens.push(Token::Text(&source[text_start..text_end]));
+            }
+            tokens.push(Token::Raw(&source[body_start..body_end]));
+            text_start = resume;
+            cursor = resume;
+            continue;
+        }
+
+        let mut open = found;
+        // `{{{NAME}}}` → the tag is the innermost `{{NAME}}`; extra braces are text.
+        while bytes.get(open + 2) == Some(&b'{') {
+            open += 1;
+        }
+
+        let Some(close) = source[open + 2..].find("}}").map(|i| open + 2 + i) else {
+            break; // No closing braces anywhere after this point.
+        };
+        let end = close + 2;
+        let inner = source[open + 2..close].trim();
+
+        let kind = match classify(inner) {
+            Ok(Some(kind)) => kind,
+            Ok(None) => {
+                // Not our syntax — keep it in the text run and move on.
+                cursor = open + 2;
+                continue;
+            }
+            Err(message) => return Err(syntax_error(lines.position(source, open), message)),
+        };
+
+        let position = lines.position(source, open);
+        let (text_end, resume) = if kind.is_block() {
+            standalone_bounds(source, text_start, open, end).unwrap_or((open, end))
+        } else {
+            (open, end)
+        };
+
+        if text_end > text_start {
+            tokens.push(Token::Text(&source[text_start..text_end]));
+        }
+        tokens.push(Token::Tag {
+            kind,
+            raw: &source[open..end],
+            position,
+            span: text_end..resume,
+        });
+
+        text_start = resume;
+        cursor = resume;
+    }
+
+    if text_start < source.len() {
+        tokens.push(Token::Text(&source[text_start..]));
+    }
+
+    Ok(tokens)
+}
+
+/// Classify the trimmed content of a `{{ … }}` tag.
+///
+/// - `Ok(Some(kind))` — recognised Scarff syntax
+/// - `Ok(None)` — foreign syntax, to be left untouched
+/// - `Err(msg)` — a Scarff keyword used incorrectly
+fn classify(inner: &str) -> Result<Option<TagKind>, String> {
+    if let Some(rest) = inner.strip_prefix('>') {
+        return partial_name(rest.trim()).map(|name| Some(TagKind::Partial(name)));
+    }
+
+    if let Some(rest) = inner.strip_prefix('#') {
+        let (keyword, argument) = split_keyword(rest);
+        let build = match keyword {
+            "if" => TagKind::If,
+            "unless" => TagKind::Unless,
+            "each" => return each_argument(argument).map(Some),
+            _ => return Ok(None),
+        };
+        return condition_argument(keyword, argument).map(|name| Some(build(name)));
+    }
+
+    if let Some(rest) = inner.strip_prefix('/') {
+        return Ok(match rest.trim() {
+            "if" => Some(TagKind::EndIf),
+            "unless" => Some(TagKind::EndUnless),
+            "each" => Some(TagKind::EndEach),
+            _ => None,
+        });
+    }
+
+    if inner == "else" {
+        return Ok(Some(TagKind::Else));
+    }
+    if let Some(rest) = inner.strip_prefix("else ") {
+        let (keyword, argument) = split_keyword(rest.trim_start());
+        if keyword == "if" {
+            return condition_argument("else if", argument).map(|name| Some(TagKind::ElseIf(name)));
+        }
+        return Ok(None);
+    }
+
+    variable_expression(inner)
+}
+
+/// Parse `NAME` or `NAME | filter | filter("arg")`.
+///
+/// Anything not starting with an identifier is foreign syntax (`Ok(None)`);
+/// once a pipe follows an identifier, malformed filters are errors.
+fn variable_expression(inner: &str) -> Result<Option<TagKind>, String> {
+    let segments = split_pipes(inner)?;
+    let name = segments[0].trim();
+    if !is_identifier(name) {
+        return Ok(None);
+    }
+
+    let filters = segments[1..]
+        .iter()
+        .map(|segment| parse_filter(segment.trim()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Some(TagKind::Variable {
+        name: name.to_string(),
+        filters,
+    }))
+}
+
+/// Split on `|` outside of double- or single-quoted strings.
+fn split_pipes(inner: &str) -> Result<Vec<&str>, String> {
+    let mut segments = Vec::new();
+    let mut quote = None;
+    let mut start = 0;
+    let mut escaped = false;
+
+    for (i, c) in inner.char_indices() {
+        match (quote, c) {
+            (Some(_), '\\') if !escaped => {
+                escaped = true;
+                continue;
+            }
+            (Some(q), c) if c == q && !escaped => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '|') => {
+                segments.push(&inner[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+        escaped = false;
+    }
+
+    if quote.is_some() {
+        return Err(format!("unterminated string in '{inner}'"));
+    }
+    segments.push(&inner[start..]);
+    Ok(segments)
+}
+
+/// Parse `name` or `name("arg", ...)` and check it against the known filters.
+fn parse_filter(segment: &str) -> Result<Filter, String> {
+    let (name, args) = match segment.split_once('(') {
+        None => (segment, Vec::new()),
+        Some((name, rest)) => {
+            let Some(args) = rest.trim_end().strip_suffix(')') else {
+                return Err(format!("missing ')' in filter '{segment}'"));
+            };
+            (name.trim_end(), parse_arguments(args)?)
+        }
+    };
+
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(format!("invalid filter '{segment}'"));
+    }
+
+    let filter = Filter {
+        name: name.to_string(),
+        args,
+    };
+    filters::check(&filter)?;
+    Ok(filter)
+}
+
+/// Parse a comma-separated list of quoted string literals.
+fn parse_arguments(args: &str) -> Result<Vec<String>, String> {
+    let mut values = Vec::new();
+    let mut chars = args.trim().chars().peekable();
+
+    while let Some(&quote) = chars.peek() {
+        if quote != '"' && quote != '\'' {
+            return Err(format!(
+                "filter arguments must be quoted strings, found '{}'",
+                args.trim()
+            ));
+        }
+        chars.next();
+
+        let mut value = String::new();
+        loop {
+            match chars.next() {
+                Some('\\') => match chars.next() {
+                    Some(c) => value.push(c),
+                    None => return Err("unterminated string in filter argument".into()),
+                },
+                Some(c) if c == quote => break,
+                Some(c) => value.push(c),
+                None => return Err("unterminated string in filter argument".into()),
+            }
+        }
+        values.push(value);
+
+        while chars.peek().is_some_and(|c| c.is_whitespace()) {
+            chars.next();
+        }
+        match chars.next() {
+            None => break,
+            Some(',') => {
+                while chars.peek().is_some_and(|c| c.is_whitespace()) {
+                    chars.next();
+                }
+            }
+            Some(c) => return Err(format!("unexpected '{c}' in filter arguments")),
+        }
+    }
+
+    Ok(values)
+}
+
+/// Partial names are relative paths under the partials directory:
+/// `rustfmt.toml`, `ci/rust.yml`.
+fn partial_name(name: &str) -> Result<String, String> {
+    if name.is_empty() {
+        return Err("{{>}} requires a partial name".into());
+    }
+    let valid = name.split('/').all(|segment| {
+        !segment.is_empty()
+            && segment != ".."
+            && segment
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+    });
+    if !valid {
+        return Err(format!(
+            "invalid partial name '{name}'; expected a relative path like `ci/rust.yml`"
+        ));
+    }
+    Ok(name.to_string())
+}
+
+fn split_keyword(s: &str) -> (&str, &str) {
+    match s.split_once(char::is_whitespace) {
+        Some((keyword, rest)) => (keyword, rest.trim()),
+        None => (s, ""),
+    }
+}
+
+fn condition_argument(keyword: &str, argument: &str) -> Result<String, String> {
+    if argument.is_empty() {
+        return Err(format!("{{{{#{keyword}}}}} requires a variable name"));
+    }
+    if !is_identifier(argument) {
+        return Err(format!(
+            "invalid condition '{argument}' in {{{{#{keyword}}}}}; expected a variable name"
+        ));
+    }
+    Ok(argument.to_string())
+}
+
+/// Parse `COLLECTION` or `COLLECTION as ITEM`.
+fn each_argument(argument: &str) -> Result<TagKind, String> {
+    let mut parts = argument.split_whitespace();
+    let (collection, alias) = match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(collection), None, _, _) => (collection, None),
+        (Some(collection), Some("as"), Some(alias), None) => (collection, Some(alias)),
+        (None, ..) => return Err("{{#each}} requires a variable name".into()),
+        _ => {
+            return Err(format!(
+                "invalid {{{{#each {argument}}}}}; expected `{{{{#each NAME}}}}` or `{{{{#each NAME as ITEM}}}}`"
+            ));
+        }
+    };
+
+    if !is_identifier(collection) {
+        return Err(format!(
+            "invalid collection '{collection}' in {{{{#each}}}}; expected a variable name"
+        ));
+    }
+    if let Some(alias) = alias
+        && (!is_identifier(alias) || alias.contains('.'))
+    {
+        return Err(format!("invalid loop variable '{alias}' in {{{{#each}}}}"));
+    }
+
+    Ok(TagKind::Each {
+        collection: collection.to_string(),
+        alias: alias.map(str::to_string),
+    })
+}
+
+/// Loop metadata available inside `{{#each}}`.
+const LOOP_VARIABLES: [&str; 4] = ["@index", "@first", "@last", "@key"];
+
+/// Variable names: a letter or `_`, then letters, digits, `_`, `-` or `.`,
+/// plus the `@`-prefixed loop variables.
+fn is_identifier(s: &str) -> bool {
+    if LOOP_VARIABLES.contains(&s) {
+        return true;
+    }
+    let mut chars = s.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+/// If the tag spanning `open..end` is alone on its line, return the range to
+/// cut instead: `(end of preceding text, resume offset after the newline)`.
+///
+/// This keeps block tags from leaving blank lines behind:
+///
+/// ```text
+/// [dependencies]
+/// {{#if DATABASE}}      ← whole line removed
+/// sqlx = "0.8"
+/// {{/if}}               ← whole line removed
+/// ```
+fn standalone_bounds(
+    source: &str,
+    text_start: usize,
+    open: usize,
+    end: usize,
+) -> Option<(usize, usize)> {
+    let line_start = source[..open]
+        .rfind('\n')
+        .map_or(0, |i| i + 1)
+        .max(text_start);
+    if !source[line_start..open].chars().all(is_inline_space) {
+        return None;
+    }
+
+    let rest = &source[end..];
+    let (trailing, consumed) = match rest.find('\n') {
+        Some(nl) => (&rest[..nl], nl + 1),
+        None => (rest, rest.len()),
+    };
+    if !trailing.chars().all(|c| is_inline_space(c) || c == '\r') {
+        return None;
+    }
+
+    Some((line_start, end + consumed))
+}
+
+fn is_inline_space(c: char) -> bool {
+    c == ' ' || c == '\t'
+}
+
+/// Incremental offset → line/column conversion (linear over the whole lex).
+#[derive(Default)]
+struct LineTracker {
+    offset: usize,
+    line: usize,
+    line_start: usize,
+}
+
+impl LineTracker {
+    fn position(&mut self, source: &str, offset: usize) -> Position {
+        for (i, b) in source.as_bytes()[self.offset..offset].iter().enumerate() {
+            if *b == b'\n' {
+                self.line += 1;
+                self.line_start = self.offset + i + 1;
+            }
+        }
+        self.offset = offset;
+        Position {
+            line: self.line + 1,
+            column: source[self.line_start..offset].chars().count() + 1,
+        }
+    }
+}
+
+// ── Parser ────────────────────────────────────────────────────────────────────
+
+struct Parser<'a> {
+    tokens: Vec<Token<'a>>,
+    cursor: usize,
+}
+
+/// The tag that ended a sequence (`{{else}}`, `{{/if}}`…).
+struct Stop {
+    kind: TagKind,
+    tag: String,
+    position: Position,
+}
+
+impl Parser<'_> {
+    /// Parse nodes until a branch/closing tag or end of input.
+    fn parse_sequence(&mut self) -> Result<(Vec<Node>, Option<Stop>), DomainError> {
+        let mut nodes = Vec::new();
+
+        while let Some(token) = self.tokens.get(self.cursor) {
+            self.cursor += 1;
+            match token {
+                Token::Text(text) | Token::Raw(text) => nodes.push(Node::Text((*text).to_string())),
+                Token::Tag {
+                    kind,
+                    raw,
+                    position,
+                    ..
+                } => {
+                    let (kind, raw, position) = (kind.clone(), raw.to_string(), *position);
+                    match kind {
+                        TagKind::Variable { name, filters } => nodes.push(Node::Variable {
+                            name,
+                            filters,
+                            raw,
+                            position,
+                        }),
+                        TagKind::If(variable) => {
+                            let condition = Condition {
+                                variable,
+                                negated: false,
+                            };
+                            nodes.push(self.parse_conditional(
+                                condition,
+                                TagKind::EndIf,
+                                position,
+                            )?);
+                        }
+                        TagKind::Unless(variable) => {
+                            let condition = Condition {
+                                variable,
+                                negated: true,
+                            };
+                            nodes.push(self.parse_conditional(
+                                condition,
+                                TagKind::EndUnless,
+                                position,
+                            )?);
+                        }
+                        TagKind::Each { collection, alias } => {
+                            nodes.push(self.parse_each(collection, alias, position)?);
+                        }
+                        // Partials are inlined when the template is loaded.
+                        TagKind::Partial(name) => {
+                            return Err(syntax_error(
+                                position,
+                                format!("partial '{name}' is not available here"),
+                            ));
+                        }
+                        TagKind::Else
+                        | TagKind::ElseIf(_)
+                        | TagKind::EndIf
+                        | TagKind::EndUnless
+                        | TagKind::EndEach => {
+                            let tag = raw.trim_start_matches('{').trim_end_matches('}').trim();
+                            let stop = Stop {
+                                tag: tag.to_string(),
+                                kind,
+                                position,
+                            };
+                            return Ok((nodes, Some(stop)));
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok((nodes, None))
+    }
+
+    /// Parse the branches of a conditional whose opening tag was just consumed.
+    fn parse_conditional(
+        &mut self,
+        condition: Condition,
+        closing: TagKind,
+        opened_at: Position,
+    ) -> Result<Node, DomainError> {
+        let (then_branch, stop) = self.parse_sequence()?;
+
+        let else_branch = match stop {
+            Some(Stop { kind, .. }) if kind == closing => Vec::new(),
+            Some(Stop {
+                kind: TagKind::Else,
+                ..
+            }) => {
+                let (nodes, stop) = self.parse_sequence()?;
+                match stop {
+                    Some(Stop { kind, .. }) if kind == closing => nodes,
+                    Some(stop) => return Err(mismatched(&stop, &closing)),
+                    None => return Err(unclosed(opened_at, &closing)),
+                }
+            }
+            Some(Stop {
+                kind: TagKind::ElseIf(variable),
+                position,
+                ..
+            }) => {
+                // `{{else if X}}` opens a nested conditional sharing our closing tag.
+                let nested = Condition {
+                    variable,
+                    negated: false,
+                };
+                vec![self.parse_conditional(nested, closing, position)?]
+            }
+            Some(stop) => return Err(mismatched(&stop, &closing)),
+            None => return Err(unclosed(opened_at, &closing)),
+        };
+
+        Ok(Node::Conditional {
+            condition,
+            then_branch,
+            else_branch,
+        })
+    }
+
+    /// Parse the body (and optional `{{else}}`) of an `{{#each}}` loop.
+    fn parse_each(
+        &mut self,
+        collection: String,
+        alias: Option<String>,
+        opened_at: Position,
+    ) -> Result<Node, DomainError> {
+        let closing = TagKind::EndEach;
+        let (body, stop) = self.parse_sequence()?;
+
+        let else_branch = match stop {
+            Some(Stop { kind, .. }) if kind == closing => Vec::new(),
+            Some(Stop {
+                kind: TagKind::Else,
+                ..
+            }) => {
+                let (nodes, stop) = self.parse_sequence()?;
+                match stop {
+                    Some(Stop { kind, .. }) if kind == closing => nodes,
+                    Some(stop) => return Err(mismatched(&stop, &closing)),
+                    None => return Err(unclosed(opened_at, &closing)),
+                }
+            }
+            Some(stop) => return Err(mismatched(&stop, &closing)),
+            None => return Err(unclosed(opened_at, &closing)),
+        };
+
+        Ok(Node::Each {
+            collection,
+            alias,
+            body,
+            else_branch,
+        })
+    }
+}
+
+fn closing_name(closing: &TagKind) -> &'static str {
+    match closing {
+        TagKind::EndUnless => "unless",
+        TagKind::EndEach => "each",
+        _ => "if",
+    }
+}
+
+fn unclosed(position: Position, closing: &TagKind) -> DomainError {
+    let name = closing_name(closing);
+    syntax_error(
+        position,
+        format!("{{{{#{name}}}}} block is never closed; add {{{{/{name}}}}}"),
+    )
+}
+
+fn mismatched(stop: &Stop, closing: &TagKind) -> DomainError {
+    let name = closing_name(closing);
+    syntax_error(
+        stop.position,
+        format!("unexpected {{{{{}}}}}; expected {{{{/{name}}}}}", stop.tag),
+    )
+}
+
+fn syntax_error(position: Position, message: String) -> DomainError {
+    DomainError::TemplateSyntax {
+        line: position.line,
+        column: position.column,
+        message,
+    }
+}
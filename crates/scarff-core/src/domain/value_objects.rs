@@ -278,6 +278,9 @@ pub enum Architecture {
 }
 
 impl Architecture {
+    /// Every architecture, in the order offered to users.
+    pub const ALL: [Self; 4] = [Self::Layered, Self::Clean, Self::FeatureModular, Self::Mvc];
+
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::Layered => "layered",
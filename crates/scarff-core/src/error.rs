@@ -80,6 +80,8 @@ pub enum ErrorCategory {
     Compatibility,
     NotFound,
     Configuration,
+    /// A program scarff ran on the user's behalf (a hook) failed.
+    External,
     Internal,
 }
 
@@ -42,7 +42,7 @@
 //!
 //! ```rust,ignore
 //! use scarff_core::{
-//!     application::{ScaffoldService, TemplateService},
+//!     application::{ScaffoldOptions, ScaffoldService, TemplateService},
 //!     domain::{Target, Language, ProjectKind},
 //! };
 //!
@@ -55,7 +55,9 @@
 //!
 //! // 2. Use application service (with injected adapters)
 //! let service = ScaffoldService::new(store, filesystem, renderer);
-//! service.scaffold(target, "my-project", "./output").unwrap();
+//! service
+//!     .scaffold(target, "my-project", "./output", &ScaffoldOptions::new())
+//!     .unwrap();
 //! ```
 
 // Re-export domain layer (stable, well-defined API)
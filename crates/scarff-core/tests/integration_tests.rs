@@ -53,7 +53,7 @@
 //         .metadata(TemplateMetadata::new("General"))
 //         .add_node(TemplateNode::File(FileSpec::new(
 //             "general.txt",
-//             TemplateContent::Literal(TemplateSource::Static("general")),
+//             TemplateContent::Literal(TemplateSource::from("general")),
 //         )))
 //         .build()
 //         .unwrap();
@@ -69,7 +69,7 @@
 //         .metadata(TemplateMetadata::new("Specific"))
 //         .add_node(TemplateNode::File(FileSpec::new(
 //             "specific.txt",
-//             TemplateContent::Literal(TemplateSource::Static("specific")),
+//             TemplateContent::Literal(TemplateSource::from("specific")),
 //         )))
 //         .build()
 //         .unwrap();